@@ -0,0 +1,46 @@
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ptr::NonNull,
+    rc::Rc,
+};
+
+/// The [`Allocator`] used for the [`Node`](crate::Node)s and **children** lists of a [`Tree`](crate::Tree).
+///
+/// Instead of making every type generic over an [`Allocator`], each [`Node`](crate::Node)'s allocation holds a *type-erased* handle to the [`Allocator`]
+/// it was allocated with, so [`Node`](crate::Node)s from different [`Allocator`]s can be mixed in the same [`Tree`](crate::Tree).
+/// The [`Allocator`] is *reference counted*, so it is dropped (e.g. freeing a whole bump arena) when the last [`Node`](crate::Node) allocated with it is dropped.
+///
+/// The [`Default`] handle uses the [`Global`] allocator.
+///
+/// # Which [`Node`](crate::Node)s use the [`Allocator`]
+/// A [`Tree`](crate::Tree) is not generic over its [`Allocator`], so only these methods know which one to use:
+/// - [`NodeBuilder::build_in()`](crate::NodeBuilder::build_in()) allocates all the [`Node`](crate::Node)s with the [`Allocator`] it is given.
+/// - Copies and conversions of a [`Node`](crate::Node) are allocated with the same [`Allocator`] as the original [`Node`](crate::Node):
+///   [`Clone`] (and [`Node::clone_deep()`](crate::Node::clone_deep())), [`Tree::map()`](crate::Tree::map()), [`Tree::map_meta()`](crate::Tree::map_meta()),
+///   [`Tree::map_ref()`](crate::Tree::map_ref()) and [`Tree::zip()`](crate::Tree::zip()) (with the [`Allocator`]s of `self`).
+/// - [`Tree::fill_like()`](crate::Tree::fill_like()) and [`Tree::unfold()`](crate::Tree::unfold()) allocate the **children** with their **parent**'s [`Allocator`].
+///
+/// Appending, inserting, detaching or moving [`Node`](crate::Node)s never reallocates them, so they keep the [`Allocator`] they were allocated with.
+/// Every other method that creates [`Node`](crate::Node)s (e.g. [`NodeBuilder::build()`](crate::NodeBuilder::build()) or the conversions from other formats)
+/// uses the [`Global`] allocator.
+#[derive(Clone, Default)]
+pub struct TreeAllocator(Option<Rc<dyn Allocator>>);
+impl TreeAllocator {
+    pub fn new(alloc: impl Allocator + 'static) -> Self {
+        Self(Some(Rc::new(alloc)))
+    }
+}
+unsafe impl Allocator for TreeAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        match &self.0 {
+            Some(alloc) => alloc.allocate(layout),
+            None => Global.allocate(layout),
+        }
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        match &self.0 {
+            Some(alloc) => unsafe { alloc.deallocate(ptr, layout) },
+            None => unsafe { Global.deallocate(ptr, layout) },
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! A [`Tree`] that stores all of its [`Node`](crate::Node)s contiguously in a single [`Vec`] (an *arena*),
+//! which is better for *cache locality* with very large [`Tree`]s than allocating every [`Node`](crate::Node) separately.
+//!
+//! [`Node`](crate::Node)s are referenced with a [`NodeId`] instead of a pointer.
+//! Every [`NodeId`] has a *generation*, so when a [`Node`](crate::Node) is removed and its slot is reused by a new [`Node`](crate::Node),
+//! the old [`NodeId`]s don't refer to the new [`Node`](crate::Node); they are just *stale* and all the methods that take them return [`None`].
+//!
+//! # Example
+//! ```
+//! # use tree_struct::arena::Tree;
+//! let mut tree = Tree::new("parent");
+//! let a = tree.append_child(tree.root(), "child a").unwrap();
+//! let b = tree.append_child(tree.root(), "child b").unwrap();
+//! tree.append_child(a, "child c").unwrap();
+//!
+//! let detached = tree.detach(a).unwrap();
+//! assert_eq!(detached.len(), 2);
+//! assert!(tree.get(a).is_none());
+//! assert_eq!(tree.children(tree.root()).unwrap(), [b]);
+//! ```
+use std::{
+    collections::VecDeque,
+    ops::{Index, IndexMut},
+};
+
+/// References a [`Node`](crate::Node) in an *arena* [`Tree`]. Obtained when adding a [`Node`](crate::Node) to a [`Tree`].
+///
+/// A [`NodeId`] should only be used with the [`Tree`] it came from,
+/// but using it with another [`Tree`] is safe, as it just finds a different [`Node`](crate::Node) (or none).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u64,
+}
+
+struct Entry<T> {
+    content: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+struct Slot<T> {
+    /// Incremented every time the slot is freed.
+    generation: u64,
+    entry: Option<Entry<T>>,
+}
+
+/// A [`Tree`] that stores all of its [`Node`](crate::Node)s in an *arena*. See the [module documentation](self).
+pub struct Tree<T> {
+    slots: Vec<Slot<T>>,
+    /// The indices of the slots that have no [`Entry`].
+    free: Vec<usize>,
+    root: NodeId,
+    len: usize,
+}
+impl<T> Tree<T> {
+    /// A [`Tree`] with only a **root**.
+    pub fn new(root: T) -> Self {
+        let mut tree = Self {
+            slots: vec![],
+            free: vec![],
+            root: NodeId { index: 0, generation: 0 },
+            len: 0,
+        };
+        tree.root = tree.alloc(root, None);
+        tree
+    }
+
+    fn alloc(&mut self, content: T, parent: Option<NodeId>) -> NodeId {
+        let entry = Some(Entry {
+            content,
+            parent,
+            children: vec![],
+        });
+        self.len += 1;
+
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.entry = entry;
+                NodeId { index, generation: slot.generation }
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, entry });
+                NodeId {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+    fn entry(&self, id: NodeId) -> Option<&Entry<T>> {
+        self.slots
+            .get(id.index)
+            .filter(|slot| slot.generation == id.generation)?
+            .entry
+            .as_ref()
+    }
+    fn entry_mut(&mut self, id: NodeId) -> Option<&mut Entry<T>> {
+        self.slots
+            .get_mut(id.index)
+            .filter(|slot| slot.generation == id.generation)?
+            .entry
+            .as_mut()
+    }
+
+    #[inline]
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+    /// The number of [`Node`](crate::Node)s in the [`Tree`]. This is `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Always `false`, because a [`Tree`] always has a **root**.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Whether **id** refers to a [`Node`](crate::Node) in the [`Tree`] (i.e. it is not *stale*).
+    #[inline]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.entry(id).is_some()
+    }
+
+    /// Get the **content** of a [`Node`](crate::Node). Returns [`None`] if **id** is *stale*.
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.entry(id).map(|entry| &entry.content)
+    }
+    /// Get the **content** of a [`Node`](crate::Node) mutably. Returns [`None`] if **id** is *stale*.
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.entry_mut(id).map(|entry| &mut entry.content)
+    }
+    /// Returns [`None`] if **id** is *stale* or if it is the **root**.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.entry(id)?.parent
+    }
+    /// Returns [`None`] if **id** is *stale*.
+    pub fn children(&self, id: NodeId) -> Option<&[NodeId]> {
+        self.entry(id).map(|entry| entry.children.as_slice())
+    }
+
+    /// Pushes a new [`Node`](crate::Node) with **content** to the end of **parent**'s *children*.
+    /// Returns [`None`] if **parent** is *stale*.
+    #[inline]
+    pub fn append_child(&mut self, parent: NodeId, content: T) -> Option<NodeId> {
+        let index = self.entry(parent)?.children.len();
+        self.insert_child(parent, index, content)
+    }
+    /// Inserts a new [`Node`](crate::Node) with **content** to **parent**'s *children* at **index**.
+    /// Returns [`None`] if **parent** is *stale* or **index** is out of bounds.
+    pub fn insert_child(&mut self, parent: NodeId, index: usize, content: T) -> Option<NodeId> {
+        if index > self.entry(parent)?.children.len() {
+            return None;
+        }
+        let child = self.alloc(content, Some(parent));
+        self.entry_mut(parent).unwrap().children.insert(index, child);
+        Some(child)
+    }
+
+    /// Removes the [`Node`](crate::Node) (and its subtree) from the [`Tree`], and returns it as a new [`Tree`].
+    /// All the [`NodeId`]s of the removed [`Node`](crate::Node)s become *stale*.
+    ///
+    /// Returns [`None`] if **id** is *stale* or if it is the **root**.
+    pub fn detach(&mut self, id: NodeId) -> Option<Self> {
+        let parent = self.parent(id)?;
+        self.entry_mut(parent).unwrap().children.retain(|child| *child != id);
+
+        let mut detached = None::<Self>;
+        let mut queue = VecDeque::from([(None, id)]);
+        while let Some((new_parent, id)) = queue.pop_front() {
+            let slot = &mut self.slots[id.index];
+            let entry = slot.entry.take().unwrap();
+            slot.generation += 1;
+            self.free.push(id.index);
+            self.len -= 1;
+
+            let new_id = match (&mut detached, new_parent) {
+                (Some(detached), Some(new_parent)) => detached.append_child(new_parent, entry.content).unwrap(),
+                _ => detached.insert(Self::new(entry.content)).root,
+            };
+            queue.extend(entry.children.into_iter().map(|child| (Some(new_id), child)));
+        }
+
+        detached
+    }
+
+    /// Iterate over the [`NodeId`]s of the [`Tree`] using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        let mut queue = VecDeque::from([self.root]);
+        std::iter::from_fn(move || {
+            let id = queue.pop_front()?;
+            queue.extend(self.children(id).unwrap());
+            Some(id)
+        })
+    }
+    /// Iterate over the [`NodeId`]s of the [`Tree`] using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        let mut stack = vec![self.root];
+        std::iter::from_fn(move || {
+            let id = stack.pop()?;
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(self.children(id).unwrap().iter().rev());
+            Some(id)
+        })
+    }
+}
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    /// # Panics
+    /// If **id** is *stale*.
+    fn index(&self, id: NodeId) -> &Self::Output {
+        self.get(id).expect("NodeId is stale")
+    }
+}
+impl<T> IndexMut<NodeId> for Tree<T> {
+    /// # Panics
+    /// If **id** is *stale*.
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        self.get_mut(id).expect("NodeId is stale")
+    }
+}
+
+impl<T> From<crate::Tree<T>> for Tree<T> {
+    /// Moves all the [`Node`](crate::Node)s into an *arena*, in **Breadth-First** order.
+    fn from(tree: crate::Tree<T>) -> Self {
+        let mut queue = VecDeque::new();
+        let (content, children) = crate::Node::into_parts(tree.root);
+        let mut arena = Self::new(content);
+        queue.extend(children.into_iter().map(|child| (arena.root, child)));
+
+        while let Some((parent, tree)) = queue.pop_front() {
+            let (content, children) = crate::Node::into_parts(tree.root);
+            let id = arena.append_child(parent, content).unwrap();
+            queue.extend(children.into_iter().map(|child| (id, child)));
+        }
+
+        arena
+    }
+}
+impl<T> From<Tree<T>> for crate::Tree<T> {
+    fn from(mut tree: Tree<T>) -> Self {
+        // Collect the shape first, then move the contents out of the arena.
+        let mut entries = vec![];
+        let mut queue = VecDeque::from([(None, tree.root)]);
+        while let Some((parent, id)) = queue.pop_front() {
+            let index = entries.len();
+            entries.push((parent, id.index));
+            queue.extend(tree.children(id).unwrap().iter().map(|child| (Some(index), *child)));
+        }
+
+        let entries = entries
+            .into_iter()
+            .map(|(parent, index)| (parent, tree.slots[index].entry.take().unwrap().content))
+            .collect();
+        // The entries are in Breadth-First order, so every parent comes before its children.
+        Self::from_flat(entries).unwrap()
+    }
+}
+impl<T> std::fmt::Debug for Tree<T>
+where T: std::fmt::Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tree")
+            .field("len", &self.len)
+            .field("nodes", &self.iter_bfs().map(|id| &self[id]).collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,199 @@
+//! A *reference counted* [`Tree`] like the one in [`sync`](crate::sync), but each [`Node`] is behind a [`tokio`] [`RwLock`],
+//! so locking it is `async`.
+//!
+//! Waiting for a lock doesn't block the thread (and the runtime) like the [`sync`](crate::sync) locks do,
+//! and the guards returned by [`Node::content()`] and [`Node::content_mut()`] can be held across `.await` points.
+//!
+//! Like in the other *reference counted* modules, a method never holds the lock of a child while locking its parent,
+//! so concurrent tasks can't deadlock each other.
+//!
+//! # Example
+//! ```
+//! # use tree_struct::async_rc::Node;
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let tree = Node::builder("parent").child(Node::builder("child a")).build();
+//! let root = tree.root();
+//! root.append_child(Node::builder("child b").build()).await;
+//!
+//! let child = root.children().await[1].ref_clone();
+//! *child.content_mut().await = "child B";
+//! assert_eq!(*child.content().await, "child B");
+//! assert!(child.parent().await.unwrap().is_same_as(&root));
+//!
+//! let detached = child.detach().await.unwrap();
+//! assert_eq!(root.children().await.len(), 1);
+//! assert!(detached.root().parent().await.is_none());
+//! # });
+//! ```
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+use tokio::sync::{RwLock, RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
+
+/// Helper struct to build a [`Tree`] of [`Node`]s. See [`crate::NodeBuilder`].
+#[derive(Debug)]
+pub struct NodeBuilder<T> {
+    pub content: T,
+    pub children: Vec<NodeBuilder<T>>,
+}
+impl<T> NodeBuilder<T> {
+    #[inline]
+    pub fn new(content: T) -> Self {
+        Self {
+            content,
+            children: vec![],
+        }
+    }
+    #[inline]
+    pub fn child(mut self, child: Self) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Builds the [`Tree`] without recursion.
+    /// The [`Node`]s are not shared yet, so this doesn't need to wait for any lock.
+    pub fn build(self) -> Tree<T> {
+        let root = Arc::new(RwLock::new(InnerNode::new(self.content)));
+        let mut stack = vec![(Arc::clone(&root), self.children)];
+
+        while let Some((parent, children)) = stack.pop() {
+            let mut built = Vec::with_capacity(children.len());
+            for builder in children {
+                let mut inner = InnerNode::new(builder.content);
+                inner.parent = Some(Arc::downgrade(&parent));
+                let child = Arc::new(RwLock::new(inner));
+                stack.push((Arc::clone(&child), builder.children));
+                built.push(Node(child));
+            }
+            parent.try_write().expect("The Node is not shared yet").children = built;
+        }
+
+        Tree { root: Node(root) }
+    }
+}
+
+struct InnerNode<T> {
+    content: T,
+    parent: Option<Weak<RwLock<Self>>>,
+    children: Vec<Node<T>>,
+}
+impl<T> InnerNode<T> {
+    fn new(content: T) -> Self {
+        Self {
+            content,
+            parent: None,
+            children: vec![],
+        }
+    }
+}
+
+/// A [`Node`] has 1 [`parent`](Self::parent()) and multiple [`children`](Self::children()).
+/// It also stores [`content`](Self::content()) of type **`T`**, behind an `async` [`RwLock`].
+pub struct Node<T>(Arc<RwLock<InnerNode<T>>>);
+impl<T> Node<T> {
+    #[inline]
+    pub fn builder(content: T) -> NodeBuilder<T> {
+        NodeBuilder::new(content)
+    }
+
+    /// Waits for a *read lock* on **self**'s **content**.
+    pub async fn content(&self) -> RwLockReadGuard<'_, T> {
+        RwLockReadGuard::map(self.0.read().await, |n| &n.content)
+    }
+    /// Waits for a *write lock* on **self**'s **content**.
+    pub async fn content_mut(&self) -> RwLockMappedWriteGuard<'_, T> {
+        RwLockWriteGuard::map(self.0.write().await, |n| &mut n.content)
+    }
+    /// Like [`Self::content()`], but returns [`None`] instead of waiting if **self** is *write-locked*.
+    #[inline]
+    pub fn try_content(&self) -> Option<RwLockReadGuard<'_, T>> {
+        self.0.try_read().ok().map(|n| RwLockReadGuard::map(n, |n| &n.content))
+    }
+
+    pub async fn parent(&self) -> Option<Self> {
+        self.0.read().await.parent.as_ref()?.upgrade().map(Self)
+    }
+    /// Allocates a *slice* of all of [`Node`]'s children, increasing all of their *reference counter*.
+    pub async fn children(&self) -> Box<[Self]> {
+        self.0.read().await.children.iter().map(Self::ref_clone).collect()
+    }
+
+    /// Pushes the **child** to the end of **self**'s *children*.
+    ///
+    /// # Panics
+    /// If **self** is in **child**'s [`Tree`], because that would create a cycle.
+    pub async fn append_child(&self, child: Tree<T>) {
+        let mut ancestor = Some(self.ref_clone());
+        while let Some(node) = ancestor {
+            assert!(!node.is_same_as(&child.root), "Can't append a Tree to one of its own Nodes");
+            ancestor = node.parent().await;
+        }
+
+        child.root.0.write().await.parent = Some(Arc::downgrade(&self.0));
+        self.0.write().await.children.push(child.root);
+    }
+    /// Removes **self** from its **parent**'s *children*, and returns it as a [`Tree`].
+    /// Returns [`None`] if **self** is a **root**.
+    pub async fn detach(&self) -> Option<Tree<T>> {
+        let parent = self.parent().await?;
+        let node = {
+            let mut parent = parent.0.write().await;
+            let index = parent.children.iter().position(|child| child.is_same_as(self))?;
+            parent.children.remove(index)
+        };
+        node.0.write().await.parent = None;
+        Some(Tree { root: node })
+    }
+
+    /// Copies the [`Node`]'s *pointer*, so both refer to the same [`Node`].
+    #[inline]
+    pub fn ref_clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+    /// Whether **self** and **other** are the same [`Node`] (not just equal).
+    #[inline]
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl<T> Debug for Node<T>
+where T: Debug {
+    /// Only prints the **content** if it is not *write-locked*, because formatting can't wait.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.try_content() {
+            Some(content) => f.debug_tuple("Node").field(&*content).finish(),
+            None => f.write_str("Node(<locked>)"),
+        }
+    }
+}
+
+/// A Tree of [`Node`]s, which owns its **root**. See [`crate::rc::Tree`].
+#[derive(Debug)]
+pub struct Tree<T> {
+    root: Node<T>,
+}
+impl<T> Tree<T> {
+    #[inline]
+    pub fn builder(content: T) -> NodeBuilder<T> {
+        NodeBuilder::new(content)
+    }
+
+    #[inline]
+    pub fn root(&self) -> Node<T> {
+        self.root.ref_clone()
+    }
+}
+impl<T> From<NodeBuilder<T>> for Tree<T> {
+    #[inline]
+    fn from(builder: NodeBuilder<T>) -> Self {
+        builder.build()
+    }
+}
+
+// Nodes and Trees can be shared between tasks on different threads.
+fn _assert_send_sync<T: Send + Sync>() {
+    fn check<S: Send + Sync>() {}
+    check::<Node<T>>();
+    check::<Tree<T>>();
+}
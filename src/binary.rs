@@ -0,0 +1,198 @@
+//! A compact binary encoding for [`Tree`]s.
+use super::*;
+
+/// A value that can be written to (and read from) the binary encoding of a [`Tree`]. See [`Tree::to_bytes()`].
+pub trait Encode: Sized {
+    /// Append the bytes of `self` to **out**.
+    fn encode(&self, out: &mut Vec<u8>);
+    /// Read a value from the start of **bytes**, and advance **bytes** past it.
+    /// Returns [`None`] if the bytes are not a valid value.
+    fn decode(bytes: &mut &[u8]) -> Option<Self>;
+}
+macro_rules! impl_encode_le {
+    ($($t:ty),*) => {
+        $(impl Encode for $t {
+            #[inline]
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes())
+            }
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                let (value, rest) = bytes.split_first_chunk()?;
+                *bytes = rest;
+                Some(Self::from_le_bytes(*value))
+            }
+        })*
+    };
+}
+impl_encode_le!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+impl Encode for bool {
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8)
+    }
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::decode(bytes)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+impl Encode for char {
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u32).encode(out)
+    }
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        char::from_u32(u32::decode(bytes)?)
+    }
+}
+impl Encode for String {
+    /// The length (as a *varint*) followed by the UTF-8 bytes.
+    fn encode(&self, out: &mut Vec<u8>) {
+        encode_varint(self.len(), out);
+        out.extend_from_slice(self.as_bytes());
+    }
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let len = decode_varint(bytes)?;
+        let (string, rest) = bytes.split_at_checked(len)?;
+        *bytes = rest;
+        String::from_utf8(string.to_vec()).ok()
+    }
+}
+impl<T> Encode for Vec<T>
+where T: Encode {
+    /// The length (as a *varint*) followed by each element.
+    fn encode(&self, out: &mut Vec<u8>) {
+        encode_varint(self.len(), out);
+        for value in self {
+            value.encode(out);
+        }
+    }
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let len = decode_varint(bytes)?;
+        // Don't trust the length for the capacity, because it could be huge.
+        let mut values = vec![];
+        for _ in 0..len {
+            values.push(T::decode(bytes)?);
+        }
+        Some(values)
+    }
+}
+
+/// Writes **value** in 7-bit groups (*LEB128*), so small numbers only take 1 byte.
+fn encode_varint(mut value: usize, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+fn decode_varint(bytes: &mut &[u8]) -> Option<usize> {
+    let mut value = 0usize;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = u8::decode(bytes)?;
+        value |= ((byte & 0x7f) as usize).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Returned by [`Tree::from_bytes()`] when the bytes are not a valid encoding of a [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The bytes ended before all the [`Node`]s were decoded.
+    UnexpectedEnd,
+    /// The *children count* or the **content** of the [`Node`] that starts at **position** is invalid (or cut off).
+    Invalid { position: usize },
+    /// There are bytes left after the whole [`Tree`] was decoded.
+    TrailingBytes { position: usize },
+}
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of the bytes"),
+            Self::Invalid { position } => write!(f, "invalid node at byte {position}"),
+            Self::TrailingBytes { position } => write!(f, "unexpected bytes after the tree at byte {position}"),
+        }
+    }
+}
+impl std::error::Error for DecodeError {}
+
+impl<T> Tree<T>
+where T: Encode {
+    /// Encodes the [`Tree`] in a compact binary format:
+    /// every [`Node`] in **Depth-First** order (pre-order), each one as its number of **children** (as a *varint*) followed by its [encoded](Encode) **content**.
+    ///
+    /// Use [`Tree::from_bytes()`] to decode it.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Node, Tree};
+    /// let tree = Node::builder(1u8).child(Node::builder(2u8)).child(Node::builder(3u8)).build();
+    /// let bytes = tree.to_bytes();
+    /// assert_eq!(bytes, [2, 1, 0, 2, 0, 3]);
+    /// assert_eq!(Tree::<u8>::from_bytes(&bytes).unwrap().iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![];
+        let mut stack = vec![self.root()];
+
+        while let Some(node) = stack.pop() {
+            let children = node.children();
+            encode_varint(children.len(), &mut out);
+            node.content.encode(&mut out);
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(children.iter().rev());
+        }
+
+        out
+    }
+    /// Decodes a [`Tree`] encoded with [`Tree::to_bytes()`].
+    /// All the bytes must be used, otherwise this returns [`DecodeError::TrailingBytes`].
+    ///
+    /// This doesn't recurse, so it is safe to use with *untrusted* input.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut rest = bytes;
+        // The Nodes that are still missing children, and how many.
+        let mut stack = Vec::<(NodeBuilder<T>, usize)>::new();
+
+        loop {
+            if rest.is_empty() {
+                return Err(DecodeError::UnexpectedEnd);
+            }
+            let invalid = DecodeError::Invalid {
+                position: bytes.len() - rest.len(),
+            };
+            let count = decode_varint(&mut rest).ok_or(invalid)?;
+            let content = T::decode(&mut rest).ok_or(invalid)?;
+            if count != 0 {
+                stack.push((NodeBuilder::new(content), count));
+                continue;
+            }
+
+            // Move the Nodes that have all their children to their parent.
+            let mut node = NodeBuilder::new(content);
+            loop {
+                match stack.last_mut() {
+                    Some((parent, remaining)) => {
+                        parent.children.push(node);
+                        *remaining -= 1;
+                        if *remaining != 0 {
+                            break;
+                        }
+                        node = stack.pop().unwrap().0;
+                    }
+                    None if rest.is_empty() => return Ok(node.build()),
+                    None => {
+                        return Err(DecodeError::TrailingBytes {
+                            position: bytes.len() - rest.len(),
+                        })
+                    }
+                }
+            }
+        }
+    }
+}
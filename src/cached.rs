@@ -0,0 +1,311 @@
+use super::*;
+use std::{
+    cell::Cell,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+/// An *approximation* of how many bytes a value owns on the heap (not counting its own [`size_of`](std::mem::size_of)).
+/// Used by [`CachedTree`] to keep track of how much memory its [`Node`]s are using.
+pub trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+macro_rules! impl_no_heap {
+    ($($t:ty),*) => {
+        $(impl HeapSize for $t {
+            #[inline]
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })*
+    };
+}
+impl_no_heap!(bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, ());
+impl HeapSize for &str {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+impl HeapSize for String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+impl<T> HeapSize for Vec<T>
+where T: HeapSize {
+    fn heap_size(&self) -> usize {
+        self.capacity() * std::mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+impl<T> HeapSize for Box<[T]>
+where T: HeapSize {
+    fn heap_size(&self) -> usize {
+        std::mem::size_of_val(&**self) + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+/// Which [`Node`]s are *evicted* first by [`CachedTree::evict_until()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EvictionPolicy {
+    /// Evict the branch that was *visited* the longest time ago.
+    /// If many were visited at the same time, the one closest to the **root** is evicted.
+    #[default]
+    LeastRecentlyVisited,
+    /// Evict the branch that uses the most memory.
+    LargestFirst,
+}
+
+/// The **content** of a [`Node`] in a [`CachedTree`], along with the bookkeeping used for *eviction*.
+pub struct Cached<T> {
+    pub content: T,
+    last_visit: Cell<u64>,
+    /// The approximate size of only this [`Node`].
+    size: usize,
+    /// The approximate size of this [`Node`] and all of its **descendants**.
+    subtree_size: usize,
+}
+impl<T> Cached<T> {
+    /// The *time* of the last visit to the [`Node`], which only means something when compared to other [`Node`]s' visits.
+    pub fn last_visit(&self) -> u64 {
+        self.last_visit.get()
+    }
+    /// The approximate number of bytes used by the [`Node`] and all of its **descendants**.
+    pub fn heap_size(&self) -> usize {
+        self.subtree_size
+    }
+}
+impl<T> Debug for Cached<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cached")
+            .field("content", &self.content)
+            .field("last_visit", &self.last_visit())
+            .field("heap_size", &self.heap_size())
+            .finish()
+    }
+}
+
+/// A [`Tree`] used as a *cache* (e.g. tile pyramids or prefix caches), which keeps track of the approximate memory used by each subtree,
+/// and can [`evict`](Self::evict_until()) branches until the whole [`Tree`] fits in a memory budget.
+///
+/// Every time a [`Node`] is obtained through the [`CachedTree`] (e.g. with [`get()`](Self::get()) or [`iter_bfs()`](Self::iter_bfs())),
+/// it is marked as *visited*, which is used by [`EvictionPolicy::LeastRecentlyVisited`].
+/// [`Node`]s are referenced by their *path*:
+/// the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**.
+///
+/// # Example
+/// ```
+/// # use tree_struct::{CachedTree, EvictionPolicy, Node};
+/// let mut cache = CachedTree::new(
+///     Node::builder(String::from("root"))
+///         .child(Node::builder("a".repeat(100)))
+///         .child(Node::builder("b".repeat(100)))
+///         .build(),
+/// );
+/// cache.get(&[1]);
+///
+/// let evicted = cache.evict_until(cache.heap_size() - 1, EvictionPolicy::LeastRecentlyVisited);
+/// assert_eq!(evicted[0].root().content, "a".repeat(100));
+/// ```
+pub struct CachedTree<T> {
+    tree: Tree<Cached<T>>,
+    clock: Cell<u64>,
+}
+impl<T> CachedTree<T>
+where T: HeapSize {
+    pub fn new(tree: Tree<T>) -> Self {
+        Self {
+            tree: Self::wrap(tree, 0),
+            clock: Cell::new(0),
+        }
+    }
+    /// Stop tracking memory and visits and get the [`Tree`] back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree.map(|cached| cached.content)
+    }
+    /// Doesn't recurse, so [`Tree`]s of any depth can be wrapped.
+    fn wrap(tree: Tree<T>, time: u64) -> Tree<Cached<T>> {
+        let tree = tree.map(|content| {
+            let size = std::mem::size_of::<Node<Cached<T>>>() + content.heap_size();
+            Cached {
+                content,
+                last_visit: Cell::new(time),
+                size,
+                subtree_size: size,
+            }
+        });
+
+        // Add the size of each subtree to its parent, starting from the deepest Nodes.
+        let nodes = tree.iter_bfs().map(Node::ptr).collect::<Vec<_>>();
+        for node in nodes.into_iter().rev() {
+            let node = unsafe { node.as_ref() };
+            if let Some(parent) = node.parent().map(Node::ptr) {
+                // The Tree is owned, and the content is not structurally pinned.
+                unsafe { (*parent.as_ptr()).content.subtree_size += node.content.subtree_size };
+            }
+        }
+
+        tree
+    }
+
+    /// The underlying [`Tree`]. Using it does *not* mark any [`Node`]s as *visited*.
+    pub fn tree(&self) -> &Tree<Cached<T>> {
+        &self.tree
+    }
+    /// The approximate number of bytes used by all the [`Node`]s of the [`Tree`].
+    pub fn heap_size(&self) -> usize {
+        self.tree.root().content.subtree_size
+    }
+
+    /// Advance the *clock*. All the [`Node`]s visited by the same call are visited at the same time.
+    fn tick(&self) -> u64 {
+        self.clock.set(self.clock.get() + 1);
+        self.clock.get()
+    }
+
+    /// Get the [`Node`] at **path**, marking it and all its *ancestors* as *visited*.
+    pub fn get(&self, path: &[usize]) -> Option<&Node<Cached<T>>> {
+        let time = self.tick();
+        let mut node = self.tree.root();
+        node.content.last_visit.set(time);
+
+        for &index in path {
+            node = node.get(index)?;
+            node.content.last_visit.set(time);
+        }
+        Some(node)
+    }
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Breadth-First Search**, marking each one as *visited* when it is yielded.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = &Node<Cached<T>>> {
+        let time = self.tick();
+        self.tree.iter_bfs().inspect(move |node| node.content.last_visit.set(time))
+    }
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Depth-First Search**, marking each one as *visited* when it is yielded.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = &Node<Cached<T>>> {
+        let time = self.tick();
+        self.tree.iter_dfs().inspect(move |node| node.content.last_visit.set(time))
+    }
+
+    fn find(&self, path: &[usize]) -> Option<NonNull<Node<Cached<T>>>> {
+        path.iter()
+            .try_fold(self.tree.root(), |node, &index| node.get(index))
+            .map(Node::ptr)
+    }
+    /// Add **delta** to the *subtree size* of **node** and all its *ancestors*, walking up through the **parent** pointers.
+    fn update_size(&mut self, node: NonNull<Node<Cached<T>>>, delta: isize) {
+        let mut node = Some(node);
+        while let Some(ptr) = node {
+            // The whole Tree is borrowed mutably, and the content is not structurally pinned.
+            let cached = unsafe { &mut (*ptr.as_ptr()).content };
+            cached.subtree_size = cached.subtree_size.saturating_add_signed(delta);
+            node = unsafe { ptr.as_ref() }.parent().map(Node::ptr);
+        }
+    }
+
+    /// Mutate the **content** of the [`Node`] at **path**, and update its size.
+    /// The [`Node`] is marked as *visited*.
+    ///
+    /// Returns `false` if there is no [`Node`] at **path**.
+    pub fn modify(&mut self, path: &[usize], f: impl FnOnce(&mut T)) -> bool {
+        if self.get(path).is_none() {
+            return false;
+        }
+        let node = self.find(path).unwrap();
+        // The whole Tree is borrowed mutably, and the content is not structurally pinned.
+        let cached = unsafe { &mut (*node.as_ptr()).content };
+        f(&mut cached.content);
+        let size = std::mem::size_of::<Node<Cached<T>>>() + cached.content.heap_size();
+        let delta = size as isize - cached.size as isize;
+        cached.size = size;
+
+        self.update_size(node, delta);
+        true
+    }
+    /// Pushes the **child** to the end of the *children* of the [`Node`] at **path**.
+    /// All the [`Node`]s of **child** are marked as *visited*.
+    ///
+    /// Returns the **child** back if there is no [`Node`] at **path**.
+    pub fn append_child(&mut self, path: &[usize], child: Tree<T>) -> Result<(), Tree<T>> {
+        if self.get(path).is_none() {
+            return Err(child);
+        }
+        let child = Self::wrap(child, self.clock.get());
+        let size = child.root().content.subtree_size;
+
+        let parent = self.find(path).unwrap();
+        if path.is_empty() {
+            self.tree.root_mut().append_child(child);
+        } else {
+            self.tree.borrow_descendant(parent).unwrap().append_child(child);
+        }
+        self.update_size(parent, size as isize);
+        Ok(())
+    }
+
+    /// Detaches branches (chosen by the **policy**) until the [`Tree`] uses at most **bytes**, and returns them.
+    /// The **root** is never evicted, so the [`Tree`] might still use more than **bytes** after this.
+    ///
+    /// This is `O(n log n)`: the branches are only collected once, and the sizes are updated by walking up from each evicted branch.
+    pub fn evict_until(&mut self, bytes: usize, policy: EvictionPolicy) -> Vec<Tree<T>> {
+        match policy {
+            EvictionPolicy::LeastRecentlyVisited => self.evict_by(bytes, |cached, depth| Reverse((cached.last_visit(), depth))),
+            EvictionPolicy::LargestFirst => self.evict_by(bytes, |cached, _| cached.subtree_size),
+        }
+    }
+    /// Evicts the branch with the greatest **key** (or the first one in **Depth-First** order if many have the same **key**)
+    /// until the [`Tree`] uses at most **bytes**. **key** is called with the [`Node`]'s **content** and depth.
+    fn evict_by<K: Ord>(&mut self, bytes: usize, key: impl Fn(&Cached<T>, usize) -> K) -> Vec<Tree<T>> {
+        let mut evicted = vec![];
+        if self.heap_size() <= bytes {
+            return evicted;
+        }
+
+        // Every branch that can be evicted, along with its position in Depth-First order and its depth.
+        let mut candidates = BinaryHeap::new();
+        let mut stack = vec![(self.tree.root(), 0)];
+        while let Some((node, depth)) = stack.pop() {
+            if depth > 0 {
+                candidates.push((key(&node.content, depth), Reverse(candidates.len()), depth, node.ptr()));
+            }
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(node.children_iter().rev().map(|child| (child, depth + 1)));
+        }
+        // The Nodes of the evicted branches, which are not in the Tree anymore.
+        let mut gone = HashSet::new();
+
+        while self.heap_size() > bytes {
+            let Some((old_key, order, depth, ptr)) = candidates.pop() else {
+                break;
+            };
+            if gone.contains(&ptr) {
+                continue;
+            }
+            // The Node was not evicted, so it is still in the Tree.
+            let node = unsafe { ptr.as_ref() };
+            // The size of a branch shrinks when one of its descendants is evicted, so its key might be out of date.
+            let new_key = key(&node.content, depth);
+            if new_key != old_key {
+                candidates.push((new_key, order, depth, ptr));
+                continue;
+            }
+
+            let parent = node.parent().unwrap().ptr();
+            let detached = self.tree.detach_descendant(ptr).unwrap();
+            gone.extend(detached.iter_bfs().map(Node::ptr));
+            self.update_size(parent, -(detached.root().content.subtree_size as isize));
+            evicted.push(detached.map(|cached| cached.content));
+        }
+
+        evicted
+    }
+}
+impl<T> Debug for CachedTree<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTree")
+            .field("tree", &self.tree)
+            .finish()
+    }
+}
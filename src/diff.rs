@@ -0,0 +1,286 @@
+//! Compare two [`Tree`]s and produce an *edit script* that turns one into the other.
+//!
+//! [`Node`]s in an [`Edit`] are referenced by their *path*:
+//! the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**.
+//! The [`Edit`]s must be applied *in order*, because the *paths* of each [`Edit`] are relative to the [`Tree`] after the previous [`Edit`]s were applied.
+//!
+//! # Example
+//! ```
+//! # use tree_struct::{diff::{diff, Edit}, Node};
+//! let old = Node::builder('a').child(Node::builder('b')).child(Node::builder('c')).build();
+//! let new = Node::builder('a').child(Node::builder('c')).child(Node::builder('B')).build();
+//!
+//! let edits = diff(&old, &new);
+//! assert!(matches!(edits[..], [Edit::Move(ref from, ref to), Edit::Update(ref path, 'B')] if from == &[1] && to == &[0] && path == &[1]));
+//! ```
+use super::*;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// A single change to a [`Tree`]. Produced by [`diff()`].
+#[derive(Debug, Clone)]
+pub enum Edit<T> {
+    /// Insert a new subtree at the *path*, shifting the [`Node`] that was there (and its next **siblings**) to the right.
+    Insert(Vec<usize>, Tree<T>),
+    /// Remove the [`Node`] (and its subtree) at the *path*.
+    Delete(Vec<usize>),
+    /// Replace the **content** of the [`Node`] at the *path*.
+    Update(Vec<usize>, T),
+    /// Remove the [`Node`] (and its subtree) at the first *path*, and insert it at the second *path*.
+    /// The second *path* is relative to the [`Tree`] *after* the [`Node`] was removed.
+    Move(Vec<usize>, Vec<usize>),
+}
+
+/// Compare **old** and **new** and return the [`Edit`]s that turn **old** into **new**.
+///
+/// **Children** are compared *by position*, except that a child that is *equal* (including its subtree)
+/// to a child further along in **old** is [`Moved`](Edit::Move) instead of being re-inserted.
+/// This does not guarantee the *minimum* number of [`Edit`]s.
+///
+/// Every subtree is [hashed](Hash) once to find the moved **children** quickly.
+/// Doesn't recurse, so [`Tree`]s of any depth can be compared.
+pub fn diff<T>(old: &Tree<T>, new: &Tree<T>) -> Vec<Edit<T>>
+where T: PartialEq + Hash + Clone {
+    let mut hashes = subtree_hashes(old);
+    hashes.extend(subtree_hashes(new));
+    // Whether two subtrees are equal, checking the hashes first so that most of them don't have to be walked.
+    let equal = |a: &Node<T>, b: &Node<T>| hashes[&a.ptr()] == hashes[&b.ptr()] && subtree_eq(a, b);
+
+    let mut edits = vec![];
+    if old.root().content != new.root().content {
+        edits.push(Edit::Update(vec![], new.root().content.clone()));
+    }
+    // The path of the Node on top of the stack. Only cloned when an Edit is emitted.
+    let mut path = vec![];
+    let child_path = |path: &Vec<usize>, index: usize| {
+        let mut path = path.clone();
+        path.push(index);
+        path
+    };
+    let mut stack = vec![Frame::new(old.root(), new.root())];
+
+    while let Some(frame) = stack.last_mut() {
+        let Some(new_child) = frame.new.get(frame.index) else {
+            // Remove the leftover children from the end, so the indices of the others don't change.
+            for index in (frame.new.children_iter().len()..frame.current.len()).rev() {
+                edits.push(Edit::Delete(child_path(&path, index)));
+            }
+            stack.pop();
+            path.pop();
+            continue;
+        };
+        let index = frame.index;
+        frame.index += 1;
+
+        match frame.current.get(index) {
+            Some(Some(old_child)) if equal(old_child, new_child) => continue,
+            _ => {}
+        }
+
+        let moved = frame.current
+            .iter()
+            .enumerate()
+            .skip(index + 1)
+            .find(|(_, old_child)| old_child.is_some_and(|old_child| equal(old_child, new_child)))
+            .map(|(from, _)| from);
+
+        if let Some(from) = moved {
+            edits.push(Edit::Move(child_path(&path, from), child_path(&path, index)));
+            let old_child = frame.current.remove(from);
+            frame.current.insert(index, old_child);
+        } else if let Some(&Some(old_child)) = frame.current.get(index) {
+            if old_child.content != new_child.content {
+                edits.push(Edit::Update(child_path(&path, index), new_child.content.clone()));
+            }
+            path.push(index);
+            stack.push(Frame::new(old_child, new_child));
+        } else {
+            edits.push(Edit::Insert(child_path(&path, index), new_child.clone_deep()));
+            frame.current.insert(index, None);
+        }
+    }
+
+    edits
+}
+
+/// A pair of [`Node`]s whose **children** are being compared by [`diff()`].
+struct Frame<'a, T> {
+    new: &'a Node<T>,
+    /// The children of old in the order they would be after applying the edits so far (None for inserted children).
+    current: Vec<Option<&'a Node<T>>>,
+    /// The index of the next child of new to compare.
+    index: usize,
+}
+impl<'a, T> Frame<'a, T> {
+    fn new(old: &'a Node<T>, new: &'a Node<T>) -> Self {
+        Self {
+            new,
+            current: old.children_iter().map(Some).collect(),
+            index: 0,
+        }
+    }
+}
+
+/// The hash of the **content** and *shape* of every subtree of the [`Tree`], hashing each [`Node`] only once.
+/// Two subtrees that are [`subtree_eq()`] always have the same hash.
+fn subtree_hashes<T>(tree: &Tree<T>) -> HashMap<NonNull<Node<T>>, u64>
+where T: Hash {
+    let mut hashes = HashMap::with_capacity(tree.len());
+    // Hash the deepest Nodes first, so the children's hashes are ready before their parent is hashed.
+    let nodes = tree.iter_bfs().collect::<Vec<_>>();
+    for node in nodes.into_iter().rev() {
+        let mut hasher = DefaultHasher::new();
+        node.content.hash(&mut hasher);
+        hasher.write_usize(node.children_iter().len());
+        for child in node.children_iter() {
+            hasher.write_u64(hashes[&child.ptr()]);
+        }
+        hashes.insert(node.ptr(), hasher.finish());
+    }
+    hashes
+}
+
+/// Whether two [`Node`]s have the same **content** and the same **children** (recursively).
+pub(crate) fn subtree_eq<T>(a: &Node<T>, b: &Node<T>) -> bool
+where T: PartialEq {
+    let mut stack = vec![(a, b)];
+
+    while let Some((a, b)) = stack.pop() {
+        if a.content != b.content || a.children_iter().len() != b.children_iter().len() {
+            return false;
+        }
+        stack.extend(a.children_iter().zip(b.children_iter()));
+    }
+
+    true
+}
+
+/// Returned by [`Tree::apply()`] when an [`Edit`] can't be applied, for example because there is no [`Node`] at its *path*.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchError {
+    /// The index of the invalid [`Edit`] in the *patch*.
+    pub edit: usize,
+}
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "edit {} of the patch can't be applied to the tree", self.edit)
+    }
+}
+impl std::error::Error for PatchError {}
+
+/// Only the *shape* of a [`Tree`], used to check that all the [`Edit`]s of a *patch* are valid before applying them.
+struct Shape {
+    children: Vec<Shape>,
+}
+impl Drop for Shape {
+    /// Drops the **children** with a stack instead of recursing.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(mut shape) = stack.pop() {
+            stack.append(&mut shape.children);
+        }
+    }
+}
+impl Shape {
+    /// Doesn't recurse, so [`Tree`]s of any depth can be patched.
+    fn new<T>(node: &Node<T>) -> Self {
+        node.fold_bottom_up(|_, children| Self { children })
+    }
+
+    fn get_mut(&mut self, path: &[usize]) -> Option<&mut Self> {
+        let mut shape = self;
+        for &index in path {
+            shape = shape.children.get_mut(index)?;
+        }
+        Some(shape)
+    }
+    /// Removes the [`Shape`] at a *path*, which must not be the **root**.
+    fn remove(&mut self, path: &[usize]) -> Option<Self> {
+        let (&index, parent) = path.split_last()?;
+        let parent = self.get_mut(parent)?;
+        (index < parent.children.len()).then(|| parent.children.remove(index))
+    }
+    /// Inserts a [`Shape`] at a *path*, which must not be the **root**.
+    fn insert(&mut self, path: &[usize], shape: Self) -> bool {
+        let Some((&index, parent)) = path.split_last() else {
+            return false;
+        };
+        match self.get_mut(parent) {
+            Some(parent) if index <= parent.children.len() => {
+                parent.children.insert(index, shape);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    /// Applies all the [`Edit`]s of a *patch* (e.g. produced by [`diff()`]) in order.
+    ///
+    /// The *patch* is applied *transactionally*:
+    /// all the [`Edit`]s are checked before the [`Tree`] is modified, so if any of them is invalid, the [`Tree`] is left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{diff::diff, Node};
+    /// let mut old = Node::builder('a').child(Node::builder('b')).build();
+    /// let new = Node::builder('a').child(Node::builder('c').child(Node::builder('d'))).build();
+    ///
+    /// old.apply(diff(&old, &new)).unwrap();
+    /// assert_eq!(old.iter_bfs().map(|n| n.content).collect::<String>(), "acd");
+    /// ```
+    pub fn apply(&mut self, patch: Vec<Edit<T>>) -> Result<(), PatchError> {
+        // Check every edit against the shape of the Tree.
+        let mut shape = Shape::new(self.root());
+        for (index, edit) in patch.iter().enumerate() {
+            let valid = match edit {
+                Edit::Insert(path, subtree) => shape.insert(path, Shape::new(subtree.root())),
+                Edit::Delete(path) => shape.remove(path).is_some(),
+                Edit::Update(path, _) => shape.get_mut(path).is_some(),
+                Edit::Move(from, to) => shape.remove(from).is_some_and(|moved| shape.insert(to, moved)),
+            };
+            if !valid {
+                return Err(PatchError { edit: index });
+            }
+        }
+
+        for edit in patch {
+            match edit {
+                Edit::Insert(path, subtree) => {
+                    let (&index, parent) = path.split_last().unwrap();
+                    self.node_mut(parent).insert_child(subtree, index);
+                }
+                Edit::Delete(path) => {
+                    let node = self.node_mut(&path).ptr();
+                    self.detach_descendant(node);
+                }
+                Edit::Update(path, content) => {
+                    self.node_mut(&path).replace_content(content);
+                }
+                Edit::Move(from, to) => {
+                    let node = self.node_mut(&from).ptr();
+                    let moved = self.detach_descendant(node).unwrap();
+                    let (&index, parent) = to.split_last().unwrap();
+                    self.node_mut(parent).insert_child(moved, index);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the [`Node`] at a *path* that is known to be valid.
+    fn node_mut(&mut self, path: &[usize]) -> Pin<&mut Node<T>> {
+        if path.is_empty() {
+            return self.root_mut();
+        }
+
+        let node = path.iter()
+            .fold(self.root(), |node, &index| node.get(index).unwrap())
+            .ptr();
+        self.borrow_descendant(node).unwrap()
+    }
+}
@@ -0,0 +1,226 @@
+use super::*;
+use std::fmt::{Display, Formatter, Result};
+
+/// The characters used by a [`TreeFormatter`] to draw the branches of a [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IndentStyle {
+    /// `├──`, `└──` and `│` (Unicode *box-drawing* characters).
+    #[default]
+    Unicode,
+    /// `|--`, `` `-- `` and `|`.
+    Ascii,
+    /// Only spaces.
+    Spaces,
+}
+impl IndentStyle {
+    /// The characters for a *branch* to a child, to the *last* child, a *vertical* line, and a *horizontal* line.
+    fn chars(self) -> [char; 4] {
+        match self {
+            Self::Unicode => ['├', '└', '│', '─'],
+            Self::Ascii => ['|', '`', '|', '-'],
+            Self::Spaces => [' '; 4],
+        }
+    }
+}
+
+/// Configures how a [`Tree`] is printed with [`TreeFormatter::format()`].
+/// The [`Display`] implementation of [`Tree`] uses the [`Default`] [`TreeFormatter`].
+///
+/// Each [`Node`] is printed on its own line (or more if its **content** has more lines), below its **parent**:
+/// ```text
+/// parent
+/// ├── child a
+/// │   └── child c
+/// └── child b
+/// ```
+///
+/// # Example
+/// ```
+/// # use tree_struct::{IndentStyle, Node, TreeFormatter};
+/// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+/// let formatter = TreeFormatter::new().style(IndentStyle::Ascii).indent(3).max_depth(1);
+/// assert_eq!(formatter.format(tree.root()).to_string(), "a\n|- b\n|  `- ...\n`- c\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeFormatter {
+    style: IndentStyle,
+    indent: usize,
+    max_depth: Option<usize>,
+}
+impl TreeFormatter {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The characters used to draw the branches. Defaults to [`IndentStyle::Unicode`].
+    pub fn style(mut self, style: IndentStyle) -> Self {
+        self.style = style;
+        self
+    }
+    /// How many columns each level of the [`Tree`] is indented by. Defaults to `4`, and can't be less than `2`.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent.max(2);
+        self
+    }
+    /// Only print the [`Node`]s up to **depth** (the **root** is at depth `0`).
+    /// The **children** of the [`Node`]s at **depth** are replaced by a single `...` line.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Get an object that [`Display`]s **node** (and its subtree) with this configuration.
+    pub fn format<'a, T>(&'a self, node: &'a Node<T>) -> FormatTree<'a, T>
+    where T: Display {
+        FormatTree { formatter: self, node }
+    }
+
+    fn fmt_node<T>(&self, root: &Node<T>, f: &mut Formatter<'_>) -> Result
+    where T: Display {
+        self.fmt_with(root, |node| node.content.to_string(), |node| node.children(), f)
+    }
+    /// Prints the subtree of **root** with the **content** and **children** of each *node* returned by the closures,
+    /// so that [`rc::Node`](crate::rc::Node)s can also be printed.
+    pub(crate) fn fmt_with<N>(&self, root: N, content: impl Fn(&N) -> String, children: impl Fn(&N) -> Box<[N]>, f: &mut Formatter<'_>) -> Result {
+        let [branch, last_branch, vertical, horizontal] = self.style.chars();
+        let connector = |first: char, line: char| {
+            let mut s = String::from(first);
+            s.extend(std::iter::repeat_n(line, self.indent - 2));
+            s.push(' ');
+            s
+        };
+        let (branch, last_branch) = (connector(branch, horizontal), connector(last_branch, horizontal));
+        let (vertical, empty) = (connector(vertical, ' '), " ".repeat(self.indent));
+
+        // Whether each ancestor (excluding the root) is the last child of its parent.
+        let mut lasts = Vec::<bool>::new();
+        let mut stack = vec![(root, 0usize, true)];
+
+        while let Some((node, depth, last)) = stack.pop() {
+            lasts.truncate(depth.saturating_sub(1));
+            let mut prefix = lasts
+                .iter()
+                .map(|&last| if last { empty.as_str() } else { vertical.as_str() })
+                .collect::<String>();
+            let mut continuation = prefix.clone();
+            if depth > 0 {
+                prefix.push_str(if last { &last_branch } else { &branch });
+                continuation.push_str(if last { &empty } else { &vertical });
+                lasts.push(last);
+            }
+
+            let content = content(&node);
+            let mut lines = content.split('\n');
+            writeln!(f, "{prefix}{}", lines.next().unwrap_or_default())?;
+            for line in lines {
+                writeln!(f, "{continuation}{line}")?;
+            }
+
+            let children = children(&node);
+            if self.max_depth == Some(depth) {
+                if !children.is_empty() {
+                    writeln!(f, "{continuation}{last_branch}...")?;
+                }
+            } else {
+                let len = children.len();
+                stack.extend(children.into_vec().into_iter().enumerate().rev().map(|(i, child)| (child, depth + 1, i == len - 1)));
+            }
+        }
+
+        Ok(())
+    }
+}
+impl Default for TreeFormatter {
+    fn default() -> Self {
+        Self {
+            style: IndentStyle::default(),
+            indent: 4,
+            max_depth: None,
+        }
+    }
+}
+
+/// Obtained by calling [`TreeFormatter::format()`].
+pub struct FormatTree<'a, T>
+where T: Display {
+    formatter: &'a TreeFormatter,
+    node: &'a Node<T>,
+}
+impl<'a, T> Display for FormatTree<'a, T>
+where T: Display {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.formatter.fmt_node(self.node, f)
+    }
+}
+
+impl<T> Display for Tree<T>
+where T: Display {
+    /// Prints the [`Tree`] with the [`Default`] [`TreeFormatter`].
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        TreeFormatter::default().fmt_node(self.root(), f)
+    }
+}
+
+/// Displays a [`Node`] (and its subtree) on a single line, as its **content** followed by its comma-separated **children** in parentheses,
+/// like `a(b(c),d)`. Obtained by calling [`Node::display_compact()`] or [`Tree::display_compact()`].
+///
+/// Unlike the [`Display`] implementation of [`Tree`], the output doesn't span multiple lines, so it fits in logs and assertion messages.
+/// The **contents** are written as is, so they can't be told apart from the tree structure if they contain parentheses or commas.
+pub struct DisplayCompact<'a, T>
+where T: Display {
+    node: &'a Node<T>,
+}
+impl<'a, T> Display for DisplayCompact<'a, T>
+where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.node.content)?;
+        // The children that are left to write for each ancestor, and whether none was written yet.
+        let mut stack = vec![(self.node.children_iter(), true)];
+
+        while let Some((children, first)) = stack.last_mut() {
+            match children.next() {
+                Some(child) => {
+                    f.write_str(if *first { "(" } else { "," })?;
+                    *first = false;
+                    write!(f, "{}", child.content)?;
+                    stack.push((child.children_iter(), true));
+                }
+                None => {
+                    if !*first {
+                        f.write_str(")")?;
+                    }
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Node<T>
+where T: Display {
+    /// Get an object that [`Display`]s **self** and its subtree on a single line. See [`DisplayCompact`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).child(Node::builder('d')).build();
+    /// assert_eq!(tree.root().display_compact().to_string(), "a(b(c),d)");
+    /// ```
+    #[inline]
+    pub fn display_compact(&self) -> DisplayCompact<'_, T> {
+        DisplayCompact { node: self }
+    }
+}
+impl<T> Tree<T>
+where T: Display {
+    /// Get an object that [`Display`]s the [`Tree`] on a single line. See [`Node::display_compact()`].
+    #[inline]
+    pub fn display_compact(&self) -> DisplayCompact<'_, T> {
+        self.root().display_compact()
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+use std::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+/// A [`Tree`] that calls a *hook* with the **content** of every [`Node`] right before it is dropped.
+/// Obtained by calling [`Tree::with_drop_hook()`].
+///
+/// When the [`DropHookTree`] is dropped, the [`Node`]s are torn down in **Post-Order** (see [`IterPostOrder`]):
+/// a [`Node`]'s **children** are dropped (in order) before the [`Node`] itself.
+/// This makes the release of resources owned by the **content** (e.g. file handles or GPU buffers) *deterministic*.
+///
+/// Derefs to the [`Tree`], so it can be used (and modified) like one.
+/// Note that [`Node`]s [`detached`](Tree::detach_descendant()) from it are no longer observed by the *hook*.
+///
+/// # Example
+/// ```
+/// # use tree_struct::Node;
+/// let mut dropped = vec![];
+/// let tree = Node::builder('a')
+///     .child(Node::builder('b').child(Node::builder('c')))
+///     .child(Node::builder('d'))
+///     .build()
+///     .with_drop_hook(|content| dropped.push(*content));
+/// drop(tree);
+/// assert_eq!(dropped, vec!['c', 'b', 'd', 'a']);
+/// ```
+pub struct DropHookTree<T, F>
+where F: FnMut(&mut T) {
+    tree: ManuallyDrop<Tree<T>>,
+    hook: F,
+}
+impl<T, F> DropHookTree<T, F>
+where F: FnMut(&mut T) {
+    pub(crate) fn new(tree: Tree<T>, hook: F) -> Self {
+        Self {
+            tree: ManuallyDrop::new(tree),
+            hook,
+        }
+    }
+
+    /// Removes the *hook* and returns the [`Tree`], which will no longer call it.
+    pub fn into_tree(self) -> Tree<T> {
+        let mut this = ManuallyDrop::new(self);
+        // `this` is never dropped, so the Tree is only taken once. The hook still needs to be dropped.
+        unsafe {
+            std::ptr::drop_in_place(&mut this.hook);
+            ManuallyDrop::take(&mut this.tree)
+        }
+    }
+}
+impl<T, F> Deref for DropHookTree<T, F>
+where F: FnMut(&mut T) {
+    type Target = Tree<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tree
+    }
+}
+impl<T, F> DerefMut for DropHookTree<T, F>
+where F: FnMut(&mut T) {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tree
+    }
+}
+impl<T, F> Drop for DropHookTree<T, F>
+where F: FnMut(&mut T) {
+    fn drop(&mut self) {
+        // The Tree is only taken here, and is never used again.
+        let tree = unsafe { ManuallyDrop::take(&mut self.tree) };
+        for (_, mut content) in tree.into_subtrees_postorder() {
+            (self.hook)(&mut content);
+        }
+    }
+}
+impl<T, F> Debug for DropHookTree<T, F>
+where T: Debug, F: FnMut(&mut T) {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&*self.tree, f)
+    }
+}
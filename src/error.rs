@@ -0,0 +1,70 @@
+//! Fallible versions of the methods that otherwise return [`None`] or panic when they are misused.
+use super::*;
+use std::ptr::NonNull;
+
+/// Returned by the `try_` methods (e.g. [`Tree::try_detach_descendant()`]) to tell *why* the operation couldn't be done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeError {
+    /// The [`Node`] is not a **descendant** of the [`Tree`]'s **root** (or it is the **root** itself).
+    NotADescendant,
+    /// A *child* **index** is greater than the number of *children* (**len**).
+    IndexOutOfBounds { index: usize, len: usize },
+    /// Adding a [`Tree`] under one of its own [`Node`]s would make the [`Node`] its own **ancestor**.
+    WouldCreateCycle,
+    /// A *child* doesn't refer back to the [`Node`] that has it in its *children* (or the **root** has a **parent**),
+    /// e.g. because a thread panicked in the middle of a mutation. See `rc::Tree::validate()`.
+    InconsistentParent,
+    /// A [`NodeRef`] doesn't refer to a [`Node`] in the [`Tree`] (e.g. because the [`Node`] was detached or dropped).
+    DanglingHandle,
+    /// A [`Node`] is still referenced outside of its [`Tree`] (e.g. by a handle returned by `rc::Tree::root()`),
+    /// so the [`Tree`] can't be taken apart. See `rc::Tree::map()`.
+    SharedNode,
+}
+impl std::fmt::Display for TreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotADescendant => write!(f, "the node is not a descendant of the root of the tree"),
+            Self::IndexOutOfBounds { index, len } => write!(f, "child index {index} is out of bounds for {len} children"),
+            Self::WouldCreateCycle => write!(f, "the node would become its own ancestor"),
+            Self::InconsistentParent => write!(f, "a child doesn't refer back to its parent"),
+            Self::DanglingHandle => write!(f, "the handle doesn't refer to a node in the tree"),
+            Self::SharedNode => write!(f, "a node is still referenced outside of the tree"),
+        }
+    }
+}
+impl std::error::Error for TreeError {}
+
+impl<T, M> Tree<T, M> {
+    /// Like [`Tree::detach_descendant()`], but returns [`TreeError::NotADescendant`] instead of [`None`].
+    #[inline]
+    pub fn try_detach_descendant(&mut self, descendant: NonNull<Node<T, M>>) -> Result<Self, TreeError> {
+        self.detach_descendant(descendant).ok_or(TreeError::NotADescendant)
+    }
+    /// Like [`Tree::borrow_descendant()`], but returns [`TreeError::NotADescendant`] instead of [`None`].
+    #[inline]
+    pub fn try_borrow_descendant(&mut self, descendant: NonNull<Node<T, M>>) -> Result<Pin<&mut Node<T, M>>, TreeError> {
+        self.borrow_descendant(descendant).ok_or(TreeError::NotADescendant)
+    }
+}
+
+impl<T, M> Node<T, M> {
+    /// Like [`Node::insert_child()`], but returns [`TreeError::IndexOutOfBounds`] (and the **child** back) instead of panicking
+    /// if **index** is greater than the number of *children*.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Node, TreeError};
+    /// let mut tree = Node::builder('a').child(Node::builder('b')).build();
+    /// let (error, child) = tree.root_mut().try_insert_child(Node::builder('c').build(), 2).unwrap_err();
+    /// assert_eq!(error, TreeError::IndexOutOfBounds { index: 2, len: 1 });
+    /// assert!(tree.root_mut().try_insert_child(child, 1).is_ok());
+    /// ```
+    pub fn try_insert_child(self: Pin<&mut Self>, child: Tree<T, M>, index: usize) -> Result<(), (TreeError, Tree<T, M>)> {
+        let len = self.children_iter().len();
+        if index > len {
+            return Err((TreeError::IndexOutOfBounds { index, len }, child));
+        }
+        self.insert_child(child, index);
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+//! A C API over [`Tree`]s of *bytes*, so the crate can back tree structures in C/C++/Python bindings.
+//!
+//! [`Tree`]s are never handed out across the FFI boundary.
+//! Instead, they are stored in a *handle table* and are referenced with an opaque [`TreeHandle`].
+//! [`Node`]s are referenced with an opaque [`NodeHandle`], which is unique to each [`Node`] (even if another [`Node`] is later allocated at the same address),
+//! and is looked up in the handle table instead of being dereferenced.
+//! The [`Node`]s are removed from the table when their [`Tree`] is freed,
+//! so passing a stale [`NodeHandle`] just makes the functions fail instead of causing *undefined behavior*.
+//! Looking up a [`NodeHandle`] is `O(depth)`, because the [`Node`] is checked to be in the [`Tree`] by walking up to its **root**.
+//!
+//! The handle table is **thread-local** (because [`Node`]s are not [`Send`]),
+//! so a [`TreeHandle`] can only be used on the thread that created it.
+//!
+//! Functions that can fail return `false`, or `0` ([`NULL_TREE`] or [`NULL_NODE`]).
+//! Calling back into this API from an iteration callback is allowed for *reading*,
+//! but functions that modify a [`Tree`] will fail while it is being iterated.
+use super::*;
+use std::{cell::RefCell, collections::HashMap, ffi::c_void, ptr::NonNull};
+
+type Content = Box<[u8]>;
+
+/// Opaque reference to a [`Tree`] stored in the handle table.
+pub type TreeHandle = u64;
+/// Opaque reference to a [`Node`] in a [`Tree`].
+pub type NodeHandle = u64;
+/// Returned when a function fails to produce a [`TreeHandle`].
+pub const NULL_TREE: TreeHandle = 0;
+/// Returned when a function fails to produce a [`NodeHandle`].
+pub const NULL_NODE: NodeHandle = 0;
+
+/// Called for every [`Node`] visited by [`tree_struct_iter_bfs()`] and [`tree_struct_iter_dfs()`]
+/// with the [`NodeHandle`], the *content* bytes, and the `user_data` passed to the iteration function.
+/// Return `false` to stop iterating.
+///
+/// It is an [`Option`] because C can pass a *null* function pointer, which makes the iteration functions return `0`.
+pub type IterCallback = Option<extern "C" fn(NodeHandle, *const u8, usize, *mut c_void) -> bool>;
+
+#[derive(Default)]
+struct HandleTable {
+    last: TreeHandle,
+    trees: HashMap<TreeHandle, Tree<Content>>,
+    /// Every [`Node`] of the [`Tree`]s in the table.
+    /// The [`Node`]s are removed when their [`Tree`] is freed, so the pointers are always valid.
+    nodes: HashMap<NodeHandle, NonNull<Node<Content>>>,
+}
+impl HandleTable {
+    /// Stores a [`Tree`] whose [`Node`]s are already in the table (e.g. a detached one).
+    fn insert(&mut self, tree: Tree<Content>) -> TreeHandle {
+        self.last += 1;
+        self.trees.insert(self.last, tree);
+        self.last
+    }
+    /// Stores a new [`Tree`] and all of its [`Node`]s.
+    fn insert_new(&mut self, tree: Tree<Content>) -> TreeHandle {
+        self.nodes.extend(tree.iter_bfs().map(|node| (node_handle(node), node.ptr())));
+        self.insert(tree)
+    }
+    /// Drops a [`Tree`] and removes all of its [`Node`]s from the table.
+    fn free(&mut self, tree: TreeHandle) -> Option<()> {
+        let tree = self.trees.remove(&tree)?;
+        for node in tree.iter_bfs() {
+            self.nodes.remove(&node_handle(node));
+        }
+        Some(())
+    }
+    /// Get a [`Node`] of **tree**, or [`None`] if **node** refers to a [`Node`] of another [`Tree`] or to one that was freed.
+    fn node(&self, tree: TreeHandle, node: NodeHandle) -> Option<&Node<Content>> {
+        let tree = self.trees.get(&tree)?;
+        // The Node is in the table, so it has not been dropped.
+        let node = unsafe { self.nodes.get(&node)?.as_ref() };
+        let mut root = node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        tree.root().is_same_as(root).then_some(node)
+    }
+}
+
+thread_local! {
+    static TREES: RefCell<HandleTable> = RefCell::new(HandleTable::default());
+}
+
+/// The *generation* of a [`Node`] is never reused, unlike its address. `0` is reserved for [`NULL_NODE`].
+fn node_handle(node: &Node<Content>) -> NodeHandle {
+    node.generation() + 1
+}
+/// Run **f** with the handle table borrowed.
+/// Returns [`None`] if the table is already *mutably borrowed* (i.e. it was called from a callback).
+fn with_table<R>(f: impl FnOnce(&HandleTable) -> Option<R>) -> Option<R> {
+    TREES.with(|table| f(&*table.try_borrow().ok()?))
+}
+/// Run **f** with the handle table mutably borrowed.
+/// Returns [`None`] if the table is already borrowed (i.e. it was called from a callback).
+fn with_table_mut<R>(f: impl FnOnce(&mut HandleTable) -> Option<R>) -> Option<R> {
+    TREES.with(|table| f(&mut *table.try_borrow_mut().ok()?))
+}
+
+/// Copies **len** bytes from **content** into a new [`Tree`] with only a **root**.
+///
+/// # Safety
+/// **content** must be valid for reads of **len** bytes, or can be *null* if **len** is `0`.
+#[no_mangle]
+pub unsafe extern "C" fn tree_struct_new(content: *const u8, len: usize) -> TreeHandle {
+    let content: Content = if len == 0 {
+        Box::new([])
+    } else {
+        if content.is_null() {
+            return NULL_TREE;
+        }
+        unsafe { std::slice::from_raw_parts(content, len) }.into()
+    };
+
+    with_table_mut(|table| Some(table.insert_new(Node::builder(content).build()))).unwrap_or(NULL_TREE)
+}
+
+/// Drops the [`Tree`] and all of its [`Node`]s.
+/// Returns `false` if **tree** is not a valid [`TreeHandle`].
+#[no_mangle]
+pub extern "C" fn tree_struct_free(tree: TreeHandle) -> bool {
+    with_table_mut(|table| table.free(tree)).is_some()
+}
+
+/// Get the **root** of the [`Tree`], or [`NULL_NODE`] if **tree** is not a valid [`TreeHandle`].
+#[no_mangle]
+pub extern "C" fn tree_struct_root(tree: TreeHandle) -> NodeHandle {
+    with_table(|table| Some(node_handle(table.trees.get(&tree)?.root()))).unwrap_or(NULL_NODE)
+}
+
+/// Moves all the [`Node`]s of **child** to the end of **parent**'s *children*.
+/// **child** is no longer a valid [`TreeHandle`] after this function succeeds.
+///
+/// Returns `false` if any handle is invalid, or if **tree** and **child** are the same.
+#[no_mangle]
+pub extern "C" fn tree_struct_append_child(tree: TreeHandle, parent: NodeHandle, child: TreeHandle) -> bool {
+    with_table_mut(|table| {
+        if tree == child || !table.trees.contains_key(&child) {
+            return None;
+        }
+        let parent = table.node(tree, parent)?.ptr();
+
+        let child = table.trees.remove(&child)?;
+        let target = table.trees.get_mut(&tree)?;
+        let parent = if target.root().is_same_as(parent) {
+            target.root_mut()
+        } else {
+            target.borrow_descendant(parent)?
+        };
+        parent.append_child(child);
+        Some(())
+    })
+    .is_some()
+}
+
+/// Removes **node** from **tree** and stores it in the handle table as a new [`Tree`].
+/// Returns [`NULL_TREE`] if any handle is invalid, or if **node** is the **root** of **tree**.
+#[no_mangle]
+pub extern "C" fn tree_struct_detach(tree: TreeHandle, node: NodeHandle) -> TreeHandle {
+    with_table_mut(|table| {
+        let node = table.node(tree, node)?.ptr();
+        let detached = table.trees.get_mut(&tree)?.detach_descendant(node)?;
+        Some(table.insert(detached))
+    })
+    .unwrap_or(NULL_TREE)
+}
+
+/// Get the *content* bytes of a [`Node`].
+/// The pointer written to **out_content** is valid until **tree** is modified or freed.
+///
+/// Returns `false` if any handle is invalid.
+///
+/// # Safety
+/// **out_content** and **out_len** must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn tree_struct_content(
+    tree: TreeHandle,
+    node: NodeHandle,
+    out_content: *mut *const u8,
+    out_len: *mut usize,
+) -> bool {
+    with_table(|table| {
+        let node = table.node(tree, node)?;
+        unsafe {
+            out_content.write(node.content.as_ptr());
+            out_len.write(node.content.len());
+        }
+        Some(())
+    })
+    .is_some()
+}
+
+fn iter(
+    tree: TreeHandle,
+    nodes: impl FnOnce(&Tree<Content>) -> Box<dyn Iterator<Item = &Node<Content>> + '_>,
+    callback: IterCallback,
+    user_data: *mut c_void,
+) -> usize {
+    let Some(callback) = callback else {
+        return 0;
+    };
+    TREES.with(|table| {
+        let Ok(table) = table.try_borrow() else {
+            return 0;
+        };
+        let Some(tree) = table.trees.get(&tree) else {
+            return 0;
+        };
+
+        let mut count = 0;
+        for node in nodes(tree) {
+            count += 1;
+            if !callback(node_handle(node), node.content.as_ptr(), node.content.len(), user_data) {
+                break;
+            }
+        }
+        count
+    })
+}
+
+/// Calls **callback** for every [`Node`] of the [`Tree`] using **Breadth-First Search**.
+/// Returns how many [`Node`]s were visited (`0` if **tree** is not a valid [`TreeHandle`] or **callback** is *null*).
+#[no_mangle]
+pub extern "C" fn tree_struct_iter_bfs(tree: TreeHandle, callback: IterCallback, user_data: *mut c_void) -> usize {
+    iter(tree, |t| Box::new(t.iter_bfs()), callback, user_data)
+}
+/// Calls **callback** for every [`Node`] of the [`Tree`] using **Depth-First Search**.
+/// Returns how many [`Node`]s were visited (`0` if **tree** is not a valid [`TreeHandle`] or **callback** is *null*).
+#[no_mangle]
+pub extern "C" fn tree_struct_iter_dfs(tree: TreeHandle, callback: IterCallback, user_data: *mut c_void) -> usize {
+    iter(tree, |t| Box::new(t.iter_dfs()), callback, user_data)
+}
@@ -0,0 +1,90 @@
+use super::*;
+use std::collections::VecDeque;
+
+/// Returned by [`Tree::from_flat()`] when the entries don't describe a [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlatError {
+    /// There are no entries.
+    Empty,
+    /// The *parent* of the entry at **index** is invalid:
+    /// the first entry (the **root**) must have no *parent*,
+    /// and every other entry must have a *parent* that comes before it.
+    InvalidParent { index: usize },
+}
+impl std::fmt::Display for FlatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "there are no entries"),
+            Self::InvalidParent { index } => write!(f, "the entry at index {index} has an invalid parent"),
+        }
+    }
+}
+impl std::error::Error for FlatError {}
+
+impl<T> Tree<T> {
+    /// Converts the [`Tree`] into a *flat* list of entries in **Breadth-First** order,
+    /// where each entry has the index of its **parent**'s entry (or [`None`] for the **root**) and the **content**.
+    ///
+    /// This representation is friendlier for *columnar* storage (e.g. databases) than nested formats.
+    /// Use [`Tree::from_flat()`] to build the [`Tree`] back.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    /// assert_eq!(tree.to_flat(), vec![(None, 'a'), (Some(0), 'b'), (Some(0), 'c'), (Some(1), 'd')]);
+    /// ```
+    pub fn to_flat(&self) -> Vec<(Option<usize>, T)>
+    where T: Clone {
+        let mut entries = vec![];
+        let mut queue = VecDeque::from([(None, self.root())]);
+
+        while let Some((parent, node)) = queue.pop_front() {
+            let index = entries.len();
+            entries.push((parent, node.content.clone()));
+            queue.extend(node.children().iter().map(|child| (Some(index), *child)));
+        }
+
+        entries
+    }
+    /// Builds a [`Tree`] from a *flat* list of entries, like the ones returned by [`Tree::to_flat()`].
+    ///
+    /// The first entry is the **root** and must have no *parent*.
+    /// Every other entry must have a *parent* that comes *before* it in the list (which also means that there can't be any cycles).
+    /// The **children** of a [`Node`] are in the same order as their entries.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{FlatError, Tree};
+    /// let tree = Tree::from_flat(vec![(None, 'a'), (Some(0), 'b'), (Some(1), 'c'), (Some(0), 'd')]).unwrap();
+    /// assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "abcd");
+    ///
+    /// assert_eq!(Tree::from_flat(vec![(None, 'a'), (Some(2), 'b'), (Some(0), 'c')]), Err(FlatError::InvalidParent { index: 1 }));
+    /// ```
+    pub fn from_flat(entries: Vec<(Option<usize>, T)>) -> Result<Self, FlatError> {
+        if entries.is_empty() {
+            return Err(FlatError::Empty);
+        }
+        let mut parents = Vec::with_capacity(entries.len());
+        let mut builders = Vec::with_capacity(entries.len());
+
+        for (index, (parent, content)) in entries.into_iter().enumerate() {
+            match parent {
+                None if index == 0 => {}
+                Some(parent) if parent < index => parents.push(parent),
+                _ => return Err(FlatError::InvalidParent { index }),
+            }
+            builders.push(NodeBuilder::new(content));
+        }
+
+        // Every entry comes after its parent, so when an entry is popped, all of its children have been added to it already (in reverse order).
+        while let Some(parent) = parents.pop() {
+            let mut child = builders.pop().unwrap();
+            child.children.reverse();
+            builders[parent].children.push(child);
+        }
+        let mut root = builders.pop().unwrap();
+        root.children.reverse();
+        Ok(root.build())
+    }
+}
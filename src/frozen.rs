@@ -0,0 +1,189 @@
+use super::*;
+use std::{collections::VecDeque, ops::Range};
+
+/// A [`Tree`] that can no longer be structurally modified, obtained by calling [`Tree::freeze()`].
+///
+/// All the [`Node`]s are stored in a *single contiguous allocation* in **Breadth-First** order,
+/// so the **children** of a [`Node`] are next to each other, and traversing the [`FrozenTree`] has better locality.
+/// This is good for [`Tree`]s that are built once and then only read.
+///
+/// Call [`FrozenTree::thaw()`] to convert it back to a regular [`Tree`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct FrozenTree<T> {
+    nodes: Box<[FrozenEntry<T>]>,
+}
+#[derive(Clone, PartialEq, Eq)]
+struct FrozenEntry<T> {
+    content: T,
+    parent: Option<usize>,
+    children: Range<usize>,
+}
+impl<T> FrozenTree<T> {
+    pub(crate) fn new(tree: Tree<T>) -> Self {
+        let mut nodes = Vec::<FrozenEntry<T>>::new();
+        let mut queue = VecDeque::from([(None::<usize>, tree)]);
+
+        while let Some((parent, tree)) = queue.pop_front() {
+            let index = nodes.len();
+            let (content, children) = Node::into_parts(tree.root);
+
+            // Children are always pushed right after their previous sibling, so they are contiguous.
+            if let Some(parent) = parent {
+                let range = &mut nodes[parent].children;
+                // The root is the only Node at index 0, so 0..0 means there are no children yet.
+                if range.end == 0 {
+                    *range = index..index;
+                }
+                range.end += 1;
+            }
+
+            nodes.push(FrozenEntry {
+                content,
+                parent,
+                children: 0..0,
+            });
+            queue.extend(children.into_iter().map(|child| (Some(index), child)));
+        }
+
+        Self { nodes: nodes.into_boxed_slice() }
+    }
+
+    /// Convert the [`FrozenTree`] back to a regular [`Tree`], which can be structurally modified.
+    pub fn thaw(self) -> Tree<T> {
+        let mut builders = Vec::<Option<NodeBuilder<T>>>::new();
+        builders.resize_with(self.nodes.len(), || None);
+
+        // Children always come after their parent, so they are built first when going in reverse.
+        for (index, entry) in self.nodes.into_vec().into_iter().enumerate().rev() {
+            builders[index] = Some(NodeBuilder {
+                content: entry.content,
+                children: entry.children.map(|child| builders[child].take().unwrap()).collect(),
+            });
+        }
+
+        builders[0].take().unwrap().build()
+    }
+
+    pub fn root(&self) -> FrozenNode<'_, T> {
+        FrozenNode { tree: self, index: 0 }
+    }
+
+    /// The number of [`Node`]s in the [`FrozenTree`].
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    /// Always `false`, because a [`FrozenTree`] always has a **root**.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Iterate over all the [`Node`]s of the [`FrozenTree`] using **Breadth-First Search**.
+    /// This is just iterating over the underlying contiguous storage.
+    pub fn iter_bfs(&self) -> impl DoubleEndedIterator<Item = FrozenNode<'_, T>> + ExactSizeIterator {
+        (0..self.nodes.len()).map(|index| FrozenNode { tree: self, index })
+    }
+    /// Iterate over all the [`Node`]s of the [`FrozenTree`] using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = FrozenNode<'_, T>> {
+        self.root().iter_dfs()
+    }
+}
+impl<T> From<Tree<T>> for FrozenTree<T> {
+    #[inline]
+    fn from(tree: Tree<T>) -> Self {
+        tree.freeze()
+    }
+}
+impl<T> From<FrozenTree<T>> for Tree<T> {
+    #[inline]
+    fn from(tree: FrozenTree<T>) -> Self {
+        tree.thaw()
+    }
+}
+impl<T> Debug for FrozenTree<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrozenTree")
+            .field("root", &self.root())
+            .finish()
+    }
+}
+
+/// A reference to a [`Node`] in a [`FrozenTree`].
+/// It is [`Copy`], so it can be passed around freely.
+pub struct FrozenNode<'a, T> {
+    tree: &'a FrozenTree<T>,
+    index: usize,
+}
+impl<'a, T> FrozenNode<'a, T> {
+    fn entry(&self) -> &'a FrozenEntry<T> {
+        &self.tree.nodes[self.index]
+    }
+    fn at(&self, index: usize) -> Self {
+        Self { tree: self.tree, index }
+    }
+
+    pub fn content(&self) -> &'a T {
+        &self.entry().content
+    }
+    pub fn parent(&self) -> Option<Self> {
+        self.entry().parent.map(|parent| self.at(parent))
+    }
+    /// Iterate over the **children** of this [`Node`]. Does not allocate.
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator {
+        let this = *self;
+        self.entry().children.clone().map(move |child| this.at(child))
+    }
+
+    /// Returns the [`Node`] immediately following this one in the **parent**'s [`children`](Self::children()).
+    /// Otherwise returns [`None`] if `self` has no **parent**, or if it is the *last* child of the **parent**.
+    pub fn next_sibling(&self) -> Option<Self> {
+        let siblings = &self.tree.nodes[self.entry().parent?].children;
+        (self.index + 1 < siblings.end).then(|| self.at(self.index + 1))
+    }
+    /// Returns the [`Node`] immediately preceeding this one in the **parent**'s [`children`](Self::children()).
+    /// Otherwise returns [`None`] if `self` has no **parent**, or if it is the *first* child of the **parent**.
+    pub fn prev_sibling(&self) -> Option<Self> {
+        let siblings = &self.tree.nodes[self.entry().parent?].children;
+        (self.index > siblings.start).then(|| self.at(self.index - 1))
+    }
+
+    /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = Self> {
+        let mut stack = vec![*self];
+        std::iter::from_fn(move || {
+            let popped = stack.pop()?;
+            stack.extend(popped.children().rev());
+            Some(popped)
+        })
+    }
+
+    #[inline]
+    /// Whether two [`FrozenNode`]s are the same (that is, they reference the same object).
+    pub fn is_same_as(&self, other: Self) -> bool {
+        std::ptr::eq(self.tree, other.tree) && self.index == other.index
+    }
+}
+impl<T> Clone for FrozenNode<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for FrozenNode<'_, T> {}
+impl<T> PartialEq for FrozenNode<'_, T>
+where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.content() == other.content()
+    }
+}
+impl<T> Eq for FrozenNode<'_, T>
+where T: Eq {}
+impl<T> Debug for FrozenNode<'_, T>
+where T: Debug {
+    /// Like [`DebugTree`], prints the entire subtree.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("content", self.content())
+            .field("children", &self.children().collect::<Box<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,122 @@
+use super::*;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Every [`Node`] gets a different *generation*, even if it is allocated at the same address as a [`Node`] that was dropped.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+pub(crate) fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+thread_local! {
+    /// The *generation* of every live [`Node`] that a [`NodeRef`] was made for, by address.
+    /// A [`Node`] is removed when it is dropped, so a [`NodeRef`] is only dereferenced if its [`Node`] is still in the table.
+    /// [`Node`]s (and their [`NodeRef`]s) can't be sent to other threads, so the table can be thread-local.
+    static LIVE: RefCell<HashMap<NonNull<()>, u64>> = RefCell::new(HashMap::new());
+}
+/// Adds a *heap-allocated* [`Node`] to the table of live [`Node`]s.
+pub(crate) fn register(ptr: NonNull<()>, generation: u64) {
+    LIVE.with(|live| live.borrow_mut().insert(ptr, generation));
+}
+/// Removes a [`Node`] that is about to be dropped from the table of live [`Node`]s.
+pub(crate) fn unregister(ptr: NonNull<()>) {
+    // The table can already be destroyed if a Tree is dropped by another thread-local's destructor.
+    let _ = LIVE.try_with(|live| live.borrow_mut().remove(&ptr));
+}
+fn is_live(ptr: NonNull<()>, generation: u64) -> bool {
+    LIVE.with(|live| live.borrow().get(&ptr) == Some(&generation))
+}
+
+/// A handle to a [`Node`] that, unlike the *NonNull pointer* obtained from [`Node::ptr()`],
+/// can be safely used after the [`Node`] was detached or dropped. Obtained by calling [`Node::node_ref()`].
+///
+/// The handle stores the address and the *generation* of the [`Node`].
+/// It is only dereferenced if a [`Node`] with both is still alive, which is tracked by a thread-local table of the [`Node`]s that have handles.
+/// See [`Tree::borrow_by_ref()`] and [`Tree::detach_by_ref()`].
+pub struct NodeRef<T, M = ()> {
+    ptr: NonNull<Node<T, M>>,
+    generation: u64,
+}
+impl<T, M> NodeRef<T, M> {
+    pub(crate) fn new(node: &Node<T, M>, generation: u64) -> Self {
+        Self { ptr: node.ptr(), generation }
+    }
+}
+impl<T, M> Clone for NodeRef<T, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, M> Copy for NodeRef<T, M> {}
+impl<T, M> PartialEq for NodeRef<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.generation == other.generation
+    }
+}
+impl<T, M> Eq for NodeRef<T, M> {}
+impl<T, M> Hash for NodeRef<T, M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ptr.hash(state);
+        self.generation.hash(state);
+    }
+}
+impl<T, M> std::fmt::Debug for NodeRef<T, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("ptr", &self.ptr)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T, M> Tree<T, M> {
+    /// Check that the [`Node`] that **node** refers to is still alive and is in this [`Tree`]. This is `O(depth)`.
+    fn find_ref(&self, node: NodeRef<T, M>) -> Result<NonNull<Node<T, M>>, TreeError> {
+        if !is_live(node.ptr.cast(), node.generation) {
+            return Err(TreeError::DanglingHandle);
+        }
+        // The Node is alive, and no other Node has its generation, so it is the Node the handle was made for.
+        let mut root = unsafe { node.ptr.as_ref() };
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        if root.is_same_as(self.root()) {
+            Ok(node.ptr)
+        } else {
+            Err(TreeError::DanglingHandle)
+        }
+    }
+
+    /// Like [`Tree::borrow_descendant()`], but takes a [`NodeRef`], so it is safe to call even if the [`Node`] was detached or dropped.
+    ///
+    /// Returns [`TreeError::DanglingHandle`] if **node** doesn't refer to a [`Node`] in this [`Tree`],
+    /// or [`TreeError::NotADescendant`] if it refers to the **root**.
+    ///
+    /// Unlike [`Tree::borrow_descendant()`], this is `O(depth)`, because the [`Node`] is checked to be in the [`Tree`] by walking up to its **root**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Node, TreeError};
+    /// let mut tree = Node::builder('a').child(Node::builder('b')).build();
+    /// let b = tree.root().children()[0].node_ref();
+    /// tree.borrow_by_ref(b).unwrap().replace_content('c');
+    ///
+    /// drop(tree.detach_by_ref(b).unwrap());
+    /// assert_eq!(tree.borrow_by_ref(b).unwrap_err(), TreeError::DanglingHandle);
+    /// ```
+    pub fn borrow_by_ref(&mut self, node: NodeRef<T, M>) -> Result<Pin<&mut Node<T, M>>, TreeError> {
+        let ptr = self.find_ref(node)?;
+        self.try_borrow_descendant(ptr)
+    }
+    /// Like [`Tree::detach_descendant()`], but takes a [`NodeRef`], so it is safe to call even if the [`Node`] was detached or dropped.
+    /// See [`Tree::borrow_by_ref()`].
+    pub fn detach_by_ref(&mut self, node: NodeRef<T, M>) -> Result<Self, TreeError> {
+        let ptr = self.find_ref(node)?;
+        self.try_detach_descendant(ptr)
+    }
+}
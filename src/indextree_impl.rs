@@ -0,0 +1,99 @@
+//! Conversions between [`Tree`] and [`indextree`]'s [`Arena`].
+use super::*;
+use indextree::{Arena, NodeId};
+use std::collections::VecDeque;
+
+/// Returned when converting an [`Arena`] that doesn't hold exactly one [`Tree`] into a [`Tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArenaError {
+    /// The [`Arena`] has no (non-removed) nodes.
+    Empty,
+    /// The [`Arena`] has more than one *root* (i.e. it is a *forest*).
+    /// Use [`Tree::from_arena()`] to convert only one of them.
+    MultipleRoots,
+}
+impl std::fmt::Display for ArenaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the arena is empty"),
+            Self::MultipleRoots => write!(f, "the arena has more than one root"),
+        }
+    }
+}
+impl std::error::Error for ArenaError {}
+
+impl<T> Tree<T> {
+    /// Consumes the [`Arena`] and builds a [`Tree`] with the subtree of the node **root**.
+    /// All the other nodes of the [`Arena`] are dropped.
+    ///
+    /// Returns [`None`] if **root** was removed from the [`Arena`] (or belongs to a different [`Arena`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let mut arena = indextree::Arena::new();
+    /// let a = arena.new_node('a');
+    /// let b = a.append_value('b', &mut arena);
+    /// b.append_value('c', &mut arena);
+    /// a.append_value('d', &mut arena);
+    ///
+    /// let tree = Tree::from_arena(arena, b).unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "bc");
+    /// ```
+    pub fn from_arena(arena: Arena<T>, root: NodeId) -> Option<Self> {
+        if arena.get(root).is_none_or(|node| node.is_removed()) {
+            return None;
+        }
+
+        // Get the shape first, then move the contents out of the Arena.
+        let mut entries = vec![];
+        let mut queue = VecDeque::from([(None, root)]);
+        while let Some((parent, id)) = queue.pop_front() {
+            let index = entries.len();
+            entries.push((parent, usize::from(id) - 1));
+            queue.extend(id.children(&arena).map(|child| (Some(index), child)));
+        }
+
+        let mut contents = arena.into_iter().map(|node| node.into_data()).collect::<Vec<_>>();
+        let entries = entries
+            .into_iter()
+            .map(|(parent, index)| (parent, contents[index].take().unwrap()))
+            .collect();
+        // The entries are in Breadth-First order, so every parent comes before its children.
+        Self::from_flat(entries).ok()
+    }
+}
+
+impl<T> From<Tree<T>> for Arena<T> {
+    /// Moves all the [`Node`]s of the [`Tree`] into a new [`Arena`].
+    /// The **root** is the first node of the [`Arena`], and the rest are added in **Breadth-First** order.
+    fn from(tree: Tree<T>) -> Self {
+        let mut arena = Arena::new();
+        let mut queue = VecDeque::from([(None::<NodeId>, tree)]);
+
+        while let Some((parent, tree)) = queue.pop_front() {
+            let (content, children) = Node::into_parts(tree.root);
+            let id = arena.new_node(content);
+            if let Some(parent) = parent {
+                parent.append(id, &mut arena);
+            }
+            queue.extend(children.into_iter().map(|child| (Some(id), child)));
+        }
+
+        arena
+    }
+}
+impl<T> TryFrom<Arena<T>> for Tree<T> {
+    type Error = ArenaError;
+
+    /// Converts an [`Arena`] that holds a single [`Tree`] (i.e. it has only one *root*).
+    fn try_from(arena: Arena<T>) -> Result<Self, Self::Error> {
+        let mut roots = arena.roots();
+        let root = roots.next().ok_or(ArenaError::Empty)?;
+        if roots.next().is_some() {
+            return Err(ArenaError::MultipleRoots);
+        }
+        drop(roots);
+        Ok(Self::from_arena(arena, root).unwrap())
+    }
+}
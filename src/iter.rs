@@ -6,28 +6,28 @@ use std::collections::VecDeque;
 /// Obtained by calling [`Tree::iter_bfs()`] or [`Node::iter_bfs()`].
 ///
 /// There is also [`IterDFS`], which uses *Depth-First search*, but **BFS** is usually *faster* in most scenarios.
-pub struct IterBFS<'a, T> {
+pub struct IterBFS<'a, T, M = ()> {
     /* Apparently a Vec would perform better than a LinkedList in this case.
     https://stackoverflow.com/questions/40848918/are-there-queue-and-stack-collections-in-rust */
-    queue: VecDeque<&'a Node<T>>,
+    queue: VecDeque<&'a Node<T, M>>,
 }
-impl<'a, T> IterBFS<'a, T> {
-    pub(crate) fn new(node: &'a Node<T>) -> Self {
+impl<'a, T, M> IterBFS<'a, T, M> {
+    pub(crate) fn new(node: &'a Node<T, M>) -> Self {
         let mut queue = VecDeque::new();
         // Step 1: Enqueue the root.
         queue.push_back(node);
         Self { queue }
     }
 }
-impl<'a, T> Iterator for IterBFS<'a, T> {
-    type Item = &'a Node<T>;
+impl<'a, T, M> Iterator for IterBFS<'a, T, M> {
+    type Item = &'a Node<T, M>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Step 2: Get next from queue.
         let popped = self.queue.pop_front();
         if let Some(popped) = popped {
             // Step 3: Enqueue its children.
-            self.queue.extend(popped.children().iter());
+            self.queue.extend(popped.children_iter());
         }
         popped
     }
@@ -38,19 +38,19 @@ impl<'a, T> Iterator for IterBFS<'a, T> {
 /// Obtained by calling [`Tree::iter_dfs()`] or [`Node::iter_dfs()`].
 ///
 /// You should most likely use [`IterBFS`], which uses *Breadth-First search*, becase it is usually *faster* in most scenarios.
-pub struct IterDFS<'a, T> {
+pub struct IterDFS<'a, T, M = ()> {
     /* Apparently a Vec would perform better than a LinkedList in this case.
     https://stackoverflow.com/questions/40848918/are-there-queue-and-stack-collections-in-rust */
-    stack: Vec<&'a Node<T>>,
+    stack: Vec<&'a Node<T, M>>,
 }
-impl<'a, T> IterDFS<'a, T> {
-    pub(crate) fn new(node: &'a Node<T>) -> Self {
+impl<'a, T, M> IterDFS<'a, T, M> {
+    pub(crate) fn new(node: &'a Node<T, M>) -> Self {
         // Step 1: Push the root.
         Self { stack: vec![node] }
     }
 }
-impl<'a, T> Iterator for IterDFS<'a, T> {
-    type Item = &'a Node<T>;
+impl<'a, T, M> Iterator for IterDFS<'a, T, M> {
+    type Item = &'a Node<T, M>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Step 2: Get next from stack.
@@ -58,8 +58,206 @@ impl<'a, T> Iterator for IterDFS<'a, T> {
         if let Some(popped) = popped {
             // Step 3: Push its children.
             // Reverse because the first child should be popped next from the stack, so it must go last in the stack.
-            self.stack.extend(popped.children().iter().rev());
+            self.stack.extend(popped.children_iter().rev());
         }
         popped
     }
 }
+
+/// An [`Iterator`] over all the [`Node`]s of a [`Tree`] (or subtree) using a **non-recursive**, **Post-Order** *Depth-First Search* algorithm.
+/// A [`Node`] is yielded *after* all of its **children**.
+///
+/// Obtained by calling [`Tree::iter()`] or [`Node::iter()`] with [`TraversalOrder::PostOrder`].
+pub struct IterPostOrder<'a, T, M = ()> {
+    /// The bool indicates whether the [`Node`]'s children have already been pushed.
+    stack: Vec<(&'a Node<T, M>, bool)>,
+}
+impl<'a, T, M> IterPostOrder<'a, T, M> {
+    pub(crate) fn new(node: &'a Node<T, M>) -> Self {
+        // Step 1: Push the root.
+        Self { stack: vec![(node, false)] }
+    }
+}
+impl<'a, T, M> Iterator for IterPostOrder<'a, T, M> {
+    type Item = &'a Node<T, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Step 2: Get next from stack.
+            let (popped, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(popped);
+            }
+            // Step 3: Push it back, followed by its children, so that it is popped after them.
+            // Reverse because the first child should be popped next from the stack, so it must go last in the stack.
+            self.stack.push((popped, true));
+            self.stack.extend(popped.children_iter().rev().map(|child| (child, false)));
+        }
+    }
+}
+
+/// An [`Iterator`] over the [`Node`]s of a [`Tree`] (or subtree) that are at a certain *depth*, from left to right.
+/// The **root** is at depth `0`.
+///
+/// Obtained by calling [`Tree::iter()`] or [`Node::iter()`] with [`TraversalOrder::Level`].
+pub struct IterLevel<'a, T, M = ()> {
+    /// Each [`Node`] is stored with its *depth*.
+    stack: Vec<(&'a Node<T, M>, usize)>,
+    depth: usize,
+}
+impl<'a, T, M> IterLevel<'a, T, M> {
+    pub(crate) fn new(node: &'a Node<T, M>, depth: usize) -> Self {
+        Self {
+            stack: vec![(node, 0)],
+            depth,
+        }
+    }
+}
+impl<'a, T, M> Iterator for IterLevel<'a, T, M> {
+    type Item = &'a Node<T, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (popped, depth) = self.stack.pop()?;
+            if depth == self.depth {
+                return Some(popped);
+            }
+            // Don't need to go deeper than the target depth.
+            self.stack.extend(popped.children_iter().rev().map(|child| (child, depth + 1)));
+        }
+    }
+}
+
+/// An [`Iterator`] over the **children** of a [`Node`] that, unlike [`Node::children()`], does not allocate.
+///
+/// Obtained by calling [`Node::children_iter()`], and passed to the *chooser* of [`Node::descend_by()`].
+pub struct ChildrenIter<'a, T, M = ()>(std::slice::Iter<'a, Owned<Node<T, M>>>);
+impl<'a, T, M> ChildrenIter<'a, T, M> {
+    pub(crate) fn new(children: &'a [Owned<Node<T, M>>]) -> Self {
+        Self(children.iter())
+    }
+}
+impl<'a, T, M> Iterator for ChildrenIter<'a, T, M> {
+    type Item = &'a Node<T, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|child| child.as_ref().get_ref())
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<T, M> DoubleEndedIterator for ChildrenIter<'_, T, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|child| child.as_ref().get_ref())
+    }
+}
+impl<T, M> ExactSizeIterator for ChildrenIter<'_, T, M> {}
+impl<T, M> Clone for ChildrenIter<'_, T, M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// The order in which [`Tree::iter()`] and [`Node::iter()`] visit the [`Node`]s.
+///
+/// All orders guarantee that **siblings** are visited in the same order as they are in their **parent**'s [`children`](Node::children()),
+/// and that iterating over the same [`Tree`] twice yields the [`Node`]s in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraversalOrder {
+    /// Visit the [`Node`]s level by level. See [`IterBFS`].
+    BreadthFirst,
+    /// Visit a [`Node`] before its **children** (*pre-order*). See [`IterDFS`].
+    DepthFirst,
+    /// Visit a [`Node`] after its **children**. See [`IterPostOrder`].
+    PostOrder,
+    /// Only visit the [`Node`]s at this *depth* (the **root** is at depth `0`). See [`IterLevel`].
+    Level(usize),
+}
+
+/// An [`Iterator`] over the [`Node`]s of a [`Tree`] (or subtree) in some [`TraversalOrder`].
+///
+/// Obtained by calling [`Tree::iter()`] or [`Node::iter()`].
+pub struct Iter<'a, T, M = ()>(IterKind<'a, T, M>);
+enum IterKind<'a, T, M> {
+    BreadthFirst(IterBFS<'a, T, M>),
+    DepthFirst(IterDFS<'a, T, M>),
+    PostOrder(IterPostOrder<'a, T, M>),
+    Level(IterLevel<'a, T, M>),
+}
+impl<'a, T, M> Iter<'a, T, M> {
+    pub(crate) fn new(node: &'a Node<T, M>, order: TraversalOrder) -> Self {
+        Self(match order {
+            TraversalOrder::BreadthFirst => IterKind::BreadthFirst(IterBFS::new(node)),
+            TraversalOrder::DepthFirst => IterKind::DepthFirst(IterDFS::new(node)),
+            TraversalOrder::PostOrder => IterKind::PostOrder(IterPostOrder::new(node)),
+            TraversalOrder::Level(depth) => IterKind::Level(IterLevel::new(node, depth)),
+        })
+    }
+}
+impl<'a, T, M> Iterator for Iter<'a, T, M> {
+    type Item = &'a Node<T, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterKind::BreadthFirst(iter) => iter.next(),
+            IterKind::DepthFirst(iter) => iter.next(),
+            IterKind::PostOrder(iter) => iter.next(),
+            IterKind::Level(iter) => iter.next(),
+        }
+    }
+}
+
+/// A *consuming* [`Iterator`] over the **contents** of a [`Tree`] using a **non-recursive**, **Post-Order** *Depth-First Search* algorithm.
+/// Each **content** is yielded with its *path* (the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**),
+/// and only after all of the [`Node`]'s **children** have been consumed.
+///
+/// Obtained by calling [`Tree::into_subtrees_postorder()`].
+pub struct IntoIterPostOrder<T> {
+    stack: Vec<PostOrderFrame<T>>,
+}
+struct PostOrderFrame<T> {
+    path: Vec<usize>,
+    content: T,
+    children: std::iter::Enumerate<std::vec::IntoIter<Tree<T>>>,
+}
+impl<T> IntoIterPostOrder<T> {
+    pub(crate) fn new(tree: Tree<T>) -> Self {
+        let (content, children) = Node::into_parts(tree.root);
+        Self {
+            stack: vec![PostOrderFrame {
+                path: vec![],
+                content,
+                children: children.into_iter().enumerate(),
+            }],
+        }
+    }
+}
+impl<T> Iterator for IntoIterPostOrder<T> {
+    type Item = (Vec<usize>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let top = self.stack.last_mut()?;
+
+            match top.children.next() {
+                // Consume the children first.
+                Some((index, child)) => {
+                    let mut path = top.path.clone();
+                    path.push(index);
+                    let (content, children) = Node::into_parts(child.root);
+                    self.stack.push(PostOrderFrame {
+                        path,
+                        content,
+                        children: children.into_iter().enumerate(),
+                    });
+                }
+                // All children have been consumed.
+                None => {
+                    let popped = self.stack.pop()?;
+                    return Some((popped.path, popped.content));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! A [`Tree`] whose **children** are stored in an ordered map, and are addressed by a *key* instead of an index,
+//! for *configuration trees* and *tries* where the **children** have names.
+//!
+//! The **children** of a [`Node`] are always in the order of their *keys*, and a [`Node`] can't have 2 **children** with the same *key*.
+//! [`Node`]s don't have a pointer to their **parent**, so they don't need to be [`Pin`](std::pin::Pin)ned,
+//! and they can be borrowed mutably (e.g. with [`Node::get_child_mut()`]) without going through the [`Tree`].
+//!
+//! # Example
+//! ```
+//! # use tree_struct::keyed::Tree;
+//! let mut tree = Tree::new("config");
+//! let window = tree.root_mut().child_or_insert_with("window", || "");
+//! window.insert_child("width", Tree::new("800"));
+//! window.insert_child("height", Tree::new("600"));
+//!
+//! assert_eq!(tree.root().get_path(["window", "height"]).unwrap().content, "600");
+//! assert_eq!(tree.root()["window"].children().map(|(key, _)| *key).collect::<Vec<_>>(), ["height", "width"]);
+//! assert_eq!(tree.len(), 4);
+//! ```
+use std::{
+    borrow::Borrow,
+    collections::{btree_map, BTreeMap, VecDeque},
+    ops::{Deref, DerefMut, Index},
+};
+
+/// A [`Node`] whose **children** are addressed by a *key* of type **`K`**. See the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Node<K, T> {
+    pub content: T,
+    children: BTreeMap<K, Self>,
+}
+impl<K, T> Node<K, T>
+where K: Ord {
+    fn new(content: T) -> Self {
+        Self {
+            content,
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// Iterate over the **children** (and their *keys*) in the order of their *keys*.
+    #[inline]
+    pub fn children(&self) -> btree_map::Iter<'_, K, Self> {
+        self.children.iter()
+    }
+    /// Like [`Self::children()`], but the **children** are `mutable`.
+    #[inline]
+    pub fn children_mut(&mut self) -> btree_map::IterMut<'_, K, Self> {
+        self.children.iter_mut()
+    }
+    /// The number of **children** (not all the descendants).
+    #[inline]
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+    /// Get the *child* with **key**, if any.
+    #[inline]
+    pub fn get_child<Q>(&self, key: &Q) -> Option<&Self>
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.children.get(key)
+    }
+    /// Like [`Self::get_child()`], but also gets the *key* that is stored in **self** (which may be a different type than **key**).
+    #[inline]
+    pub fn get_child_key_value<Q>(&self, key: &Q) -> Option<(&K, &Self)>
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.children.get_key_value(key)
+    }
+    /// Get the *child* with **key** as `mutable`, if any.
+    #[inline]
+    pub fn get_child_mut<Q>(&mut self, key: &Q) -> Option<&mut Self>
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.children.get_mut(key)
+    }
+    /// Follows the **keys** down from **self**, one *child* at a time.
+    /// Returns [`None`] if one of the *children* doesn't exist.
+    pub fn get_path<'a, Q>(&self, keys: impl IntoIterator<Item = &'a Q>) -> Option<&Self>
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        keys.into_iter().try_fold(self, |node, key| node.get_child(key))
+    }
+    /// Like [`Self::get_path()`], but the [`Node`] is `mutable`.
+    pub fn get_path_mut<'a, Q>(&mut self, keys: impl IntoIterator<Item = &'a Q>) -> Option<&mut Self>
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        keys.into_iter().try_fold(self, |node, key| node.get_child_mut(key))
+    }
+
+    /// Adds **child** with **key**, and returns the *child* that had the same **key** (if any).
+    #[inline]
+    pub fn insert_child(&mut self, key: K, child: Tree<K, T>) -> Option<Tree<K, T>> {
+        self.children.insert(key, child.root).map(|root| Tree { root })
+    }
+    /// Get the *child* with **key**, or add a *child* with the **content** returned by **f** and get that one.
+    /// **f** is only called if there is no such *child*, and the *child* is only searched once.
+    #[inline]
+    pub fn child_or_insert_with(&mut self, key: K, f: impl FnOnce() -> T) -> &mut Self {
+        self.children.entry(key).or_insert_with(|| Self::new(f()))
+    }
+    /// Removes the *child* with **key** (and its subtree), and returns it as a [`Tree`].
+    #[inline]
+    pub fn remove_child<Q>(&mut self, key: &Q) -> Option<Tree<K, T>>
+    where K: Borrow<Q>, Q: Ord + ?Sized {
+        self.children.remove(key).map(|root| Tree { root })
+    }
+
+    /// The number of [`Node`]s in the *subtree* (including **self**).
+    /// This is `O(n)`, because the [`Node`]s don't keep track of their **parent** to update its count.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.iter_dfs().count()
+    }
+    /// Always `false`, because the *subtree* always has **self**.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Iterate over the [`Node`]s of the *subtree* using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = &Self> + '_ {
+        let mut queue = VecDeque::from([self]);
+        std::iter::from_fn(move || {
+            let node = queue.pop_front()?;
+            queue.extend(node.children.values());
+            Some(node)
+        })
+    }
+    /// Iterate over the [`Node`]s of the *subtree* using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = &Self> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(node.children.values().rev());
+            Some(node)
+        })
+    }
+}
+impl<K, T, Q> Index<&Q> for Node<K, T>
+where K: Ord + Borrow<Q>, Q: Ord + ?Sized {
+    type Output = Self;
+
+    /// # Panics
+    /// If there is no *child* with **key**.
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get_child(key).expect("No child with this key")
+    }
+}
+impl<K, T> Drop for Node<K, T> {
+    /// Drops the *subtree* without recursion, so that very deep [`Tree`]s don't overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children).into_values().collect::<Vec<_>>();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(std::mem::take(&mut node.children).into_values());
+        }
+    }
+}
+
+/// Owns a [`Node`] whose **children** are addressed by a *key*. See the [module documentation](self).
+///
+/// A [`Tree`] can be used as its **root** [`Node`] through [`Deref`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tree<K, T> {
+    root: Node<K, T>,
+}
+impl<K, T> Tree<K, T>
+where K: Ord {
+    /// A [`Tree`] with only a **root**.
+    #[inline]
+    pub fn new(root: T) -> Self {
+        Self { root: Node::new(root) }
+    }
+
+    #[inline]
+    pub fn root(&self) -> &Node<K, T> {
+        &self.root
+    }
+    #[inline]
+    pub fn root_mut(&mut self) -> &mut Node<K, T> {
+        &mut self.root
+    }
+}
+impl<K, T> Deref for Tree<K, T> {
+    type Target = Node<K, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.root
+    }
+}
+impl<K, T> DerefMut for Tree<K, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.root
+    }
+}
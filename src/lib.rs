@@ -1,15 +1,107 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#[cfg(feature = "allocator_api")]
+mod alloc;
+mod binary;
+mod cached;
+pub mod diff;
+mod display;
+mod drop_hook;
+mod error;
+mod flat;
+mod frozen;
+mod handle;
+#[cfg(feature = "indextree")]
+mod indextree_impl;
 mod iter;
+mod merge;
 mod node;
+mod ops;
+mod owned;
+mod path;
+#[cfg(feature = "petgraph")]
+mod petgraph_impl;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod static_tree;
+mod text;
+mod versioned;
+mod zipper;
 #[cfg(feature = "rc")]
 pub mod rc;
+#[cfg(feature = "arc")]
+pub mod sync;
+#[cfg(feature = "async")]
+pub mod async_rc;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "keyed")]
+pub mod keyed;
+#[cfg(feature = "trie")]
+pub mod trie;
+#[cfg(feature = "persistent")]
+pub mod persistent;
 
-pub use iter::{IterBFS, IterDFS};
-pub use node::{Node, NodeBuilder};
+pub use binary::{DecodeError, Encode};
+pub use cached::{Cached, CachedTree, EvictionPolicy, HeapSize};
+pub use display::{DisplayCompact, FormatTree, IndentStyle, TreeFormatter};
+pub use drop_hook::DropHookTree;
+pub use error::TreeError;
+pub use flat::FlatError;
+pub use frozen::{FrozenNode, FrozenTree};
+pub use handle::NodeRef;
+#[cfg(feature = "indextree")]
+pub use indextree_impl::ArenaError;
+pub use iter::{ChildrenIter, IntoIterPostOrder, Iter, IterBFS, IterDFS, IterLevel, IterPostOrder, TraversalOrder};
+pub use merge::MergeAction;
+pub use node::{ChildEntry, Descend, Node, NodeBuilder};
+pub use ops::{Descendants, NodeOps, TreeOps};
+pub use path::NodePath;
+#[cfg(feature = "petgraph")]
+pub use petgraph_impl::GraphError;
+pub use static_tree::StaticNode;
+pub use text::{BracketError, IndentedError, SexprError, TextError, TextFormat};
+pub use versioned::{ChangeKind, Version, Versioned, VersionedTree};
+pub use zipper::TreeZipper;
 use std::{fmt::Debug, pin::Pin, ptr::NonNull};
+use cfg_if::cfg_if;
+use owned::Owned;
 
-type Owned<T> = Pin<Box<T>>;
 type Parent<T> = NonNull<T>;
+// Helper functions that allow writing the same code with or without a custom allocator.
+cfg_if! {
+    if #[cfg(feature = "allocator_api")] {
+        pub use alloc::TreeAllocator;
+        type Children<T> = Vec<Owned<T>, TreeAllocator>;
+
+        #[inline]
+        fn children_in<T>(alloc: &TreeAllocator) -> Children<T> {
+            Vec::new_in(alloc.clone())
+        }
+        /// The allocator of a *children* list, which is also the one its [`Node`] was allocated with.
+        #[inline]
+        fn allocator_of<T>(children: &Children<T>) -> TreeAllocator {
+            children.allocator().clone()
+        }
+    } else {
+        /// Without the `allocator_api` feature, everything is allocated with the global allocator.
+        /// (Not a unit struct, so it is created with `default()` like the real one.)
+        #[derive(Clone, Default)]
+        struct TreeAllocator(());
+        type Children<T> = Vec<Owned<T>>;
+
+        #[inline]
+        fn children_in<T>(_: &TreeAllocator) -> Children<T> {
+            Vec::new()
+        }
+        #[inline]
+        fn allocator_of<T>(_: &Children<T>) -> TreeAllocator {
+            TreeAllocator::default()
+        }
+    }
+}
 
 /// A Tree of [`Node`]s.
 /// The root of the Tree has *no parent*.
@@ -18,8 +110,8 @@ type Parent<T> = NonNull<T>;
 /// When a [`Node`] method *returns* this type, it means it is **passing ownership** of the [`Node`]s.
 ///
 /// When a [`Node`] method *asks* for this type as argument, it means it is **taking ownership** of the [`Node`]s.
-pub struct Tree<T> {
-    root: Owned<Node<T>>,
+pub struct Tree<T, M = ()> {
+    root: Owned<Node<T, M>>,
 }
 impl<T> Tree<T> {
     #[inline]
@@ -27,13 +119,158 @@ impl<T> Tree<T> {
         NodeBuilder::new(content)
     }
 
-    pub fn root(&self) -> &Node<T> {
+    /// Create a new [`Tree`] with the same *shape* as **shape**, where the **content** of each [`Node`] is generated by **f**.
+    ///
+    /// **f** is called with the **content** of the corresponding [`Node`] in **shape**, and its *path*
+    /// (the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Node, Tree};
+    /// let input = Node::builder("a").child(Node::builder("bb").child(Node::builder("ccc"))).build();
+    /// let lengths = Tree::fill_like(&input, |s, _| s.len());
+    /// let paths = Tree::fill_like(&input, |_, path| path.to_vec());
+    ///
+    /// assert_eq!(lengths.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert_eq!(paths.iter_bfs().last().unwrap().content, vec![0, 0]);
+    /// ```
+    pub fn fill_like<U>(shape: &Tree<U>, mut f: impl FnMut(&U, &[usize]) -> T) -> Self {
+        let tree = NodeBuilder::new(f(&shape.root().content, &[])).build();
+        // Every new Node, always after its parent.
+        let mut built = vec![];
+        // The path of the Node whose children are on top of the stack.
+        let mut path = vec![];
+        // The children of each Node of shape that are left to copy, with the copy of that Node.
+        let mut stack = vec![(shape.root().children_iter().enumerate(), tree.root().ptr())];
+
+        while let Some((children, parent)) = stack.last_mut() {
+            let Some((index, child)) = children.next() else {
+                stack.pop();
+                path.pop();
+                continue;
+            };
+            let parent = *parent;
+            path.push(index);
+            // Only the Nodes of the new Tree are mutated, and each one is only borrowed while a child is appended.
+            let new_child = unsafe { Node::push_leaf(parent, f(&child.content, &path), ()) };
+            built.push(new_child);
+            stack.push((child.children_iter().enumerate(), new_child));
+        }
+
+        unsafe { Node::add_up_lens(built) };
+        tree
+    }
+
+    /// Builds a [`Tree`] by expanding **seed**: **f** returns the **content** of the [`Node`] for a *seed*,
+    /// and the *seeds* of its **children** (in order).
+    /// **f** is called for the *seeds* in **Depth-First** order (see [`Tree::unfold_bfs()`] for **Breadth-First** order).
+    ///
+    /// Doesn't recurse, so [`Tree`]s of any depth can be built.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// // Each number is split in two halves until it reaches 1.
+    /// let tree = Tree::unfold(5, |n| (n, if n > 1 { vec![n / 2, n - n / 2] } else { vec![] }));
+    /// assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), vec![5, 2, 1, 1, 3, 1, 2, 1, 1]);
+    /// ```
+    pub fn unfold<S, I>(seed: S, f: impl FnMut(S) -> (T, I)) -> Self
+    where I: IntoIterator<Item = S> {
+        Self::unfold_with(seed, f, false)
+    }
+    /// Like [`Tree::unfold()`], but **f** is called for the *seeds* in **Breadth-First** order,
+    /// e.g. to stop expanding when the [`Tree`] gets too big without leaving a branch much deeper than the others.
+    pub fn unfold_bfs<S, I>(seed: S, f: impl FnMut(S) -> (T, I)) -> Self
+    where I: IntoIterator<Item = S> {
+        Self::unfold_with(seed, f, true)
+    }
+    fn unfold_with<S, I>(seed: S, mut f: impl FnMut(S) -> (T, I), bfs: bool) -> Self
+    where I: IntoIterator<Item = S> {
+        let (content, seeds) = f(seed);
+        let tree = NodeBuilder::new(content).build();
+        // Every new Node, always after its parent.
+        let mut built = vec![];
+        // Each seed that hasn't been expanded yet, with the Node its Node will be appended to.
+        let mut pending = std::collections::VecDeque::new();
+        let push_seeds = |pending: &mut std::collections::VecDeque<_>, parent: NonNull<Node<T>>, seeds: I| {
+            if bfs {
+                pending.extend(seeds.into_iter().map(|seed| (parent, seed)));
+            } else {
+                // Reverse because the first child should be popped next.
+                let seeds = seeds.into_iter().collect::<Vec<_>>();
+                pending.extend(seeds.into_iter().rev().map(|seed| (parent, seed)));
+            }
+        };
+        push_seeds(&mut pending, tree.root().ptr(), seeds);
+
+        while let Some((parent, seed)) = if bfs { pending.pop_front() } else { pending.pop_back() } {
+            let (content, seeds) = f(seed);
+            // Only the Nodes of the new Tree are mutated, and each one is only borrowed while a child is appended.
+            let child = unsafe { Node::push_leaf(parent, content, ()) };
+            built.push(child);
+            push_seeds(&mut pending, child, seeds);
+        }
+
+        unsafe { Node::add_up_lens(built) };
+        tree
+    }
+
+    #[inline]
+    /// Consume the [`Tree`] from the bottom up, yielding each [`Node`]'s *path* and owned **content** after its **children**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).child(Node::builder('d')).build();
+    /// assert_eq!(
+    ///     tree.into_subtrees_postorder().collect::<Vec<_>>(),
+    ///     vec![(vec![0, 0], 'c'), (vec![0], 'b'), (vec![1], 'd'), (vec![], 'a')]
+    /// );
+    /// ```
+    pub fn into_subtrees_postorder(self) -> IntoIterPostOrder<T> {
+        IntoIterPostOrder::new(self)
+    }
+
+    #[inline]
+    /// Call **hook** with the **content** of every [`Node`] (in **Post-Order**) when the [`Tree`] is dropped.
+    /// See [`DropHookTree`].
+    pub fn with_drop_hook<F>(self, hook: F) -> DropHookTree<T, F>
+    where F: FnMut(&mut T) {
+        DropHookTree::new(self, hook)
+    }
+
+    /// Convert the [`Tree`] into a [`FrozenTree`], which stores all [`Node`]s contiguously and can't be structurally modified.
+    /// Use [`FrozenTree::thaw()`] to get the [`Tree`] back.
+    #[inline]
+    pub fn freeze(self) -> FrozenTree<T> {
+        FrozenTree::new(self)
+    }
+}
+impl<T, M> Tree<T, M> {
+    pub fn root(&self) -> &Node<T, M> {
         self.root.as_ref().get_ref()
     }
-    pub fn root_mut(&mut self) -> Pin<&mut Node<T>> {
+    pub fn root_mut(&mut self) -> Pin<&mut Node<T, M>> {
         self.root.as_mut()
     }
 
+    #[inline]
+    /// Performs a *right rotation* at the **root**, so the **root**'s *first* child becomes the new **root**.
+    /// See [`Node::rotate_right()`].
+    ///
+    /// Returns `false` (and doesn't modify anything) if the **root** has no *children*.
+    pub fn rotate_right(&mut self) -> bool {
+        Node::rotate(&mut self.root, true)
+    }
+    #[inline]
+    /// Performs a *left rotation* at the **root**, so the **root**'s *last* child becomes the new **root**.
+    /// See [`Node::rotate_left()`].
+    ///
+    /// Returns `false` (and doesn't modify anything) if the **root** has no *children*.
+    pub fn rotate_left(&mut self) -> bool {
+        Node::rotate(&mut self.root, false)
+    }
+
     /// Removes the **descendant** of the **root [`Node`]** from the [`Tree`], and returns the *detached [`Node`]* with ownership (aka a [`Tree`]).
     ///
     /// Returns [`None`] if it is not a **descendant** of the **root**, or **root** [`is_same_as`](Node::is_same_as()) **descendant**.
@@ -53,7 +290,7 @@ impl<T> Tree<T> {
     /// assert!(detached.root().is_same_as(target));
     /// ```
     #[inline]
-    pub fn detach_descendant(&mut self, descendant: NonNull<Node<T>>) -> Option<Self> {
+    pub fn detach_descendant(&mut self, descendant: NonNull<Node<T, M>>) -> Option<Self> {
         self.root_mut().detach_descendant(descendant)
     }
 
@@ -79,27 +316,266 @@ impl<T> Tree<T> {
     ///
     /// It should be enough to assert that the whole [`Tree`] is `mut`, so by extension the **descendant** is also `mut`.
     #[inline]
-    pub fn borrow_descendant(&mut self, descendant: NonNull<Node<T>>) -> Option<Pin<&mut Node<T>>> {
+    pub fn borrow_descendant(&mut self, descendant: NonNull<Node<T, M>>) -> Option<Pin<&mut Node<T, M>>> {
         self.root_mut().borrow_descendant(descendant)
     }
 
+    /// Moves a **descendant** of the **root [`Node`]** (along with its subtree) to be the *child* of **new_parent** at **index**,
+    /// without creating an intermediate [`Tree`].
+    /// **index** is the position in **new_parent**'s *children* *after* **descendant** was removed from its old **parent**.
+    ///
+    /// Returns `false` (and doesn't modify the [`Tree`]) if **descendant** is not a **descendant** of the **root**,
+    /// if **new_parent** is not in the [`Tree`] or is in the subtree of **descendant**, or if **index** is out of bounds.
+    ///
+    /// Like [`Tree::detach_descendant()`], **descendant** and **new_parent** must be *NonNull pointers* (obtained from [`Node::ptr`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a')
+    ///     .child(Node::builder('b').child(Node::builder('c')))
+    ///     .child(Node::builder('d'))
+    ///     .build();
+    /// let c = tree.root().children()[0].children()[0].ptr();
+    /// let root = tree.root().ptr();
+    /// assert!(tree.move_descendant(c, root, 0));
+    /// assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "acbd");
+    /// ```
+    #[inline]
+    pub fn move_descendant(&mut self, descendant: NonNull<Node<T, M>>, new_parent: NonNull<Node<T, M>>, index: usize) -> bool {
+        self.root_mut().move_descendant(descendant, new_parent, index)
+    }
+
+    /// Removes a **descendant** of the **root [`Node`]**, but moves its **children** to its **parent**'s *children* (at the removed [`Node`]'s position),
+    /// and returns the removed [`Node`]'s **content**.
+    ///
+    /// Returns [`None`] if it is not a **descendant** of the **root**, or **root** [`is_same_as`](Node::is_same_as()) **descendant**.
+    ///
+    /// Like [`Tree::detach_descendant()`], **descendant** must be a *NonNull pointer* (obtained from [`Node::ptr`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a')
+    ///     .child(Node::builder('b').child(Node::builder('c')).child(Node::builder('d')))
+    ///     .child(Node::builder('e'))
+    ///     .build();
+    /// let b = tree.root().children()[0].ptr();
+    /// assert_eq!(tree.remove_promote(b), Some('b'));
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "acde");
+    /// ```
+    #[inline]
+    pub fn remove_promote(&mut self, descendant: NonNull<Node<T, M>>) -> Option<T> {
+        self.root_mut().remove_promote(descendant)
+    }
+
+    /// Exchanges the places of two **descendants** of the **root [`Node`]** (along with their subtrees),
+    /// so that each one ends up in the other's **parent** at the other's index.
+    ///
+    /// Returns `false` (and doesn't modify the [`Tree`]) if either is not a **descendant** of the **root**,
+    /// or if one is an *ancestor* of the other (including being the same [`Node`]).
+    ///
+    /// Like [`Tree::detach_descendant()`], **a** and **b** must be *NonNull pointers* (obtained from [`Node::ptr`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a')
+    ///     .child(Node::builder('b').child(Node::builder('c')))
+    ///     .child(Node::builder('d'))
+    ///     .build();
+    /// let c = tree.root().children()[0].children()[0].ptr();
+    /// let d = tree.root().children()[1].ptr();
+    /// assert!(tree.swap_subtrees(c, d));
+    /// assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "abdc");
+    /// ```
+    pub fn swap_subtrees(&mut self, a: NonNull<Node<T, M>>, b: NonNull<Node<T, M>>) -> bool {
+        self.root_mut().swap_descendants(a, b)
+    }
+
     #[inline]
     /// Iterate over all the [`Node`]s of the [`Tree`] using **Breadth-First Search**.
-    pub fn iter_bfs(&self) -> IterBFS<T> {
+    pub fn iter_bfs(&self) -> IterBFS<T, M> {
         IterBFS::new(self.root())
     }
     #[inline]
     /// Iterate over all the [`Node`]s of the [`Tree`] using **Depth-First Search**.
-    pub fn iter_dfs(&self) -> IterDFS<T> {
+    pub fn iter_dfs(&self) -> IterDFS<T, M> {
         IterDFS::new(self.root())
     }
+    #[inline]
+    /// Iterate over the [`Node`]s of the [`Tree`] in the specified [`TraversalOrder`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Node, TraversalOrder};
+    /// let tree = Node::builder('a')
+    ///     .child(Node::builder('b').child(Node::builder('d')))
+    ///     .child(Node::builder('c'))
+    ///     .build();
+    /// let order = |order| tree.iter(order).map(|n| n.content).collect::<String>();
+    ///
+    /// assert_eq!(order(TraversalOrder::BreadthFirst), "abcd");
+    /// assert_eq!(order(TraversalOrder::DepthFirst), "abdc");
+    /// assert_eq!(order(TraversalOrder::PostOrder), "dbca");
+    /// assert_eq!(order(TraversalOrder::Level(1)), "bc");
+    /// ```
+    pub fn iter(&self, order: TraversalOrder) -> Iter<'_, T, M> {
+        Iter::new(self.root(), order)
+    }
+
+    #[inline]
+    /// Computes a value for every [`Node`] from its **content** and its **children**'s values, and returns the value of the **root**.
+    /// See [`Node::fold_bottom_up()`].
+    pub fn fold_bottom_up<R>(&self, f: impl FnMut(&T, Vec<R>) -> R) -> R {
+        self.root().fold_bottom_up(f)
+    }
+    #[inline]
+    /// Walk down from the **root**, letting **chooser** pick which **child** to descend into at each level.
+    /// See [`Node::descend_by()`].
+    pub fn descend_by(&self, chooser: impl FnMut(&T, ChildrenIter<'_, T, M>) -> Descend) -> &Node<T, M> {
+        self.root().descend_by(chooser)
+    }
+
+    /// Converts the **content** of every [`Node`] with **f**, keeping the same structure and *metadata*.
+    /// **f** is called on the [`Node`]s in **Depth-First** order.
+    /// Doesn't recurse, so [`Tree`]s of any depth can be converted.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tokens = Node::builder("+").child(Node::builder("1")).child(Node::builder("2")).build();
+    /// let lengths = tokens.map(|token| token.len());
+    /// assert_eq!(lengths.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 1, 1]);
+    /// ```
+    #[inline]
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Tree<U, M> {
+        Node::map(self.root, |content, meta| (f(content), meta))
+    }
+    /// Replaces the *metadata* of every [`Node`] (see [`Node::meta()`]) with the one returned by **f**, keeping the same **content**.
+    /// **f** is called on the [`Node`]s in **Depth-First** order.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder("+").child(Node::builder("1")).build();
+    /// let spans = tree.map_meta(|token, ()| token.len());
+    /// assert_eq!(*spans.root().children()[0].meta(), 1);
+    ///
+    /// let tree = spans.map_meta(|_, _| ());
+    /// assert_eq!(tree.root().content, "+");
+    /// ```
+    #[inline]
+    pub fn map_meta<N>(self, mut f: impl FnMut(&T, M) -> N) -> Tree<T, N> {
+        Node::map(self.root, |content, meta| {
+            let meta = f(&content, meta);
+            (content, meta)
+        })
+    }
+    /// Like [`Tree::map()`], but the [`Tree`] is not consumed and **f** is called with a reference to the **content**.
+    #[inline]
+    pub fn map_ref<U>(&self, f: impl FnMut(&T) -> U) -> Tree<U> {
+        self.root().map_ref(f)
+    }
+
+    /// Pairs up the **content** of every [`Node`] with the one at the same position in **other**,
+    /// creating a new [`Tree`] with the same shape as both.
+    ///
+    /// Returns a [`ShapeMismatch`] error if the [`Tree`]s don't have the same shape.
+    /// Doesn't recurse, so [`Tree`]s of any depth can be zipped.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let expected = Node::builder(1).child(Node::builder(2)).build();
+    /// let actual = Node::builder("1").child(Node::builder("2")).build();
+    /// let zipped = expected.zip(&actual).unwrap();
+    /// assert!(zipped.iter_bfs().all(|n| n.content.0.to_string() == *n.content.1));
+    ///
+    /// let different = Node::builder("1").child(Node::builder("2").child(Node::builder("3"))).build();
+    /// assert_eq!(expected.zip(&different).unwrap_err().path, vec![0]);
+    /// ```
+    #[inline]
+    pub fn zip<'a, 'b, U>(&'a self, other: &'b Tree<U>) -> Result<Tree<(&'a T, &'b U)>, ShapeMismatch> {
+        self.root().zip(other.root())
+    }
+
+    #[inline]
+    /// Drops all the [`Node`]s that are deeper than **max** (the **root** is at depth `0`). See [`Node::truncate_depth()`].
+    pub fn truncate_depth(&mut self, max: usize) {
+        self.root_mut().truncate_depth(max)
+    }
+    #[inline]
+    /// Removes every [`Node`] (along with its subtree) whose **content** does not satisfy the **predicate**.
+    /// The **root** is never removed. See [`Node::retain()`].
+    pub fn retain(&mut self, predicate: impl FnMut(&T) -> bool) -> Vec<Self> {
+        self.root_mut().retain(predicate)
+    }
+
+    /// Consume the [`Tree`] and collect the **content** of all its [`Node`]s in **Breadth-First** order.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    /// assert_eq!(tree.into_vec_bfs(), vec!['a', 'b', 'c', 'd']);
+    /// ```
+    pub fn into_vec_bfs(self) -> Vec<T> {
+        let mut contents = vec![];
+        let mut queue = std::collections::VecDeque::from([self]);
+
+        while let Some(tree) = queue.pop_front() {
+            let (content, children) = Node::into_parts(tree.root);
+            contents.push(content);
+            queue.extend(children);
+        }
+
+        contents
+    }
+    /// Consume the [`Tree`] and collect the **content** of all its [`Node`]s in **Depth-First** order.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    /// assert_eq!(tree.into_vec_dfs(), vec!['a', 'b', 'd', 'c']);
+    /// ```
+    pub fn into_vec_dfs(self) -> Vec<T> {
+        let mut contents = vec![];
+        let mut stack = vec![self];
+
+        while let Some(tree) = stack.pop() {
+            let (content, children) = Node::into_parts(tree.root);
+            contents.push(content);
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(children.into_iter().rev());
+        }
+
+        contents
+    }
+
+    #[inline]
+    /// The number of [`Node`]s in the [`Tree`]. This is `O(1)`, see [`Node::len()`].
+    pub fn len(&self) -> usize {
+        self.root().len()
+    }
+    #[inline]
+    /// Always `false`, because a [`Tree`] always has a **root**.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    #[inline]
+    /// Collect some metrics about the shape of the [`Tree`]. See [`Node::stats()`].
+    pub fn stats(&self) -> TreeStats {
+        self.root().stats()
+    }
 }
 
 /* Only Tree should implement IntoIter because , semantically, it makes sense to iterate through a Tree, but doesn't make sense to iterate through a Node.
 Node still has iter_bfs() and iter_dfs() in case the user wants to use it that way. */
-impl<'a, T> IntoIterator for &'a Tree<T> {
-    type Item = &'a Node<T>;
-    type IntoIter = IterBFS<'a, T>;
+impl<'a, T, M> IntoIterator for &'a Tree<T, M> {
+    type Item = &'a Node<T, M>;
+    type IntoIter = IterBFS<'a, T, M>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -119,22 +595,64 @@ where T: Default {
         NodeBuilder::default().build()
     }
 }
-impl<T> Clone for Tree<T>
-where T: Clone {
+impl<T, M> Clone for Tree<T, M>
+where T: Clone, M: Clone {
     /// Clones the entire [`Tree`] by calling [`Node::clone_deep()`] on the **root**.
     fn clone(&self) -> Self {
         self.root().clone_deep()
     }
 }
-impl<T> PartialEq for Tree<T>
+impl<T, M> PartialEq for Tree<T, M>
 where T: PartialEq {
+    /// Two [`Tree`]s are equal if they have the same shape and the same **content** in every position
+    /// (unlike [`Node`]s, which only compare their own **content**).
     fn eq(&self, other: &Self) -> bool {
-        self.root().eq(other.root())
+        self.len() == other.len()
+            && self.iter_dfs().zip(other.iter_dfs()).all(|(a, b)| {
+                a.children_iter().len() == b.children_iter().len() && a.content == b.content
+            })
     }
 }
-impl<T> Eq for Tree<T>
+impl<T, M> Eq for Tree<T, M>
 where T: Eq {}
-impl<T> Debug for Tree<T>
+impl<T, M> PartialOrd for Tree<T, M>
+where T: PartialOrd {
+    /// See [`Tree::cmp()`](Ord::cmp()).
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.iter_dfs().map(|n| &n.content).partial_cmp(other.iter_dfs().map(|n| &n.content))? {
+            std::cmp::Ordering::Equal => Some(self.shape().cmp(other.shape())),
+            ordering => Some(ordering),
+        }
+    }
+}
+impl<T, M> Ord for Tree<T, M>
+where T: Ord {
+    /// Compares the **contents** of the [`Tree`]s in **Depth-First** order, lexicographically (like [`Vec`]s).
+    /// If they are all equal, the [`Tree`] whose [`Node`]s have fewer *children* first (in the same order) is the lesser one,
+    /// so that only equal [`Tree`]s are [`Equal`](std::cmp::Ordering::Equal).
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.iter_dfs().map(|n| &n.content).cmp(other.iter_dfs().map(|n| &n.content))
+            .then_with(|| self.shape().cmp(other.shape()))
+    }
+}
+impl<T, M> Tree<T, M> {
+    /// The number of *children* of each [`Node`] in **Depth-First** order, which describes the shape of the [`Tree`].
+    fn shape(&self) -> impl Iterator<Item = usize> + '_ {
+        self.iter_dfs().map(|n| n.children_iter().len())
+    }
+}
+impl<T, M> std::hash::Hash for Tree<T, M>
+where T: std::hash::Hash {
+    /// Hashes the **content** and the number of *children* of every [`Node`] in **Depth-First** order,
+    /// which is consistent with the [`PartialEq`] implementation.
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for node in self.iter_dfs() {
+            node.content.hash(state);
+            node.children_iter().len().hash(state);
+        }
+    }
+}
+impl<T, M> Debug for Tree<T, M>
 where T: Debug {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Tree")
@@ -143,12 +661,44 @@ where T: Debug {
     }
 }
 
+/// Metrics about the shape of a [`Tree`] (or subtree), all computed in a single traversal.
+///
+/// Obtained by calling [`Tree::stats()`] or [`Node::stats()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TreeStats {
+    /// The number of [`Node`]s in the [`Tree`] (including the **root**).
+    pub nodes: usize,
+    /// The number of [`Node`]s with no **children**.
+    pub leaves: usize,
+    /// The number of edges in the longest path from the **root** to a *leaf*.
+    /// A [`Tree`] with only a **root** has a height of `0`.
+    pub height: usize,
+    /// The most **children** that a single [`Node`] has.
+    pub max_branching: usize,
+    /// The most [`Node`]s found in a single level of the [`Tree`].
+    pub max_width: usize,
+}
+
+/// Returned by [`Tree::zip()`] when the two [`Tree`]s don't have the same shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeMismatch {
+    /// The *path* of the first [`Node`] (in **Depth-First** order) whose **children** are different in each [`Tree`]:
+    /// the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**.
+    pub path: Vec<usize>,
+}
+impl std::fmt::Display for ShapeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the trees have a different number of children at path {:?}", self.path)
+    }
+}
+impl std::error::Error for ShapeMismatch {}
+
 /// Obtained by calling [`Node::debug_tree()`].
-pub struct DebugTree<'a, T>
+pub struct DebugTree<'a, T, M = ()>
 where T: Debug {
-    root: &'a Node<T>,
+    root: &'a Node<T, M>,
 }
-impl<'a, T> Debug for DebugTree<'a, T>
+impl<'a, T, M> Debug for DebugTree<'a, T, M>
 where T: Debug {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Node")
@@ -0,0 +1,103 @@
+use super::*;
+
+/// Returned by the *resolver* of [`Tree::merge()`] to decide what happens to the **children** of the two merged [`Node`]s.
+///
+/// The *resolver* is also responsible for merging the **content** (e.g. by combining both, or by replacing the old one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeAction {
+    /// Match the **children** of both [`Node`]s and merge each pair (calling the *resolver* again).
+    /// The **children** of the other [`Node`] that have no match are appended.
+    Combine,
+    /// Keep the **children** of the [`Node`] in `self`, and drop the other [`Node`]'s **children**.
+    Keep,
+    /// Replace the **children** of the [`Node`] in `self` with the other [`Node`]'s **children**.
+    Replace,
+}
+
+impl<T> Tree<T> {
+    /// Merges **other** into `self`, starting with the **roots**, and matching **children** *by position*
+    /// (i.e. the first child of a [`Node`] is merged with the first child of the other [`Node`]).
+    ///
+    /// For each pair of matched [`Node`]s, **resolve** is called with the **content** in `self` and the **content** from **other**,
+    /// and the returned [`MergeAction`] decides what to do with their **children**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{MergeAction, Node};
+    /// let mut config = Node::builder(1).child(Node::builder(2)).build();
+    /// let overlay = Node::builder(10).child(Node::builder(20)).child(Node::builder(30)).build();
+    /// config.merge(overlay, |a, b| {
+    ///     *a += b;
+    ///     MergeAction::Combine
+    /// });
+    /// assert_eq!(config.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![11, 22, 30]);
+    /// ```
+    pub fn merge(&mut self, other: Self, resolve: impl FnMut(&mut T, T) -> MergeAction) {
+        self.merge_with(other, |_, index, _| Some(index), resolve)
+    }
+    /// Like [`Tree::merge()`], but **children** are matched by the *key* returned by **key**
+    /// (i.e. a child of a [`Node`] is merged with the first child of the other [`Node`] that has the same *key*).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{MergeAction, Node};
+    /// let mut config = Node::builder(("root", 0)).child(Node::builder(("a", 1))).child(Node::builder(("b", 2))).build();
+    /// let overlay = Node::builder(("root", 0)).child(Node::builder(("b", 3))).child(Node::builder(("c", 4))).build();
+    /// config.merge_by_key(overlay, |(key, _)| *key, |a, b| {
+    ///     *a = b;
+    ///     MergeAction::Combine
+    /// });
+    /// assert_eq!(
+    ///     config.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+    ///     vec![("root", 0), ("a", 1), ("b", 3), ("c", 4)]
+    /// );
+    /// ```
+    pub fn merge_by_key<K: Eq>(&mut self, other: Self, mut key: impl FnMut(&T) -> K, resolve: impl FnMut(&mut T, T) -> MergeAction) {
+        self.merge_with(
+            other,
+            |children, _, content| {
+                let content = key(content);
+                children.iter().position(|child| key(&child.content) == content)
+            },
+            resolve,
+        )
+    }
+
+    /// **find_match** gets the **children** of a [`Node`] in `self`, and the index and **content** of a child of the other [`Node`],
+    /// and returns the index of the matching child in `self` (if any).
+    fn merge_with(
+        &mut self,
+        other: Self,
+        mut find_match: impl FnMut(&[&Node<T>], usize, &T) -> Option<usize>,
+        mut resolve: impl FnMut(&mut T, T) -> MergeAction,
+    ) {
+        let mut stack = vec![(self.root().ptr(), other)];
+
+        while let Some((mut target, other)) = stack.pop() {
+            // Only the Node at target is borrowed mutably, and no other references to it exist at this point.
+            let mut target = unsafe { Pin::new_unchecked(target.as_mut()) };
+            let (content, children) = Node::into_parts(other.root);
+
+            match resolve(&mut unsafe { target.as_mut().get_unchecked_mut() }.content, content) {
+                MergeAction::Keep => {}
+                MergeAction::Replace => {
+                    target.as_mut().clear_children();
+                    target.append_children(children);
+                }
+                MergeAction::Combine => {
+                    let target_children = target.children();
+                    let mut unmatched = vec![];
+
+                    for (index, child) in children.into_iter().enumerate() {
+                        match find_match(&target_children, index, &child.root().content) {
+                            Some(matched) if matched < target_children.len() => stack.push((target_children[matched].ptr(), child)),
+                            _ => unmatched.push(child),
+                        }
+                    }
+                    drop(target_children);
+                    target.append_children(unmatched);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::*;
 use ptrplus::AsPtr;
-use std::{marker::PhantomPinned, ptr::NonNull};
+use std::{cell::Cell, cmp::Ordering, marker::PhantomPinned, ops::RangeBounds, ptr::NonNull};
 
 /// Helper struct to build a [`Tree`] of [`Node`]s.
 ///
@@ -40,6 +40,7 @@ use std::{marker::PhantomPinned, ptr::NonNull};
 /// assert_eq!(tree1, tree2);
 /// ```
 #[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NodeBuilder<T> {
     pub content: T,
     pub children: Vec<Self>,
@@ -52,43 +53,128 @@ impl<T> NodeBuilder<T> {
             children: vec![],
         }
     }
+    /// New [`NodeBuilder`] with space for at least **capacity** *children* without reallocating.
+    /// The built [`Node`] keeps the capacity, so appending children to it later doesn't reallocate either.
+    pub fn with_children_capacity(content: T, capacity: usize) -> Self {
+        NodeBuilder {
+            content,
+            children: Vec::with_capacity(capacity),
+        }
+    }
     pub fn child(mut self, child: Self) -> Self {
         self.children.push(child);
         self
     }
+    /// Adds all the **children** at once, in order. Also see [`Self::child()`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder(0).children((1..=3).map(Node::builder)).child_content(4).build();
+    /// assert_eq!(tree.root().children().len(), 4);
+    /// ```
+    pub fn children(mut self, children: impl IntoIterator<Item = Self>) -> Self {
+        self.children.extend(children);
+        self
+    }
+    /// Adds a *child* with **content** and no *children* of its own. Shortcut for `.child(NodeBuilder::new(content))`.
+    #[inline]
+    pub fn child_content(self, content: T) -> Self {
+        self.child(Self::new(content))
+    }
 
     /// Create a new [`Tree`] from nodes with **children** and **content**.
     /// The children will be made into [`Pin`]ned [`Node`]s with the proper **parent**.
+    ///
+    /// Doesn't recurse, so [`Tree`]s of any depth can be built.
     pub fn build(self) -> Tree<T> {
-        let mut root = Box::pin(Node {
-            content: self.content,
-            parent: None,
-            children: vec![],
-            _pin: PhantomPinned,
-        });
+        self.build_with(&TreeAllocator::default(), |_| ())
+    }
+    /// Like [`NodeBuilder::build()`], but every [`Node`] also gets *metadata* (see [`Node::meta()`]),
+    /// which **meta** computes from the [`Node`]'s **content**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder("a").child(Node::builder("bc")).build_with_meta(|content| content.len());
+    /// assert_eq!(*tree.root()[0].meta(), 2);
+    /// ```
+    pub fn build_with_meta<M>(self, meta: impl FnMut(&T) -> M) -> Tree<T, M> {
+        self.build_with(&TreeAllocator::default(), meta)
+    }
+    /// Like [`NodeBuilder::build()`], but all the [`Node`]s (and their **children** lists) are allocated with **alloc**.
+    /// The [`Allocator`](std::alloc::Allocator) is dropped after all the [`Node`]s allocated with it are dropped.
+    ///
+    /// Copies of the [`Node`]s (e.g. made by [`Node::clone_deep()`]) use the same [`Allocator`](std::alloc::Allocator),
+    /// but most other methods that create new [`Node`]s use the *global* allocator. See [`TreeAllocator`](crate::TreeAllocator).
+    #[cfg(feature = "allocator_api")]
+    pub fn build_in(self, alloc: impl std::alloc::Allocator + 'static) -> Tree<T> {
+        self.build_with(&TreeAllocator::new(alloc), |_| ())
+    }
+    fn build_with<M>(self, alloc: &TreeAllocator, mut meta: impl FnMut(&T) -> M) -> Tree<T, M> {
+        let root_meta = meta(&self.content);
+        let root = Node::new(self.content, root_meta, alloc).pin_in(alloc);
+        // Every built Node, always after its parent.
+        let mut built = vec![];
+        // The Nodes whose children haven't been built yet.
+        let mut stack = vec![(root.ptr(), self.children)];
 
-        unsafe { root.as_mut().get_unchecked_mut() }.children =
-            Self::build_children(root.ptr(), self.children);
+        while let Some((mut parent, children)) = stack.pop() {
+            // Each Node is only borrowed while its children are built.
+            unsafe { parent.as_mut() }.children.reserve_exact(children.capacity());
 
+            for builder in children {
+                let child_meta = meta(&builder.content);
+                let child = unsafe { Node::push_leaf(parent, builder.content, child_meta) };
+                built.push(child);
+                stack.push((child, builder.children));
+            }
+        }
+
+        unsafe { Node::add_up_lens(built) };
         Tree { root }
     }
-    fn build_children(parent: Parent<Node<T>>, children: Vec<Self>) -> Vec<Owned<Node<T>>> {
-        children
-            .into_iter()
-            .map(|builder| {
-                let mut child = Box::pin(Node {
-                    content: builder.content,
-                    parent: Some(parent),
-                    children: vec![],
-                    _pin: PhantomPinned,
-                });
-
-                unsafe { child.as_mut().get_unchecked_mut() }.children =
-                    Self::build_children(child.ptr(), builder.children);
-
-                child
-            })
-            .collect()
+}
+
+/// Returned by the *chooser* of [`Node::descend_by()`] to decide where to go next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Descend {
+    /// Descend into the **child** at this index.
+    Child(usize),
+    /// Stop at the current [`Node`].
+    Stop,
+}
+
+/// A *child* of a [`Node`] that may or may not exist, returned by [`Node::child_entry()`].
+#[derive(Debug)]
+pub enum ChildEntry<'a, T, M = ()> {
+    /// The first *child* that matched the *key*.
+    Occupied(Pin<&'a mut Node<T, M>>),
+    /// No *child* matched the *key*, so this holds the **parent** a *child* can be appended to.
+    Vacant(Pin<&'a mut Node<T, M>>),
+}
+impl<'a, T, M> ChildEntry<'a, T, M> {
+    /// Get the *child* if it exists, or append a new *child* with **content** and get that one.
+    /// The new *child* gets the default *metadata*.
+    #[inline]
+    pub fn or_insert(self, content: T) -> Pin<&'a mut Node<T, M>>
+    where M: Default {
+        self.or_insert_with(|| NodeBuilder::new(content).build_with_meta(|_| M::default()))
+    }
+    /// Get the *child* if it exists, or append the [`Tree`] returned by **f** and get its **root**.
+    /// **f** is only called if there is no such *child*.
+    ///
+    /// The *subtree sizes* of the [`Tree`] are computed once when it is built, so appending it only walks up to the **root** once,
+    /// not once per [`Node`]. To build many levels at once, build them as a single [`Tree`] (e.g. with [`Tree::unfold()`]) instead of one entry at a time.
+    pub fn or_insert_with(self, f: impl FnOnce() -> Tree<T, M>) -> Pin<&'a mut Node<T, M>> {
+        match self {
+            Self::Occupied(child) => child,
+            Self::Vacant(mut parent) => {
+                parent.as_mut().append_child(f());
+                let this = unsafe { parent.get_unchecked_mut() };
+                this.children.last_mut().unwrap().as_mut()
+            }
+        }
     }
 }
 
@@ -109,16 +195,96 @@ impl<T> NodeBuilder<T> {
 /// This allows the Node struct to implement traits that require returning a *stack-allocated* Node (e.g. [`Default`] and [`Clone`]).
 /// However, it is recommended to convert the returned [`Node`] into a [`Tree`] using `Tree::from()` or `Node::into()` as an "ez mode"
 /// for getting rid of compiler errors that are caused by trying to use `&mut Node` or trying to move it.
-pub struct Node<T> {
+pub struct Node<T, M = ()> {
     pub content: T,
+    /// See [`Node::meta()`].
+    meta: M,
     parent: Option<Parent<Self>>,
-    children: Vec<Owned<Self>>,
+    children: Children<Self>,
+    /// The number of [`Node`]s in the *subtree* (including `self`), kept up to date by every method that adds or removes [`Node`]s.
+    len: usize,
+    /// Distinguishes this [`Node`] from the ones that were allocated at the same address before. See [`NodeRef`].
+    generation: u64,
+    /// Whether a [`NodeRef`] was made for this [`Node`], in which case it must be removed from the table of live [`Node`]s when it is dropped.
+    referenced: Cell<bool>,
+    /// The pointer that owns this [`Node`], if it is heap-allocated.
+    /// The pointers to this [`Node`] are copies of it instead of being obtained from a reference, so they are not invalidated by later borrows. See [`Owned`].
+    this: Option<NonNull<Self>>,
     _pin: PhantomPinned,
 }
-impl<T> Node<T> {
+impl<T, M> Node<T, M> {
+    /// A [`Node`] with no **parent** or **children**, whose *children* list will be allocated with **alloc**.
+    fn new(content: T, meta: M, alloc: &TreeAllocator) -> Self {
+        Self {
+            content,
+            meta,
+            parent: None,
+            children: children_in(alloc),
+            len: 1,
+            generation: handle::next_generation(),
+            referenced: Cell::new(false),
+            this: None,
+            _pin: PhantomPinned,
+        }
+    }
+    /// Moves the [`Node`] to the heap, where it is pinned, and keeps the pointer that owns it.
+    fn pin_in(self, alloc: &TreeAllocator) -> Owned<Self> {
+        let mut owned = Owned::new_in(self, alloc);
+        unsafe { owned.as_mut().get_unchecked_mut() }.this = Some(owned.ptr());
+        owned
+    }
+    /// The allocator that **self** (and its *children* list) was allocated with.
     #[inline]
-    pub fn builder(content: T) -> NodeBuilder<T> {
-        NodeBuilder::new(content)
+    fn allocator(&self) -> TreeAllocator {
+        allocator_of(&self.children)
+    }
+    /// Used by the builders to append a new *leaf* with **content** and **meta** to **parent**'s *children*,
+    /// *without* updating the *subtree size* of **parent** and its *ancestors* (see [`Self::add_up_lens()`]).
+    /// The *leaf* is allocated with **parent**'s allocator. Returns the pointer to the new *leaf*.
+    ///
+    /// **parent** must not be borrowed anywhere else.
+    pub(super) unsafe fn push_leaf(mut parent: NonNull<Self>, content: T, meta: M) -> NonNull<Self> {
+        let alloc = parent.as_ref().allocator();
+        let mut child = Self::new(content, meta, &alloc).pin_in(&alloc);
+        let ptr = child.ptr();
+        child.as_mut().get_unchecked_mut().parent = Some(parent);
+        parent.as_mut().children.push(child);
+        ptr
+    }
+    /// Adds the *subtree size* of every [`Node`] appended with [`Self::push_leaf()`] to its **parent**'s, starting from the deepest ones,
+    /// so a builder computes all of them in one pass instead of walking up to the **root** for every [`Node`].
+    ///
+    /// **built** must have every [`Node`] after its **parent**, and none of them can be borrowed anywhere else.
+    pub(super) unsafe fn add_up_lens(built: Vec<NonNull<Self>>) {
+        for node in built.into_iter().rev() {
+            let node = node.as_ref();
+            (*node.parent.unwrap().as_ptr()).len += node.len;
+        }
+    }
+
+    /// The *metadata* of the [`Node`]: bookkeeping (e.g. dirty flags, layout coordinates or source spans) stored next to the **content**,
+    /// so the **content** doesn't have to be wrapped in a custom struct.
+    ///
+    /// It is `()` (which takes no space) unless the [`Tree`] was built with [`NodeBuilder::build_with_meta()`] or converted with [`Tree::map_meta()`].
+    /// The *metadata* is not compared, hashed or printed along with the **content**.
+    #[inline]
+    pub fn meta(&self) -> &M {
+        &self.meta
+    }
+    /// Mutable access to the *metadata* of the [`Node`]. See [`Node::meta()`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a').child(Node::builder('b')).build_with_meta(|_| false);
+    /// let b = tree.root()[0].ptr();
+    /// *tree.borrow_descendant(b).unwrap().meta_mut() = true;
+    /// assert_eq!(tree.iter_bfs().map(|n| *n.meta()).collect::<Vec<_>>(), vec![false, true]);
+    /// ```
+    #[inline]
+    pub fn meta_mut(self: Pin<&mut Self>) -> &mut M {
+        // The metadata is not structurally pinned.
+        &mut unsafe { self.get_unchecked_mut() }.meta
     }
 
     /// Get an *immutable reference* to the `parent` [`Node`] of `self`.
@@ -136,6 +302,47 @@ impl<T> Node<T> {
             .map(|child| child.as_ref().get_ref())
             .collect()
     }
+    /// Iterate over the **children** without allocating, unlike [`Node::children()`].
+    #[inline]
+    pub fn children_iter(&self) -> ChildrenIter<'_, T, M> {
+        ChildrenIter::new(&self.children)
+    }
+    /// Get the **child** at **index** without allocating, or [`None`] if **index** is out of bounds.
+    /// Also available through [`Index`](std::ops::Index), which panics instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).build();
+    /// assert_eq!(tree.root()[0][0].content, 'c');
+    /// assert!(tree.root()[0].get(1).is_none());
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Self> {
+        self.children.get(index).map(|child| child.as_ref().get_ref())
+    }
+    /// The number of [`Node`]s in the *subtree* (including `self`).
+    /// This is `O(1)`, because the size of every *subtree* is updated when [`Node`]s are added or removed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Always `false`, because the *subtree* always has at least `self`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Adds **added** to (and subtracts **removed** from) the *subtree size* of **node** and all of its *ancestors*.
+    fn update_len(node: &mut Self, added: usize, removed: usize) {
+        node.len = node.len + added - removed;
+        let mut ancestor = node.parent;
+        while let Some(mut current) = ancestor {
+            // The len is not structurally pinned.
+            let current = unsafe { current.as_mut() };
+            current.len = current.len + added - removed;
+            ancestor = current.parent;
+        }
+    }
 
     /// A [`Node`] is a **descendant** of another [`Node`] if:
     /// 1. The two [`Node`]s are not the same ([`std::ptr::eq()`]).
@@ -173,25 +380,492 @@ impl<T> Node<T> {
         self.find_self_next(self.parent()?.children.iter().rev())
     }
 
+    /// Replaces **self**'s **content** with **content**, returning the old one.
+    /// The [`Node`] stays in place, so `T` doesn't need to be [`Clone`].
+    #[inline]
+    pub fn replace_content(self: Pin<&mut Self>, content: T) -> T {
+        // The content is not structurally pinned.
+        std::mem::replace(&mut unsafe { self.get_unchecked_mut() }.content, content)
+    }
+
     /// Pushes the **child** to the end of **self**'s *children*.
     /// Also see [`Self::insert_child()`].
-    pub fn append_child(self: Pin<&mut Self>, mut child: Tree<T>) {
+    pub fn append_child(self: Pin<&mut Self>, mut child: Tree<T, M>) {
         // Compiler ensures `self != child.root`.
         unsafe {
             let this = self.get_unchecked_mut();
-            child.root_mut().get_unchecked_mut().parent = Some(NonNull::new_unchecked(this));
-            this.children.push(child.root)
+            child.root_mut().get_unchecked_mut().parent = Some(this.ptr());
+            let added = child.root.len;
+            this.children.push(child.root);
+            Self::update_len(this, added, 0);
         }
     }
+    /// Reserves space for at least **additional** more *children*, so that many can be appended without reallocating.
+    #[inline]
+    pub fn reserve_children(self: Pin<&mut Self>, additional: usize) {
+        // Only the pointers to the children move, not the children themselves.
+        unsafe { self.get_unchecked_mut() }.children.reserve(additional)
+    }
+    /// Pushes all the **children** to the end of **self**'s *children*, in order.
+    /// The *children* list is grown only once if the [`Iterator`]'s length is known.
+    /// This is also available through [`Extend`].
+    pub fn append_children(self: Pin<&mut Self>, children: impl IntoIterator<Item = Tree<T, M>>) {
+        let this = unsafe { self.get_unchecked_mut() };
+        let parent = this.ptr();
+        let mut added = 0;
+
+        this.children.extend(children.into_iter().map(|mut child| {
+            unsafe { child.root.as_mut().get_unchecked_mut() }.parent = Some(parent);
+            added += child.root.len;
+            child.root
+        }));
+        Self::update_len(this, added, 0);
+    }
     /// Inserts the **child** to **self**'s *children* at some index.
     /// Also see [`Self::append_child()`].
-    pub fn insert_child(self: Pin<&mut Self>, mut child: Tree<T>, index: usize) {
+    ///
+    /// # Panics
+    /// If **index** is greater than the number of *children*. See [`Self::try_insert_child()`].
+    pub fn insert_child(self: Pin<&mut Self>, mut child: Tree<T, M>, index: usize) {
+        let len = self.children.len();
+        assert!(index <= len, "child index {index} is out of bounds for {len} children");
+        // Compiler ensures `self != child.root`.
+        unsafe {
+            let this = self.get_unchecked_mut();
+            child.root_mut().get_unchecked_mut().parent = Some(this.ptr());
+            let added = child.root.len;
+            this.children.insert(index, child.root);
+            Self::update_len(this, added, 0);
+        }
+    }
+
+    /// Moves the *children* of **other**'s **root** to the end of **self**'s *children*.
+    ///
+    /// If **keep_root** is `true`, the **root** of **other** is kept and appended as a *child* (just like [`Self::append_child()`]).
+    /// Otherwise, the **root** is dropped and only its *children* are moved.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a').child(Node::builder('b')).build();
+    /// let other = Node::builder('x').child(Node::builder('c')).child(Node::builder('d')).build();
+    /// tree.root_mut().graft(other, false);
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "abcd");
+    /// ```
+    pub fn graft(self: Pin<&mut Self>, other: Tree<T, M>, keep_root: bool) {
+        if keep_root {
+            self.append_child(other);
+            return;
+        }
+
+        let (_, children) = Self::into_parts(other.root);
+        self.append_children(children);
+    }
+
+    /// Inserts the **child** to **self**'s *children* right *before* **target**, which must be one of **self**'s *children*.
+    /// Also see [`Self::insert_child_after()`].
+    ///
+    /// Returns the **child** back if **target** is not one of **self**'s *children*.
+    pub fn insert_child_before(self: Pin<&mut Self>, target: NonNull<Self>, child: Tree<T, M>) -> Result<(), Tree<T, M>> {
+        match self.child_index(target) {
+            Some(index) => {
+                self.insert_child(child, index);
+                Ok(())
+            }
+            None => Err(child),
+        }
+    }
+    /// Inserts the **child** to **self**'s *children* right *after* **target**, which must be one of **self**'s *children*.
+    /// Also see [`Self::insert_child_before()`].
+    ///
+    /// Returns the **child** back if **target** is not one of **self**'s *children*.
+    pub fn insert_child_after(self: Pin<&mut Self>, target: NonNull<Self>, child: Tree<T, M>) -> Result<(), Tree<T, M>> {
+        match self.child_index(target) {
+            Some(index) => {
+                self.insert_child(child, index + 1);
+                Ok(())
+            }
+            None => Err(child),
+        }
+    }
+    /// The index of **child** in **self**'s *children*.
+    pub(super) fn child_index(&self, child: NonNull<Self>) -> Option<usize> {
+        self.children
+            .iter()
+            .position(|c| child.as_ptr() == c.ptr().as_ptr())
+    }
+
+    /// Removes the *last* of **self**'s *children* and returns it as a [`Tree`] (with no *parent*),
+    /// or [`None`] if **self** has no *children*.
+    /// This is the opposite of [`Self::append_child()`].
+    pub fn pop_child(self: Pin<&mut Self>) -> Option<Tree<T, M>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut root = this.children.pop()?;
+        Self::update_len(this, 0, root.len);
+        unsafe { root.as_mut().get_unchecked_mut() }.parent = None;
+        Some(Tree { root })
+    }
+    /// Replaces the *children* of **self** in **range** with **replace_with**, and returns the removed *children* as [`Tree`]s (with no *parent*).
+    /// Like [`Vec::splice()`], **range** and **replace_with** don't need to have the same length.
+    ///
+    /// # Panics
+    /// If **range** is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder('a').child(Node::builder('b')).child(Node::builder('c')).child(Node::builder('d')).build();
+    /// let removed = tree.root_mut().splice_children(1..3, [Node::builder('x').build()]);
+    /// assert_eq!(removed.iter().map(|t| t.root().content).collect::<String>(), "cd");
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "abx");
+    /// ```
+    pub fn splice_children(self: Pin<&mut Self>, range: impl RangeBounds<usize>, replace_with: impl IntoIterator<Item = Tree<T, M>>) -> Vec<Tree<T, M>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let parent = this.ptr();
+        let mut added = 0;
+
+        let removed = this.children
+            .splice(
+                range,
+                replace_with.into_iter().map(|mut child| {
+                    unsafe { child.root.as_mut().get_unchecked_mut() }.parent = Some(parent);
+                    added += child.root.len;
+                    child.root
+                }),
+            )
+            .collect::<Vec<_>>();
+        Self::update_len(this, added, removed.iter().map(|root| root.len).sum());
+
+        removed
+            .into_iter()
+            .map(|mut root| {
+                unsafe { root.as_mut().get_unchecked_mut() }.parent = None;
+                Tree { root }
+            })
+            .collect()
+    }
+    /// Removes all of **self**'s *children* and returns them as [`Tree`]s (with no *parent*), in the same order.
+    #[inline]
+    pub fn clear_children(self: Pin<&mut Self>) -> Vec<Tree<T, M>> {
+        self.split_children_off(0)
+    }
+
+    /// Removes **self**'s *children* from **index** onward and returns them as [`Tree`]s (with no *parent*), in the same order.
+    /// Like [`Vec::split_off()`], **self** keeps the *children* in `[0, index)`.
+    ///
+    /// # Panics
+    /// If **index** is greater than the number of *children*.
+    pub fn split_children_off(self: Pin<&mut Self>, index: usize) -> Vec<Tree<T, M>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let removed = this.children
+            .drain(index..)
+            .map(|mut root| {
+                unsafe { root.as_mut().get_unchecked_mut() }.parent = None;
+                Tree { root }
+            })
+            .collect::<Vec<_>>();
+        Self::update_len(this, 0, removed.iter().map(|tree| tree.root.len).sum());
+        removed
+    }
+
+    /// Swaps two of **self**'s *children* in place.
+    /// The *parent* of the **children** doesn't change, so nothing else is modified.
+    ///
+    /// # Panics
+    /// If **a** or **b** are out of bounds.
+    pub fn swap_children(self: Pin<&mut Self>, a: usize, b: usize) {
+        unsafe { self.get_unchecked_mut() }.children.swap(a, b)
+    }
+    /// Reverses the order of **self**'s *children* in place.
+    /// The *children*'s subtrees are not reversed (see [`Self::reverse_recursive()`]).
+    pub fn reverse_children(self: Pin<&mut Self>) {
+        unsafe { self.get_unchecked_mut() }.children.reverse()
+    }
+    /// Reverses the order of the *children* of every [`Node`] in the *subtree* (including `self`), so the whole subtree is *mirrored*.
+    pub fn reverse_recursive(self: Pin<&mut Self>) {
+        let mut stack = vec![unsafe { self.get_unchecked_mut() }];
+
+        while let Some(node) = stack.pop() {
+            node.children.reverse();
+            stack.extend(node.children.iter_mut().map(|child| unsafe { child.as_mut().get_unchecked_mut() }));
+        }
+    }
+    /// Drops all the [`Node`]s of the *subtree* that are deeper than **max** (`self` is at depth `0`).
+    /// So `truncate_depth(0)` drops all of **self**'s *children*.
+    pub fn truncate_depth(self: Pin<&mut Self>, max: usize) {
+        let mut stack = vec![(unsafe { self.get_unchecked_mut() }, 0)];
+
+        while let Some((node, depth)) = stack.pop() {
+            if depth == max {
+                Self::update_len(node, 0, node.len - 1);
+                node.children.clear();
+            } else {
+                stack.extend(node.children.iter_mut().map(|child| (unsafe { child.as_mut().get_unchecked_mut() }, depth + 1)));
+            }
+        }
+    }
+    /// Sorts **self**'s *children* with a comparator function on their **content**.
+    /// The sort is *stable*, and the *children*'s subtrees are not sorted.
+    /// Also see [`Self::sort_recursive_by()`].
+    pub fn sort_children_by(self: Pin<&mut Self>, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        unsafe { self.get_unchecked_mut() }
+            .children
+            .sort_by(|a, b| compare(&a.content, &b.content))
+    }
+    /// Sorts **self**'s *children* with a key extraction function on their **content**.
+    /// The sort is *stable*, and the *children*'s subtrees are not sorted.
+    pub fn sort_children_by_key<K: Ord>(self: Pin<&mut Self>, mut f: impl FnMut(&T) -> K) {
+        unsafe { self.get_unchecked_mut() }
+            .children
+            .sort_by_key(|child| f(&child.content))
+    }
+    /// Sorts the *children* of every [`Node`] in the *subtree* (including `self`) with a comparator function on their **content**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder("/")
+    ///     .child(Node::builder("src")
+    ///         .child(Node::builder("main.rs"))
+    ///         .child(Node::builder("lib.rs")))
+    ///     .child(Node::builder("Cargo.toml"))
+    ///     .build();
+    /// tree.root_mut().sort_recursive_by(|a, b| a.cmp(b));
+    /// assert_eq!(
+    ///     tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(),
+    ///     vec!["/", "Cargo.toml", "src", "lib.rs", "main.rs"]
+    /// );
+    /// ```
+    pub fn sort_recursive_by(self: Pin<&mut Self>, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        let mut stack = vec![self];
+
+        while let Some(node) = stack.pop() {
+            // Children stay with the same parent, so it is ok to get them as mutable.
+            let node = unsafe { node.get_unchecked_mut() };
+            node.children.sort_by(|a, b| compare(&a.content, &b.content));
+            stack.extend(node.children.iter_mut().map(|child| child.as_mut()));
+        }
+    }
+
+    /// Performs a *right rotation* on the *child* of **self** at **child_index** (**P**),
+    /// where the *pivot* (**C**) is the *first* child of **P**:
+    /// **C** takes **P**'s place, the *last* child of **C** becomes the *first* child of **P**, and **P** becomes the *last* child of **C**.
+    ///
+    /// If the [`Node`]s are treated as *binary* (the first child being the *left* and the last child being the *right*),
+    /// this is the standard tree rotation, which preserves the *in-order* sequence of the [`Node`]s.
+    /// Also see [`Tree::rotate_right()`] to rotate at the **root**.
+    ///
+    /// Returns `false` (and doesn't modify anything) if **P** has no *children*.
+    ///
+    /// # Panics
+    /// If **child_index** is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// //     root            root
+    /// //      |               |
+    /// //      P               C
+    /// //     / \     =>     / \
+    /// //    C   z          x   P
+    /// //   / \                / \
+    /// //  x   y              y   z
+    /// let mut tree = Node::builder("root")
+    ///     .child(Node::builder("P")
+    ///         .child(Node::builder("C").child(Node::builder("x")).child(Node::builder("y")))
+    ///         .child(Node::builder("z")))
+    ///     .build();
+    /// assert!(tree.root_mut().rotate_right(0));
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "rootCxPyz");
+    /// ```
+    pub fn rotate_right(self: Pin<&mut Self>, child_index: usize) -> bool {
+        Self::rotate(&mut unsafe { self.get_unchecked_mut() }.children[child_index], true)
+    }
+    /// Performs a *left rotation* on the *child* of **self** at **child_index** (**P**),
+    /// where the *pivot* (**C**) is the *last* child of **P**:
+    /// **C** takes **P**'s place, the *first* child of **C** becomes the *last* child of **P**, and **P** becomes the *first* child of **C**.
+    ///
+    /// This is the opposite of [`Self::rotate_right()`].
+    /// Also see [`Tree::rotate_left()`] to rotate at the **root**.
+    ///
+    /// Returns `false` (and doesn't modify anything) if **P** has no *children*.
+    ///
+    /// # Panics
+    /// If **child_index** is out of bounds.
+    pub fn rotate_left(self: Pin<&mut Self>, child_index: usize) -> bool {
+        Self::rotate(&mut unsafe { self.get_unchecked_mut() }.children[child_index], false)
+    }
+    /// Rotates the [`Node`] in **slot** (**P**) with its *pivot* (**C**), leaving **C** in **slot**.
+    pub(super) fn rotate(slot: &mut Owned<Self>, right: bool) -> bool {
+        let p_ptr = slot.ptr();
+        let p = unsafe { slot.as_mut().get_unchecked_mut() };
+
+        let mut c = match (right, p.children.is_empty()) {
+            (_, true) => return false,
+            (true, false) => p.children.remove(0),
+            (false, false) => p.children.pop().unwrap(),
+        };
+        let c_ptr = c.ptr();
+        let c_mut = unsafe { c.as_mut().get_unchecked_mut() };
+
+        // The inner subtree of C moves to the other side of P.
+        let inner = match (right, c_mut.children.is_empty()) {
+            (_, true) => None,
+            (true, false) => c_mut.children.pop(),
+            (false, false) => Some(c_mut.children.remove(0)),
+        };
+        // C ends up with all the Nodes that P had, so the size of the whole subtree doesn't change.
+        let inner_len = inner.as_ref().map_or(0, |inner| inner.len);
+        (p.len, c_mut.len) = (p.len - c_mut.len + inner_len, p.len);
+        if let Some(mut inner) = inner {
+            unsafe { inner.as_mut().get_unchecked_mut() }.parent = Some(p_ptr);
+            if right {
+                p.children.insert(0, inner);
+            } else {
+                p.children.push(inner);
+            }
+        }
+
+        c_mut.parent = p.parent;
+        p.parent = Some(c_ptr);
+        let p = std::mem::replace(slot, c);
+        let c = unsafe { slot.as_mut().get_unchecked_mut() };
+        if right {
+            c.children.push(p);
+        } else {
+            c.children.insert(0, p);
+        }
+        true
+    }
+
+    /// Replaces the child of **self** at some index with **child**,
+    /// and returns the *replaced [`Node`]* with ownership (aka a [`Tree`]).
+    /// This is faster than [detaching](crate::Tree::detach_descendant()) and [inserting](Self::insert_child()) because the *children* are not shifted.
+    ///
+    /// # Panics
+    /// If **index** is out of bounds.
+    pub fn replace_child(self: Pin<&mut Self>, mut child: Tree<T, M>, index: usize) -> Tree<T, M> {
         // Compiler ensures `self != child.root`.
         unsafe {
             let this = self.get_unchecked_mut();
-            child.root_mut().get_unchecked_mut().parent = Some(NonNull::new_unchecked(this));
-            this.children.insert(index, child.root)
+            child.root_mut().get_unchecked_mut().parent = Some(this.ptr());
+            let added = child.root.len;
+            let mut root = std::mem::replace(&mut this.children[index], child.root);
+            Self::update_len(this, added, root.len);
+            root.as_mut().get_unchecked_mut().parent = None;
+            Tree { root }
+        }
+    }
+
+    /// Finds the first *child* whose **content** matches **key**,
+    /// returning a [`ChildEntry`] that can get it or append a new *child* in its place, without searching again.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// // Builds a trie of the paths' segments.
+    /// let mut tree = Node::builder("").build();
+    /// for path in ["src/lib.rs", "src/node.rs", "tests/node.rs"] {
+    ///     let mut node = tree.root_mut();
+    ///     for segment in path.split('/') {
+    ///         node = node.child_entry(|c| *c == segment).or_insert(segment);
+    ///     }
+    /// }
+    /// assert_eq!(tree.display_compact().to_string(), "(src(lib.rs,node.rs),tests(node.rs))");
+    /// ```
+    pub fn child_entry(self: Pin<&mut Self>, mut key: impl FnMut(&T) -> bool) -> ChildEntry<'_, T, M> {
+        let this = unsafe { self.get_unchecked_mut() };
+        match this.children.iter().position(|child| key(&child.content)) {
+            Some(index) => ChildEntry::Occupied(this.children[index].as_mut()),
+            None => ChildEntry::Vacant(unsafe { Pin::new_unchecked(this) }),
+        }
+    }
+
+    /// Coalesces runs of adjacent **children** whose *contents* are the [`same`](Fn),
+    /// leaving only the first child of each run (the *survivor*).
+    ///
+    /// The *content* of every other child in the run is [`merge`](FnMut)d into the *survivor*'s,
+    /// and its **children** are appended to the *survivor*'s *children*.
+    /// Note that [`same`](Fn) is called with the *survivor*'s content *after* previous merges.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder(String::from("p"))
+    ///     .child(Node::builder(String::from("a")))
+    ///     .child(Node::builder(String::from("b")))
+    ///     .child(Node::builder(String::from("1")))
+    ///     .child(Node::builder(String::from("c")))
+    ///     .build();
+    /// let is_alpha = |s: &String| s.chars().all(char::is_alphabetic);
+    /// tree.root_mut().merge_adjacent_children(|a, b| is_alpha(a) && is_alpha(b), |a, b| a.push_str(&b));
+    /// assert_eq!(
+    ///     tree.root().children().iter().map(|c| c.content.as_str()).collect::<Vec<_>>(),
+    ///     vec!["ab", "1", "c"]
+    /// );
+    /// ```
+    pub fn merge_adjacent_children(self: Pin<&mut Self>, same: impl Fn(&T, &T) -> bool, mut merge: impl FnMut(&mut T, T)) {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut merged = 0;
+
+        // Children remain in the same parent, so their parent pointers stay valid.
+        for child in this.children.drain(..).collect::<Vec<_>>() {
+            match this.children.last_mut() {
+                Some(survivor) if same(&survivor.content, &child.content) => {
+                    let (content, children) = Self::into_parts(child);
+                    let parent = survivor.ptr();
+                    let survivor = unsafe { survivor.as_mut().get_unchecked_mut() };
+                    merge(&mut survivor.content, content);
+                    // Only the merged Node is gone, its children move to the survivor.
+                    survivor.children.extend(children.into_iter().map(|mut child| {
+                        unsafe { child.root.as_mut().get_unchecked_mut() }.parent = Some(parent);
+                        survivor.len += child.root.len;
+                        child.root
+                    }));
+                    merged += 1;
+                }
+                _ => this.children.push(child),
+            }
         }
+        Self::update_len(this, 0, merged);
+    }
+
+    /// Removes every **descendant** of **self** (along with its subtree) whose **content** does not satisfy the **predicate**,
+    /// and returns the removed subtrees as [`Tree`]s with no *parent*.
+    ///
+    /// The **predicate** is not called for `self`, or for the **descendants** of a [`Node`] that was removed.
+    /// All the **children** of a [`Node`] are checked (and returned) before moving on to their own **children**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let mut tree = Node::builder(0)
+    ///     .child(Node::builder(1).child(Node::builder(2)))
+    ///     .child(Node::builder(3).child(Node::builder(4)))
+    ///     .build();
+    /// let removed = tree.retain(|n| n % 3 != 0);
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(removed[0].iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![3, 4]);
+    /// ```
+    pub fn retain(self: Pin<&mut Self>, mut predicate: impl FnMut(&T) -> bool) -> Vec<Tree<T, M>> {
+        let mut removed = vec![];
+        let mut stack = vec![unsafe { self.get_unchecked_mut() }];
+
+        while let Some(node) = stack.pop() {
+            // Kept children are put back in the same parent, so their parent pointers stay valid.
+            for mut child in node.children.drain(..).collect::<Vec<_>>() {
+                if predicate(&child.content) {
+                    node.children.push(child);
+                } else {
+                    Self::update_len(node, 0, child.len);
+                    unsafe { child.as_mut().get_unchecked_mut() }.parent = None;
+                    removed.push(Tree { root: child });
+                }
+            }
+            stack.extend(node.children.iter_mut().rev().map(|child| unsafe { child.as_mut().get_unchecked_mut() }));
+        }
+
+        removed
     }
 
     /// See [`crate::Tree::detach_descendant()`].
@@ -199,7 +873,7 @@ impl<T> Node<T> {
     ///
     /// **descendant** does not have to be `mut`.
     /// It should be enough to assert that the whole [`Tree`] is `mut`, so by extension the **descendant** is also `mut`.
-    pub(super) fn detach_descendant(self: Pin<&mut Self>, descendant: NonNull<Self>) -> Option<Tree<T>> {
+    pub(super) fn detach_descendant(self: Pin<&mut Self>, descendant: NonNull<Self>) -> Option<Tree<T, M>> {
         if !self.is_descendant(descendant) {
             return None;
         }
@@ -215,10 +889,98 @@ impl<T> Node<T> {
 
         // If children is not UnsafeCell, use std::mem::transmute(parent.children.remove(index)).
         let mut root = parent.children.remove(index);
+        Self::update_len(parent, 0, root.len);
         unsafe { root.as_mut().get_unchecked_mut() }.parent = None;
         Some(Tree { root })
     }
 
+    /// See [`crate::Tree::remove_promote()`].
+    pub(super) fn remove_promote(self: Pin<&mut Self>, descendant: NonNull<Self>) -> Option<T> {
+        if !self.is_descendant(descendant) {
+            return None;
+        }
+
+        let parent_ptr = unsafe { descendant.as_ref() }.parent.unwrap();
+        let parent = unsafe { &mut *parent_ptr.as_ptr() };
+        let index = parent.child_index(descendant).expect("Node is not found in its parent");
+
+        let (content, children) = Self::into_parts(parent.children.remove(index));
+        // The children are spliced in where the removed Node was.
+        parent.children.splice(
+            index..index,
+            children.into_iter().map(|mut child| {
+                unsafe { child.root.as_mut().get_unchecked_mut() }.parent = Some(parent_ptr);
+                child.root
+            }),
+        );
+        Self::update_len(parent, 0, 1);
+        Some(content)
+    }
+
+    /// See [`crate::Tree::move_descendant()`].
+    pub(super) fn move_descendant(self: Pin<&mut Self>, descendant: NonNull<Self>, new_parent: NonNull<Self>, index: usize) -> bool {
+        if !self.is_descendant(descendant) {
+            return false;
+        }
+        // Walk up from new_parent once, checking that it is in this Tree and not in the subtree of descendant.
+        let mut ancestor = Some(unsafe { new_parent.as_ref() });
+        loop {
+            match ancestor {
+                Some(node) if node.is_same_as(descendant) => return false,
+                Some(node) if node.is_same_as(&*self) => break,
+                Some(node) => ancestor = node.parent(),
+                None => return false,
+            }
+        }
+
+        let old_parent = unsafe { descendant.as_ref() }.parent.unwrap();
+        let old_index = unsafe { old_parent.as_ref() }.child_index(descendant).expect("Node is not found in its parent");
+        let len = unsafe { new_parent.as_ref() }.children.len() - usize::from(old_parent == new_parent);
+        if index > len {
+            return false;
+        }
+
+        let mut root = unsafe { &mut *old_parent.as_ptr() }.children.remove(old_index);
+        let len = root.len;
+        Self::update_len(unsafe { &mut *old_parent.as_ptr() }, 0, len);
+        unsafe { root.as_mut().get_unchecked_mut() }.parent = Some(new_parent);
+        unsafe { &mut *new_parent.as_ptr() }.children.insert(index, root);
+        Self::update_len(unsafe { &mut *new_parent.as_ptr() }, len, 0);
+        true
+    }
+
+    /// See [`crate::Tree::swap_subtrees()`].
+    pub(super) fn swap_descendants(self: Pin<&mut Self>, a: NonNull<Self>, b: NonNull<Self>) -> bool {
+        if !self.is_descendant(a) || !self.is_descendant(b) {
+            return false;
+        }
+        // Neither can be an ancestor of the other (or the same Node).
+        let (a_ref, b_ref) = unsafe { (a.as_ref(), b.as_ref()) };
+        if a_ref.is_same_as(b) || a_ref.is_descendant(b) || b_ref.is_descendant(a) {
+            return false;
+        }
+
+        let (a_parent, b_parent) = (a_ref.parent.unwrap(), b_ref.parent.unwrap());
+        let (a_len, b_len) = (a_ref.len, b_ref.len);
+        let a_index = unsafe { a_parent.as_ref() }.child_index(a).expect("Node is not found in its parent");
+        let b_index = unsafe { b_parent.as_ref() }.child_index(b).expect("Node is not found in its parent");
+
+        if a_parent == b_parent {
+            unsafe { &mut *a_parent.as_ptr() }.children.swap(a_index, b_index);
+        } else {
+            // The parents are different Nodes, so they can be borrowed mutably at the same time.
+            let (a_siblings, b_siblings) = unsafe { (&mut (*a_parent.as_ptr()).children, &mut (*b_parent.as_ptr()).children) };
+            std::mem::swap(&mut a_siblings[a_index], &mut b_siblings[b_index]);
+            unsafe {
+                (*a_siblings[a_index].ptr().as_ptr()).parent = Some(a_parent);
+                (*b_siblings[b_index].ptr().as_ptr()).parent = Some(b_parent);
+                Self::update_len(&mut *a_parent.as_ptr(), b_len, a_len);
+                Self::update_len(&mut *b_parent.as_ptr(), a_len, b_len);
+            }
+        }
+        true
+    }
+
     /// See [`crate::Tree::borrow_descendant()`].
     /// TODO: Don't know if should make it public.
     ///
@@ -226,110 +988,403 @@ impl<T> Node<T> {
     /// It should be enough to assert that the whole [`Tree`] is `mut`, so by extension the **descendant** is also `mut`.
     pub(super) fn borrow_descendant(self: Pin<&mut Self>, descendant: NonNull<Self>) -> Option<Pin<&mut Self>> {
         if self.is_descendant(descendant) {
+            // Use the pointer that owns the Node, in case **descendant** was obtained from a reference.
+            let descendant = unsafe { descendant.as_ref() }.ptr();
             Some(unsafe { Pin::new_unchecked(&mut *descendant.as_ptr()) })
         } else {
             None
         }
     }
 
+    /// Takes the **content** out of an *owned* [`Node`], and passes ownership of its **children** as [`Tree`]s (with no *parent*).
+    pub(super) fn into_parts(this: Owned<Self>) -> (T, Vec<Tree<T, M>>) {
+        let (content, _, children) = Self::into_parts_with_meta(this);
+        (content, children)
+    }
+    /// Like [`Self::into_parts()`], but the *metadata* is also taken out.
+    fn into_parts_with_meta(this: Owned<Self>) -> (T, M, Vec<Tree<T, M>>) {
+        // The Node is owned and its children are given a new parent (None), so it is ok to move it out of the Pin.
+        let mut node = unsafe { this.into_inner() };
+        // The Node was moved out of its allocation and won't be dropped.
+        if node.referenced.get() {
+            handle::unregister(node.ptr().cast());
+        }
+
+        let children = node
+            .children
+            .drain(..)
+            .map(|mut root| {
+                unsafe { root.as_mut().get_unchecked_mut() }.parent = None;
+                Tree { root }
+            })
+            .collect();
+
+        // Node implements Drop, so the content can't be moved out of it.
+        // Instead, drop the other fields that own something, without running Node's Drop (there are no children left to drop anyway).
+        let node = std::mem::ManuallyDrop::new(node);
+        let (content, meta) = unsafe {
+            drop(std::ptr::read(&node.children));
+            (std::ptr::read(&node.content), std::ptr::read(&node.meta))
+        };
+
+        (content, meta, children)
+    }
+
+    /// Converts the **content** and *metadata* of every [`Node`] in the *subtree* of an *owned* [`Node`] with **f**.
+    /// See [`crate::Tree::map()`] and [`crate::Tree::map_meta()`].
+    ///
+    /// Doesn't recurse, so [`Node`]s of any depth can be converted.
+    pub(super) fn map<U, N>(this: Owned<Self>, mut f: impl FnMut(T, M) -> (U, N)) -> Tree<U, N> {
+        let mut root = None;
+        // Each Node is paired with the converted Node that will be the parent of its conversion.
+        let mut stack = vec![(this, None)];
+
+        while let Some((node, parent)) = stack.pop() {
+            let len = node.len;
+            let alloc = node.allocator();
+            let (content, meta, children) = Self::into_parts_with_meta(node);
+            let (content, meta) = f(content, meta);
+            let mut mapped = Node::new(content, meta, &alloc).pin_in(&alloc);
+            let mapped_mut = unsafe { mapped.as_mut().get_unchecked_mut() };
+            mapped_mut.len = len;
+            mapped_mut.parent = parent;
+            mapped_mut.children.reserve_exact(children.len());
+
+            let ptr = Some(mapped.ptr());
+            // Reverse because the first child should be popped (and converted) next from the stack.
+            stack.extend(children.into_iter().rev().map(|child| (child.root, ptr)));
+            match parent {
+                // The parent is only borrowed to push its children, which are converted in order.
+                Some(mut parent) => unsafe { parent.as_mut() }.children.push(mapped),
+                None => root = Some(mapped),
+            }
+        }
+
+        Tree { root: root.unwrap() }
+    }
+    /// Like [`Self::map()`], but the [`Node`]s are not consumed. See [`crate::Tree::map_ref()`].
+    pub(super) fn map_ref<U>(&self, mut f: impl FnMut(&T) -> U) -> Tree<U> {
+        let mut root = None;
+        // Each Node is paired with the converted Node that will be the parent of its conversion.
+        let mut stack = vec![(self, None)];
+
+        while let Some((node, parent)) = stack.pop() {
+            let alloc = node.allocator();
+            let mut mapped = Node::new(f(&node.content), (), &alloc).pin_in(&alloc);
+            let mapped_mut = unsafe { mapped.as_mut().get_unchecked_mut() };
+            mapped_mut.len = node.len;
+            mapped_mut.parent = parent;
+            mapped_mut.children.reserve_exact(node.children.len());
+
+            let ptr = Some(mapped.ptr());
+            // Reverse because the first child should be popped (and converted) next from the stack.
+            stack.extend(node.children_iter().rev().map(|child| (child, ptr)));
+            match parent {
+                // The parent is only borrowed to push its children, which are converted in order.
+                Some(mut parent) => unsafe { parent.as_mut() }.children.push(mapped),
+                None => root = Some(mapped),
+            }
+        }
+
+        Tree { root: root.unwrap() }
+    }
+
+    /// Pairs up the **content** of every [`Node`] in the *subtree* with the one at the same position in **other**'s *subtree*.
+    /// See [`crate::Tree::zip()`].
+    pub(super) fn zip<'a, 'b, U, N>(&'a self, other: &'b Node<U, N>) -> Result<Tree<(&'a T, &'b U)>, ShapeMismatch> {
+        let mut root = None;
+        // Each pair of Nodes is paired with the zipped Node that will be the parent of their zipped Node.
+        let mut stack = vec![(self, other, None)];
+
+        while let Some((a, b, parent)) = stack.pop() {
+            if a.children.len() != b.children.len() {
+                // The path is only needed for the error, so it is found by walking up from the Node instead of being kept for every Node.
+                let mut path = vec![];
+                let mut node = a;
+                while !node.is_same_as(self) {
+                    let parent = node.parent().unwrap();
+                    path.push(parent.child_index(node.ptr()).unwrap());
+                    node = parent;
+                }
+                path.reverse();
+                return Err(ShapeMismatch { path });
+            }
+            let alloc = a.allocator();
+            let mut zipped = Node::new((&a.content, &b.content), (), &alloc).pin_in(&alloc);
+            let zipped_mut = unsafe { zipped.as_mut().get_unchecked_mut() };
+            // Both subtrees have the same shape, so the zipped one has the same number of Nodes.
+            zipped_mut.len = a.len;
+            zipped_mut.parent = parent;
+            zipped_mut.children.reserve_exact(a.children.len());
+
+            let ptr = Some(zipped.ptr());
+            // Reverse because the first children should be popped next from the stack.
+            stack.extend(a.children_iter().zip(b.children_iter()).rev().map(|(a, b)| (a, b, ptr)));
+            match parent {
+                // The parent is only borrowed to push its children, which are zipped in order.
+                Some(mut parent) => unsafe { parent.as_mut() }.children.push(zipped),
+                None => root = Some(zipped),
+            }
+        }
+
+        Ok(Tree { root: root.unwrap() })
+    }
+
     #[inline]
     /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Breadth-First Search**.
-    pub fn iter_bfs(&self) -> IterBFS<T> {
+    pub fn iter_bfs(&self) -> IterBFS<T, M> {
         IterBFS::new(self)
     }
     #[inline]
     /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Depth-First Search**.
-    pub fn iter_dfs(&self) -> IterDFS<T> {
+    pub fn iter_dfs(&self) -> IterDFS<T, M> {
         IterDFS::new(self)
     }
+    #[inline]
+    /// Iterate over the [`Node`]s of the *subtree* (including `self`) in the specified [`TraversalOrder`].
+    pub fn iter(&self, order: TraversalOrder) -> Iter<'_, T, M> {
+        Iter::new(self, order)
+    }
+
+    /// Computes a value for every [`Node`] of the *subtree* (including `self`) from its **content** and the values computed for its **children** (in order),
+    /// and returns the value computed for `self`. Leaves are called with no values.
+    ///
+    /// The [`Node`]s are visited in **Post-Order** (see [`IterPostOrder`]), without recursion.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// // (1 + 2) * 3
+    /// let expr = Node::builder("*")
+    ///     .child(Node::builder("+").child(Node::builder("1")).child(Node::builder("2")))
+    ///     .child(Node::builder("3"))
+    ///     .build();
+    /// let result = expr.fold_bottom_up(|token, operands: Vec<i32>| match *token {
+    ///     "+" => operands.into_iter().sum(),
+    ///     "*" => operands.into_iter().product(),
+    ///     n => n.parse().unwrap(),
+    /// });
+    /// assert_eq!(result, 9);
+    /// ```
+    pub fn fold_bottom_up<R>(&self, mut f: impl FnMut(&T, Vec<R>) -> R) -> R {
+        let mut results = vec![];
+
+        // The results of a Node's children are always the last ones in the stack when the Node is visited.
+        for node in self.iter(TraversalOrder::PostOrder) {
+            let children = results.split_off(results.len() - node.children.len());
+            results.push(f(&node.content, children));
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Walk down the *subtree* starting at `self`, calling **chooser** with each visited [`Node`]'s **content** and **children**
+    /// to pick which **child** to descend into next, until it returns [`Descend::Stop`] (the [`Node`] where it stopped is returned).
+    ///
+    /// This is useful for *decision trees* and *tries*, and does not allocate.
+    ///
+    /// # Panics
+    /// If **chooser** returns a [`Descend::Child`] index that is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Descend, Node};
+    /// // A binary search tree.
+    /// let tree = Node::builder(5)
+    ///     .child(Node::builder(2).child(Node::builder(1)).child(Node::builder(3)))
+    ///     .child(Node::builder(8))
+    ///     .build();
+    /// let found = tree.descend_by(|&n, children| match 3.cmp(&n) {
+    ///     std::cmp::Ordering::Less if children.len() > 0 => Descend::Child(0),
+    ///     std::cmp::Ordering::Greater if children.len() > 1 => Descend::Child(1),
+    ///     _ => Descend::Stop,
+    /// });
+    /// assert_eq!(found.content, 3);
+    /// ```
+    pub fn descend_by(&self, mut chooser: impl FnMut(&T, ChildrenIter<'_, T, M>) -> Descend) -> &Self {
+        let mut node = self;
+
+        while let Descend::Child(index) = chooser(&node.content, node.children_iter()) {
+            node = node.children[index].as_ref().get_ref();
+        }
+
+        node
+    }
+
+    /// Collect some metrics about the shape of the *subtree* (including `self`).
+    /// The [`Tree`] is traversed level by level (**Breadth-First**) only once.
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats::default();
+        let mut level = vec![self];
+
+        while !level.is_empty() {
+            stats.nodes += level.len();
+            stats.max_width = stats.max_width.max(level.len());
+
+            let mut next_level = vec![];
+            for node in level {
+                let children = node.children_iter();
+                if children.len() == 0 {
+                    stats.leaves += 1;
+                }
+                stats.max_branching = stats.max_branching.max(children.len());
+                next_level.extend(children);
+            }
+
+            if !next_level.is_empty() {
+                stats.height += 1;
+            }
+            level = next_level;
+        }
+
+        stats
+    }
 
     #[inline]
     /// Whether two [`Node`]s are the same (that is, they reference the same object).
     pub fn is_same_as(&self, other: impl AsPtr<Raw = Self>) -> bool {
         std::ptr::eq(self, other.as_ptr())
     }
+    #[cfg(feature = "ffi")]
+    #[inline]
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation
+    }
+    #[inline]
+    /// Get a [`NodeRef`] handle for **self**, which, unlike [`Node::ptr()`], is safe to use after **self** is detached or dropped.
+    pub fn node_ref(&self) -> NodeRef<T, M> {
+        // Only heap-allocated Nodes can be in a Tree, and they are never moved.
+        if let Some(ptr) = self.this {
+            if !self.referenced.replace(true) {
+                handle::register(ptr.cast(), self.generation);
+            }
+        }
+        NodeRef::new(self, self.generation)
+    }
     #[inline]
     /// Get a *[`NonNull`] pointer* for **self**, which should only be treated as a `*const Self`.
     /// Useful for [`Tree::detach_descendant`] and [`Tree::borrow_descendant`].
     pub fn ptr(&self) -> NonNull<Self> {
-        NonNull::from(self)
+        self.this.unwrap_or_else(|| NonNull::from(self))
     }
 }
-impl<T> Node<T>
-where T: Clone {
-    /// Copies the [`Node`]'s [`content`](Node::content) and its [`children`](Node::children) recursively.
+impl<T> Node<T> {
+    #[inline]
+    pub fn builder(content: T) -> NodeBuilder<T> {
+        NodeBuilder::new(content)
+    }
+}
+impl<T, M> Node<T, M>
+where T: Default {
+    /// Moves the **content** out of **self**, leaving [`T::default()`](Default::default()) in its place.
+    #[inline]
+    pub fn take_content(self: Pin<&mut Self>) -> T {
+        self.replace_content(T::default())
+    }
+}
+impl<T, M> Node<T, M>
+where T: Clone, M: Clone {
+    /// Copies the [`Node`]'s [`content`](Node::content) (and [`meta`](Node::meta())) and the whole subtree of its [`children`](Node::children).
     /// The resulting cloned [`Node`] will have no **parent**.
     ///
     /// For a method that clones the [`Node`] but *not* its subtree, see [`Node::clone`].
-    pub fn clone_deep(&self) -> Tree<T> {
-        let mut root = Box::pin(self.clone());
+    ///
+    /// Doesn't recurse, so [`Node`]s of any depth can be cloned.
+    pub fn clone_deep(&self) -> Tree<T, M> {
+        let root = self.clone().pin_in(&self.allocator());
+        // Each Node is paired with its clone, whose children haven't been cloned yet.
+        let mut stack = vec![(self, root.ptr())];
+
+        while let Some((node, mut cloned)) = stack.pop() {
+            // Each clone is only borrowed while its children are cloned.
+            let cloned_mut = unsafe { cloned.as_mut() };
+            cloned_mut.len = node.len;
+            cloned_mut.children.reserve_exact(node.children.len());
 
-        unsafe { root.as_mut().get_unchecked_mut() }.children =
-            self.clone_children_deep(root.ptr());
+            for child in node.children_iter() {
+                let mut cloned_child = child.clone().pin_in(&child.allocator());
+                unsafe { cloned_child.as_mut().get_unchecked_mut() }.parent = Some(cloned);
+                stack.push((child, cloned_child.ptr()));
+                cloned_mut.children.push(cloned_child);
+            }
+        }
 
         Tree { root }
     }
-    fn clone_children_deep(&self, parent: Parent<Self>) -> Vec<Owned<Self>> {
-        self.children
-            .iter()
-            .map(|node| {
-                let mut child = Box::pin(node.as_ref().get_ref().clone());
-                let mut_child = unsafe { child.as_mut().get_unchecked_mut() };
-                mut_child.parent = Some(parent);
-                mut_child.children = node.clone_children_deep(mut_child.ptr());
-                child
-            })
-            .collect()
-    }
 }
-impl<T> Node<T>
+impl<T, M> Node<T, M>
 where T: Debug {
     /// [`Debug`] the entire subtree (`self` and its **children**).
     #[inline]
-    pub fn debug_tree(&self) -> DebugTree<T> {
+    pub fn debug_tree(&self) -> DebugTree<T, M> {
         DebugTree { root: self }
     }
 }
 
-impl<T> Default for Node<T>
-where T: Default {
-    /// Creates a Node with the Default content.
+impl<T, M> std::ops::Index<usize> for Node<T, M> {
+    type Output = Self;
+
+    /// Get the **child** at **index**. See [`Node::get()`].
+    ///
+    /// # Panics
+    /// If **index** is out of bounds.
+    fn index(&self, index: usize) -> &Self {
+        self.children[index].as_ref().get_ref()
+    }
+}
+impl<T, M> Extend<Tree<T, M>> for Pin<&mut Node<T, M>> {
+    /// See [`Node::append_children()`].
+    #[inline]
+    fn extend<I: IntoIterator<Item = Tree<T, M>>>(&mut self, children: I) {
+        self.as_mut().append_children(children)
+    }
+}
+impl<T, M> Drop for Node<T, M> {
+    /// Drops the *subtree* without recursion, so that very deep [`Tree`]s don't overflow the stack.
+    /// Each [`Node`] is dropped after its **children** are moved to the work stack, so it has no **children** left to drop.
+    fn drop(&mut self) {
+        if self.referenced.get() {
+            handle::unregister(self.ptr().cast());
+        }
+        let mut stack = self.children.drain(..).collect::<Vec<_>>();
+        while let Some(mut node) = stack.pop() {
+            // The Node is not moved, only its children.
+            // (The children list can have a different allocator than the stack, so it can't be appended.)
+            for child in unsafe { node.as_mut().get_unchecked_mut() }.children.drain(..) {
+                stack.push(child);
+            }
+        }
+    }
+}
+impl<T, M> Default for Node<T, M>
+where T: Default, M: Default {
+    /// Creates a Node with the Default content (and metadata).
     /// Converting the returned Node to a [`Tree`] is recommended.
     fn default() -> Self {
-        Self {
-            content: T::default(),
-            parent: None,
-            children: vec![],
-            _pin: PhantomPinned,
-        }
+        Self::new(T::default(), M::default(), &TreeAllocator::default())
     }
 }
-impl<T> Clone for Node<T>
-where T: Clone {
-    /// Copies the [`Node`]'s [`content`](Node::content), but not its [`children`](Node::children).
+impl<T, M> Clone for Node<T, M>
+where T: Clone, M: Clone {
+    /// Copies the [`Node`]'s [`content`](Node::content) (and [`meta`](Node::meta())), but not its [`children`](Node::children).
     /// The resulting cloned [`Node`] will have no **parent** or **children**.
     ///
     /// Converting the returned Node to a [`Tree`] is recommended.
     ///
     /// For a method that clones the [`Node`] *and* its subtree, see [`Node::clone_deep`].
     fn clone(&self) -> Self {
-        Self {
-            content: self.content.clone(),
-            parent: None,
-            children: vec![],
-            _pin: PhantomPinned,
-        }
+        Self::new(self.content.clone(), self.meta.clone(), &self.allocator())
     }
 }
-impl<T> PartialEq for Node<T>
+impl<T, M> PartialEq for Node<T, M>
 where T: PartialEq {
     fn eq(&self, other: &Self) -> bool {
         self.content == other.content
     }
 }
-impl<T> Eq for Node<T>
+impl<T, M> Eq for Node<T, M>
 where T: Eq {}
-impl<T> Debug for Node<T>
+impl<T, M> Debug for Node<T, M>
 where T: Debug {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Node")
@@ -0,0 +1,143 @@
+//! Traits implemented by both the boxed [`Node`]s and [`Tree`]s and the [`rc`](crate::rc) ones,
+//! so that algorithms that only read a [`Tree`] can be written once for both.
+use super::*;
+use std::{marker::PhantomData, ops::Deref};
+
+/// The read-only operations of a *node* that both the boxed [`Node`] (as `&Node`) and [`rc::Node`](crate::rc::Node) have.
+///
+/// A value of this type is a *handle* to a *node*, which is cheap to copy with [`NodeOps::ref_clone()`]
+/// (a *reference* for the boxed [`Node`], and a *reference counted* pointer for the [`rc::Node`](crate::rc::Node)).
+///
+/// # Example
+/// ```
+/// # use tree_struct::{Node, NodeOps};
+/// // Works with any backend.
+/// fn leaves<T: Clone, N: NodeOps<T>>(node: &N) -> Vec<T> {
+///     node.descendants().filter(NodeOps::is_leaf).map(|n| n.content().clone()).collect()
+/// }
+///
+/// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+/// assert_eq!(leaves(&tree.root()), vec!['d', 'c']);
+/// ```
+pub trait NodeOps<T>: Sized {
+    /// Borrows the **content** of a *node* (e.g. a *read lock* for the [`rc::Node`](crate::rc::Node)).
+    type Content<'a>: Deref<Target = T>
+    where Self: 'a;
+
+    fn content(&self) -> Self::Content<'_>;
+    fn parent(&self) -> Option<Self>;
+    fn children(&self) -> Box<[Self]>;
+    /// Get the *child* at **index**, without getting all the **children**.
+    fn get(&self, index: usize) -> Option<Self>;
+    /// The number of **children**.
+    fn child_count(&self) -> usize;
+    /// Get another *handle* to the same *node*.
+    fn ref_clone(&self) -> Self;
+
+    #[inline]
+    fn is_leaf(&self) -> bool {
+        self.child_count() == 0
+    }
+    /// The number of ancestors of **self** (the **root** is at depth `0`).
+    fn depth(&self) -> usize {
+        std::iter::successors(self.parent(), NodeOps::parent).count()
+    }
+    /// Iterates over **self** and all its descendants in **Depth-First** order.
+    #[inline]
+    fn descendants(&self) -> Descendants<T, Self> {
+        Descendants {
+            stack: vec![self.ref_clone()],
+            _content: PhantomData,
+        }
+    }
+}
+
+/// The read-only operations of a *tree* that both the boxed [`Tree`] and [`rc::Tree`](crate::rc::Tree) have.
+///
+/// # Example
+/// ```
+/// # use tree_struct::{Node, NodeOps, TreeOps};
+/// fn max_depth<T>(tree: &impl TreeOps<T>) -> usize {
+///     tree.root().descendants().map(|n| n.depth()).max().unwrap()
+/// }
+///
+/// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+/// assert_eq!(max_depth(&tree), 2);
+/// assert_eq!(TreeOps::len(&tree), 4);
+/// ```
+pub trait TreeOps<T> {
+    /// The *handle* to the [`Node`]s of the *tree*.
+    type Node<'a>: NodeOps<T>
+    where Self: 'a;
+
+    fn root(&self) -> Self::Node<'_>;
+    /// The number of *nodes* in the *tree*.
+    fn len(&self) -> usize {
+        self.root().descendants().count()
+    }
+    /// Always `false`, because a *tree* always has a **root**.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Iterates over a *node* and its descendants in **Depth-First** order. Obtained by calling [`NodeOps::descendants()`].
+pub struct Descendants<T, N>
+where N: NodeOps<T> {
+    stack: Vec<N>,
+    _content: PhantomData<fn() -> T>,
+}
+impl<T, N> Iterator for Descendants<T, N>
+where N: NodeOps<T> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children().into_vec().into_iter().rev());
+        Some(node)
+    }
+}
+
+impl<T> NodeOps<T> for &Node<T> {
+    type Content<'a> = &'a T
+    where Self: 'a;
+
+    #[inline]
+    fn content(&self) -> &T {
+        &self.content
+    }
+    #[inline]
+    fn parent(&self) -> Option<Self> {
+        Node::parent(*self)
+    }
+    #[inline]
+    fn children(&self) -> Box<[Self]> {
+        Node::children(*self)
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<Self> {
+        Node::get(*self, index)
+    }
+    #[inline]
+    fn child_count(&self) -> usize {
+        self.children_iter().len()
+    }
+    #[inline]
+    fn ref_clone(&self) -> Self {
+        self
+    }
+}
+impl<T> TreeOps<T> for Tree<T> {
+    type Node<'a> = &'a Node<T>
+    where Self: 'a;
+
+    #[inline]
+    fn root(&self) -> &Node<T> {
+        Tree::root(self)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        Tree::len(self)
+    }
+}
@@ -0,0 +1,78 @@
+use super::*;
+use std::{mem::ManuallyDrop, ops::Deref};
+
+/// Owns a heap-allocated and [`Pin`]ned [`Node`], like `Pin<Box<Node>>`.
+///
+/// Unlike a [`Box`], moving or borrowing this pointer doesn't assert that it has *unique* access to the [`Node`].
+/// The *children* of the [`Node`] hold a copy of this pointer as their **parent**,
+/// and a [`Box`] would invalidate those copies (under *Stacked Borrows* and *Tree Borrows*) every time it is moved (e.g. when the [`Tree`] is moved).
+///
+/// All the other pointers to the [`Node`] (the *children*'s **parent** and the one returned by [`Node::ptr()`]) are copies of this pointer,
+/// so they all have the same *provenance*, and they stay valid until the [`Node`] is dropped.
+pub(crate) struct Owned<T> {
+    ptr: NonNull<T>,
+    #[cfg(feature = "allocator_api")]
+    alloc: TreeAllocator,
+}
+impl<T> Owned<T> {
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in(value: T, alloc: &TreeAllocator) -> Self {
+        let (ptr, alloc) = Box::into_raw_with_allocator(Box::new_in(value, alloc.clone()));
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            alloc,
+        }
+    }
+    #[cfg(not(feature = "allocator_api"))]
+    pub fn new_in(value: T, _: &TreeAllocator) -> Self {
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(value))) },
+        }
+    }
+
+    /// The pointer that owns the value. Use it instead of a pointer obtained from a *reference* to the value.
+    #[inline]
+    pub fn ptr(&self) -> NonNull<T> {
+        self.ptr
+    }
+    #[inline]
+    pub fn as_ref(&self) -> Pin<&T> {
+        unsafe { Pin::new_unchecked(self.ptr.as_ref()) }
+    }
+    #[inline]
+    pub fn as_mut(&mut self) -> Pin<&mut T> {
+        unsafe { Pin::new_unchecked(&mut *self.ptr.as_ptr()) }
+    }
+
+    /// Moves the value out of the allocation.
+    /// The caller must ensure that nothing points to the value anymore, because it was [`Pin`]ned.
+    pub unsafe fn into_inner(self) -> T {
+        *self.into_box()
+    }
+    #[cfg(feature = "allocator_api")]
+    fn into_box(self) -> Box<T, TreeAllocator> {
+        let this = ManuallyDrop::new(self);
+        unsafe { Box::from_raw_in(this.ptr.as_ptr(), std::ptr::read(&this.alloc)) }
+    }
+    #[cfg(not(feature = "allocator_api"))]
+    fn into_box(self) -> Box<T> {
+        let this = ManuallyDrop::new(self);
+        unsafe { Box::from_raw(this.ptr.as_ptr()) }
+    }
+}
+impl<T> Deref for Owned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+impl<T> Drop for Owned<T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "allocator_api")]
+        drop(unsafe { Box::from_raw_in(self.ptr.as_ptr(), self.alloc.clone()) });
+        #[cfg(not(feature = "allocator_api"))]
+        drop(unsafe { Box::from_raw(self.ptr.as_ptr()) });
+    }
+}
@@ -0,0 +1,284 @@
+//! Addressing [`Node`]s with *paths* of child indices (or *string paths*, e.g. for config files and CLI tools).
+use super::*;
+use std::{fmt::Display, num::ParseIntError, ops::Deref, str::FromStr};
+
+/// The *path* of a [`Node`]: the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**.
+/// The **root**'s path is empty. Obtained by calling [`Node::path()`].
+///
+/// It dereferences to a slice of the indices, so it can be passed to the methods that take a path as `&[usize]`.
+/// It is [`Display`]ed (and parsed with [`FromStr`]) in the same format as [`Node::str_path()`], e.g. `"0/2/1"`.
+///
+/// # Example
+/// ```
+/// # use tree_struct::NodePath;
+/// let path = "1/0".parse::<NodePath>().unwrap();
+/// assert_eq!(path.parent().unwrap().to_string(), "1");
+/// assert!(path.starts_with(&NodePath::from(vec![1])));
+/// assert_eq!(path.concat(&path).to_string(), "1/0/1/0");
+/// assert_eq!(NodePath::root().to_string(), "");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct NodePath(Vec<usize>);
+impl NodePath {
+    /// The path of the **root**, which is empty.
+    #[inline]
+    pub fn root() -> Self {
+        Self(vec![])
+    }
+    #[inline]
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The path of the **parent**, or [`None`] if this is the **root**'s path.
+    pub fn parent(&self) -> Option<Self> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+    /// The path of the *child* at **index** of the [`Node`] at this path.
+    pub fn child(&self, index: usize) -> Self {
+        let mut child = self.clone();
+        child.push(index);
+        child
+    }
+    /// Makes this the path of the *child* at **index**.
+    #[inline]
+    pub fn push(&mut self, index: usize) {
+        self.0.push(index)
+    }
+    /// Makes this the path of the **parent**, and returns the removed index (or [`None`] if this is the **root**'s path).
+    #[inline]
+    pub fn pop(&mut self) -> Option<usize> {
+        self.0.pop()
+    }
+    /// Whether the [`Node`] at this path is in the subtree of the [`Node`] at **prefix** (including that [`Node`] itself).
+    #[inline]
+    pub fn starts_with(&self, prefix: &Self) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+    /// The path of the [`Node`] at **other** when it is taken relative to the [`Node`] at this path.
+    pub fn concat(&self, other: &Self) -> Self {
+        Self(self.0.iter().chain(&other.0).copied().collect())
+    }
+}
+impl Deref for NodePath {
+    type Target = [usize];
+
+    #[inline]
+    fn deref(&self) -> &[usize] {
+        &self.0
+    }
+}
+impl AsRef<[usize]> for NodePath {
+    #[inline]
+    fn as_ref(&self) -> &[usize] {
+        &self.0
+    }
+}
+impl From<Vec<usize>> for NodePath {
+    #[inline]
+    fn from(indices: Vec<usize>) -> Self {
+        Self(indices)
+    }
+}
+impl From<NodePath> for Vec<usize> {
+    #[inline]
+    fn from(path: NodePath) -> Self {
+        path.0
+    }
+}
+impl FromIterator<usize> for NodePath {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl Display for NodePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, index) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{index}")?;
+        }
+        Ok(())
+    }
+}
+impl FromStr for NodePath {
+    type Err = ParseIntError;
+
+    /// Parses the indices separated by slashes (`/`). An empty string is the **root**'s path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::root());
+        }
+        s.split('/').map(str::parse).collect()
+    }
+}
+
+impl<T> Node<T> {
+    /// The [`NodePath`] of the [`Node`]. Use [`Tree::get_by_path()`] to get the [`Node`] back.
+    pub fn path(&self) -> NodePath {
+        let mut indices = vec![];
+        let mut node = self;
+        while let Some(parent) = node.parent() {
+            indices.push(parent.child_index(node.ptr()).expect("Node is not found in its parent"));
+            node = parent;
+        }
+        indices.reverse();
+        NodePath(indices)
+    }
+    /// The *path* of the [`Node`] as a string:
+    /// the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**, separated by slashes (`/`).
+    /// The **root**'s path is an empty string.
+    ///
+    /// Use [`Tree::get_by_str_path()`] to get the [`Node`] back.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).child(Node::builder('c').child(Node::builder('d'))).build();
+    /// let d = tree.root().children()[1].children()[0];
+    /// assert_eq!(d.str_path(), "1/0");
+    /// assert!(tree.get_by_str_path("1/0").unwrap().is_same_as(d));
+    /// ```
+    #[inline]
+    pub fn str_path(&self) -> String {
+        self.path().to_string()
+    }
+}
+impl<T> Node<T>
+where T: AsRef<str> {
+    /// Like [`Node::str_path()`], but each [`Node`] (except the **root**) is referenced by its **content** instead of its index.
+    ///
+    /// Use [`Tree::get_by_name_path()`] to get the [`Node`] back.
+    /// The path will not lead back to the same [`Node`] if a **content** contains a slash,
+    /// or if an earlier **sibling** of one of the [`Node`]s has the same **content**.
+    pub fn name_path(&self) -> String {
+        let mut names = vec![];
+        let mut node = self;
+        while let Some(parent) = node.parent() {
+            names.push(node.content.as_ref());
+            node = parent;
+        }
+        names.reverse();
+        names.join("/")
+    }
+}
+
+impl<T> Tree<T> {
+    /// Get the [`Node`] at **path** (e.g. a [`NodePath`]), or [`None`] if there is no [`Node`] at that path.
+    pub fn get_by_path(&self, path: &[usize]) -> Option<&Node<T>> {
+        path.iter().try_fold(self.root(), |node, &index| node.get(index))
+    }
+    /// Get the [`Node`] at a path produced by [`Node::str_path()`] (e.g. `"0/2/1"`). An empty string is the **root**.
+    ///
+    /// Returns [`None`] if a segment is not an index, or there is no [`Node`] at that path.
+    #[inline]
+    pub fn get_by_str_path(&self, path: &str) -> Option<&Node<T>> {
+        self.get_by_path(&path.parse::<NodePath>().ok()?)
+    }
+}
+impl<T> Tree<T>
+where T: AsRef<str> {
+    /// Get the [`Node`] at a path produced by [`Node::name_path()`] (e.g. `"users/alice/settings"`).
+    /// Each segment is the **content** of a **child** of the previous [`Node`], starting with the **root**'s **children**
+    /// (the first child with that **content** is chosen). An empty string is the **root**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder("config")
+    ///     .child(Node::builder("users").child(Node::builder("alice")))
+    ///     .build();
+    /// let alice = tree.get_by_name_path("users/alice").unwrap();
+    /// assert_eq!(alice.content, "alice");
+    /// assert_eq!(alice.name_path(), "users/alice");
+    /// assert!(tree.get_by_name_path("users/bob").is_none());
+    /// ```
+    pub fn get_by_name_path(&self, path: &str) -> Option<&Node<T>> {
+        let mut node = self.root();
+        if path.is_empty() {
+            return Some(node);
+        }
+        for segment in path.split('/') {
+            node = node.children().iter().find(|child| child.content.as_ref() == segment).copied()?;
+        }
+        Some(node)
+    }
+}
+
+impl<T> Tree<T>
+where T: Default {
+    /// Like [`collect()`](FromIterator::from_iter())ing the *(path, content)* pairs,
+    /// but returns [`TreeError::IndexOutOfBounds`] if an index is greater than the number of **siblings** created so far,
+    /// instead of filling the gap with [`T::default()`](Default::default()).
+    ///
+    /// So the **parent** of a path can still be missing, but the pairs must be in an order where each **sibling** comes before the next one
+    /// (e.g. **Breadth-First** or **Depth-First** order).
+    /// Use this when the paths come from an untrusted source, since a single huge index can't make it allocate a huge number of [`Node`]s.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{Tree, TreeError};
+    /// let tree = Tree::from_paths([(vec![], "a"), (vec![0, 0], "c"), (vec![1], "d")]).unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["a", "", "d", "c"]);
+    ///
+    /// let error = Tree::from_paths([(vec![0], 'b'), (vec![usize::MAX], 'c')]).unwrap_err();
+    /// assert_eq!(error, TreeError::IndexOutOfBounds { index: usize::MAX, len: 1 });
+    /// ```
+    pub fn from_paths<P>(pairs: impl IntoIterator<Item = (P, T)>) -> Result<Self, TreeError>
+    where P: AsRef<[usize]> {
+        let mut root = NodeBuilder::new(T::default());
+
+        for (path, content) in pairs {
+            let mut node = &mut root;
+            for &index in path.as_ref() {
+                let len = node.children.len();
+                if index > len {
+                    return Err(TreeError::IndexOutOfBounds { index, len });
+                }
+                if index == len {
+                    node.children.push(NodeBuilder::new(T::default()));
+                }
+                node = &mut node.children[index];
+            }
+            node.content = content;
+        }
+
+        Ok(root.build())
+    }
+}
+
+impl<T, P> FromIterator<(P, T)> for Tree<T>
+where T: Default, P: AsRef<[usize]> {
+    /// Builds a [`Tree`] from *(path, content)* pairs, where a path is the index of each [`Node`] in its **parent**'s [`children`](Node::children()),
+    /// starting from the **root** (e.g. a [`NodePath`] or a [`Vec`]). An empty path is the **root**.
+    ///
+    /// The [`Node`]s that are not given a **content** (e.g. the **parent** of a path, or an earlier **sibling**) get [`T::default()`](Default::default()),
+    /// so the pairs can come in any order. If a path is repeated, the last **content** is kept.
+    ///
+    /// The indices are trusted: an index allocates that many **siblings** (and panics if that's more than fits in memory, e.g. [`usize::MAX`]).
+    /// Use [`Tree::from_paths()`] to reject the indices that would leave a gap.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let tree = [(vec![1, 0], "d"), (vec![], "a"), (vec![0], "b")].into_iter().collect::<Tree<_>>();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["a", "b", "", "d"]);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        let mut root = NodeBuilder::new(T::default());
+
+        for (path, content) in iter {
+            let mut node = &mut root;
+            for &index in path.as_ref() {
+                if node.children.len() <= index {
+                    node.children.resize_with(index + 1, || NodeBuilder::new(T::default()));
+                }
+                node = &mut node.children[index];
+            }
+            node.content = content;
+        }
+
+        root.build()
+    }
+}
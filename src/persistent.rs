@@ -0,0 +1,200 @@
+//! An *immutable* (persistent) [`Tree`], where every edit returns a new [`Tree`] and leaves the old one as it was.
+//!
+//! The new [`Tree`] shares all the unchanged *subtrees* with the old one through [`Arc`]s,
+//! so an edit only copies the [`Node`]s on the *path* from the **root** to the edited [`Node`].
+//! This makes keeping old versions (e.g. for *undo*/*redo* or *snapshots*) much cheaper than [`clone_deep()`](crate::Node::clone_deep()).
+//!
+//! [`Node`]s are addressed by their *path* of **child** indices from the **root** (like [`NodePath`](crate::NodePath)).
+//!
+//! # Example
+//! ```
+//! # use tree_struct::persistent::Tree;
+//! let v1 = Tree::new("parent").insert_at(&[0], Tree::new("child a")).unwrap();
+//! let v2 = v1.insert_at(&[1], Tree::new("child b")).unwrap();
+//! let v3 = v2.set_at(&[0], "child A").unwrap();
+//!
+//! // The old versions are unchanged, so undoing is just going back to one of them.
+//! let contents = |tree: &Tree<&'static str>| tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>();
+//! assert_eq!(contents(&v1), ["parent", "child a"]);
+//! assert_eq!(contents(&v2), ["parent", "child a", "child b"]);
+//! assert_eq!(contents(&v3), ["parent", "child A", "child b"]);
+//! // "child b" was not edited, so it is shared.
+//! assert!(v2.get(&[1]).unwrap().is_same_as(v3.get(&[1]).unwrap()));
+//! ```
+use std::sync::Arc;
+
+/// A [`Node`] of a persistent [`Tree`]. It can't be modified, only replaced in a new version of the [`Tree`].
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Node<T> {
+    pub content: T,
+    children: Vec<Arc<Self>>,
+    /// The number of [`Node`]s in the *subtree* (including `self`).
+    len: usize,
+}
+impl<T> Node<T> {
+    fn new(content: T, children: Vec<Arc<Self>>) -> Self {
+        let len = 1 + children.iter().map(|child| child.len).sum::<usize>();
+        Self { content, children, len }
+    }
+
+    /// Iterate over the **children**.
+    #[inline]
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = &Self> + ExactSizeIterator + '_ {
+        self.children.iter().map(|child| &**child)
+    }
+    /// Get the *child* at **index**, if any.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Self> {
+        self.children.get(index).map(|child| &**child)
+    }
+    /// The number of [`Node`]s in the *subtree* (including `self`). This is `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Always `false`, because the *subtree* always has **self**.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Whether **self** and **other** are the same [`Node`] (e.g. a *subtree* that is shared by 2 versions of a [`Tree`]), not just equal.
+    #[inline]
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+impl<T> Clone for Node<T>
+where T: Clone {
+    /// Copies the **content**, but the **children** are shared.
+    fn clone(&self) -> Self {
+        Self {
+            content: self.content.clone(),
+            children: self.children.clone(),
+            len: self.len,
+        }
+    }
+}
+impl<T> Drop for Node<T> {
+    /// Drops the [`Node`]s that are not shared with another [`Tree`] without recursion, so that very deep [`Tree`]s don't overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(child) = stack.pop() {
+            if let Ok(mut child) = Arc::try_unwrap(child) {
+                stack.append(&mut child.children);
+            }
+        }
+    }
+}
+
+/// A persistent [`Tree`]. See the [module documentation](self).
+///
+/// [`Clone`]ing a [`Tree`] only copies the pointer to its **root**.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Tree<T> {
+    root: Arc<Node<T>>,
+}
+impl<T> Tree<T> {
+    /// A [`Tree`] with only a **root**.
+    #[inline]
+    pub fn new(root: T) -> Self {
+        Self {
+            root: Arc::new(Node::new(root, vec![])),
+        }
+    }
+
+    #[inline]
+    pub fn root(&self) -> &Node<T> {
+        &self.root
+    }
+    /// The number of [`Node`]s in the [`Tree`]. This is `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.root.len
+    }
+    /// Always `false`, because a [`Tree`] always has a **root**.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+    /// Get the [`Node`] at **path** (the indices of the *children* to follow from the **root**), if any.
+    pub fn get(&self, path: &[usize]) -> Option<&Node<T>> {
+        path.iter().try_fold(self.root(), |node, &index| node.get(index))
+    }
+
+    /// Iterate over the [`Node`]s of the [`Tree`] using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = &Node<T>> + '_ {
+        let mut stack = vec![self.root()];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(node.children().rev());
+            Some(node)
+        })
+    }
+}
+impl<T> Tree<T>
+where T: Clone {
+    /// Copies the [`Node`]s on **path** (so that the [`Node`]s of **self** are not modified)
+    /// and calls **f** with the copy of the last one, whose *subtree* grows by **added** [`Node`]s.
+    /// **path** must be valid.
+    fn edit(&self, path: &[usize], added: isize, f: impl FnOnce(&mut Node<T>)) -> Self {
+        let mut tree = self.clone();
+        let mut node = Arc::make_mut(&mut tree.root);
+        for &index in path {
+            node.len = node.len.wrapping_add_signed(added);
+            node = Arc::make_mut(&mut node.children[index]);
+        }
+        node.len = node.len.wrapping_add_signed(added);
+        f(node);
+        tree
+    }
+
+    /// Returns a new [`Tree`] where the [`Node`] at **path** has **content**.
+    /// Returns [`None`] if there is no [`Node`] at **path**.
+    pub fn set_at(&self, path: &[usize], content: T) -> Option<Self> {
+        self.get(path)?;
+        Some(self.edit(path, 0, |node| node.content = content))
+    }
+    /// Returns a new [`Tree`] where **subtree** is inserted at **path**,
+    /// i.e. as the *child* at index `path.last()` of the [`Node`] at the rest of **path**.
+    /// **subtree** is shared, not copied.
+    ///
+    /// Returns [`None`] if **path** is empty (the **root** can't be replaced this way), if its **parent** doesn't exist,
+    /// or if the index is greater than the number of *children*.
+    pub fn insert_at(&self, path: &[usize], subtree: Tree<T>) -> Option<Self> {
+        let (&index, parent) = path.split_last()?;
+        if index > self.get(parent)?.children.len() {
+            return None;
+        }
+        let added = subtree.len() as isize;
+        Some(self.edit(parent, added, |node| node.children.insert(index, subtree.root)))
+    }
+    /// Returns a new [`Tree`] without the [`Node`] (and *subtree*) at **path**, and the removed *subtree*.
+    /// Returns [`None`] if there is no [`Node`] at **path**, or if **path** is empty (the **root** can't be removed).
+    pub fn remove_at(&self, path: &[usize]) -> Option<(Self, Tree<T>)> {
+        let (&index, parent) = path.split_last()?;
+        let removed = Arc::clone(self.get(parent)?.children.get(index)?);
+        let tree = self.edit(parent, -(removed.len as isize), |node| {
+            node.children.remove(index);
+        });
+        Some((tree, Tree { root: removed }))
+    }
+}
+impl<T> Clone for Tree<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            root: Arc::clone(&self.root),
+        }
+    }
+}
+
+impl<T> From<&crate::Tree<T>> for Tree<T>
+where T: Clone {
+    /// Copies all the **contents** of the [`Tree`](crate::Tree).
+    fn from(tree: &crate::Tree<T>) -> Self {
+        Self {
+            root: tree.fold_bottom_up(|content, children| Arc::new(Node::new(content.clone(), children))),
+        }
+    }
+}
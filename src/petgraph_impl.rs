@@ -0,0 +1,131 @@
+//! Conversions between [`Tree`] and [`petgraph`]'s [`DiGraph`].
+use super::*;
+use petgraph::{
+    graph::{DiGraph, NodeIndex},
+    visit::EdgeRef,
+    Direction,
+};
+use std::collections::VecDeque;
+
+/// Returned by [`Tree::from_petgraph()`] when the graph is not a *rooted tree*.
+/// **node** is the index of the offending node in the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphError {
+    /// The graph has no nodes.
+    Empty,
+    /// Every node has an incoming edge, so there is no **root**.
+    NoRoot,
+    /// More than one node has no incoming edges.
+    MultipleRoots,
+    /// A node has more than one incoming edge (i.e. more than one **parent**).
+    MultipleParents { node: usize },
+    /// A node can't be reached from the **root** (it is part of a cycle).
+    Unreachable { node: usize },
+}
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the graph is empty"),
+            Self::NoRoot => write!(f, "the graph has no root"),
+            Self::MultipleRoots => write!(f, "the graph has more than one root"),
+            Self::MultipleParents { node } => write!(f, "node {node} has more than one parent"),
+            Self::Unreachable { node } => write!(f, "node {node} can't be reached from the root"),
+        }
+    }
+}
+impl std::error::Error for GraphError {}
+
+impl<T> Tree<T> {
+    /// Copies the [`Tree`] into a [`DiGraph`] with an edge from each **parent** to each of its **children**.
+    /// The **root** is the node at index `0`, and the rest are added in **Breadth-First** order.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    /// let graph = tree.to_petgraph();
+    /// assert_eq!(graph.node_weights().collect::<String>(), "abcd");
+    /// assert_eq!(graph.edge_count(), 3);
+    /// ```
+    pub fn to_petgraph(&self) -> DiGraph<T, ()>
+    where T: Clone {
+        let mut graph = DiGraph::new();
+        let mut queue = VecDeque::from([(None::<NodeIndex>, self.root())]);
+
+        while let Some((parent, node)) = queue.pop_front() {
+            let index = graph.add_node(node.content.clone());
+            if let Some(parent) = parent {
+                graph.add_edge(parent, index, ());
+            }
+            queue.extend(node.children().iter().map(|child| (Some(index), *child)));
+        }
+
+        graph
+    }
+
+    /// Builds a [`Tree`] from a [`DiGraph`] that is a *rooted tree*:
+    /// there is exactly one node with no incoming edges (the **root**), every other node has exactly one incoming edge,
+    /// and all the nodes can be reached from the **root**.
+    /// The edge weights are dropped.
+    ///
+    /// The **children** of a [`Node`] are in the order their edges were added to the graph.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{GraphError, Tree};
+    /// let mut graph = petgraph::graph::DiGraph::<char, ()>::new();
+    /// let a = graph.add_node('a');
+    /// let b = graph.add_node('b');
+    /// let c = graph.add_node('c');
+    /// graph.add_edge(a, c, ());
+    /// graph.add_edge(a, b, ());
+    ///
+    /// let tree = Tree::from_petgraph(graph.clone()).unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "acb");
+    ///
+    /// graph.add_edge(c, b, ());
+    /// assert_eq!(Tree::from_petgraph(graph), Err(GraphError::MultipleParents { node: 1 }));
+    /// ```
+    pub fn from_petgraph<E>(graph: DiGraph<T, E>) -> Result<Self, GraphError> {
+        let mut root = None;
+        for node in graph.node_indices() {
+            match graph.neighbors_directed(node, Direction::Incoming).count() {
+                0 if root.is_some() => return Err(GraphError::MultipleRoots),
+                0 => root = Some(node),
+                1 => {}
+                _ => return Err(GraphError::MultipleParents { node: node.index() }),
+            }
+        }
+        let root = match root {
+            Some(root) => root,
+            None if graph.node_count() == 0 => return Err(GraphError::Empty),
+            None => return Err(GraphError::NoRoot),
+        };
+
+        // Every node has at most 1 parent, so no node can be visited twice.
+        let mut visited = vec![false; graph.node_count()];
+        let mut entries = vec![];
+        let mut queue = VecDeque::from([(None, root)]);
+        while let Some((parent, node)) = queue.pop_front() {
+            let index = entries.len();
+            entries.push((parent, node.index()));
+            visited[node.index()] = true;
+
+            let mut edges = graph.edges_directed(node, Direction::Outgoing).collect::<Vec<_>>();
+            edges.sort_by_key(|edge| edge.id());
+            queue.extend(edges.into_iter().map(|edge| (Some(index), edge.target())));
+        }
+        if let Some(node) = visited.iter().position(|visited| !visited) {
+            return Err(GraphError::Unreachable { node });
+        }
+
+        let (nodes, _) = graph.into_nodes_edges();
+        let mut contents = nodes.into_iter().map(|node| Some(node.weight)).collect::<Vec<_>>();
+        let entries = entries
+            .into_iter()
+            .map(|(parent, index)| (parent, contents[index].take().unwrap()))
+            .collect();
+        // The entries are in Breadth-First order, so every parent comes before its children.
+        Ok(Self::from_flat(entries).unwrap())
+    }
+}
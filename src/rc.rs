@@ -0,0 +1,35 @@
+#![doc = include_str!("./rc_impl/README.md")]
+//!
+//! The Nodes in this module use [`Rc`] and [`RefCell`](std::cell::RefCell), so they can't be shared between threads.
+//! See the [`sync`](crate::sync) module (with the `arc` feature) for the thread-safe version.
+// The same implementation is also included by the `sync` module, with different types.
+#[allow(clippy::duplicate_mod)]
+#[path = "rc_impl/mod.rs"]
+mod rc_impl;
+
+pub use rc_impl::*;
+use std::{
+    rc::{Rc, Weak as WeakRc},
+    cell::{RefCell as RwLock, Ref as ReadLock, RefMut as WriteLock},
+    // Must have separate type (names) for referencing a Node borrow and one that derives from a Node borrow (see parking_lot import in `sync`).
+    cell::{Ref as ContentReadLock, RefMut as ContentWriteLock},
+};
+
+// Helper functions that allow writing the same code between RefCell and RwLock.
+// Resulting types for *read* are `impl Deref<Target = T>` and for *write* the DerefMut variant.
+#[inline]
+fn borrow<T>(this: &RwLock<T>) -> ReadLock<T> {
+    this.borrow()
+}
+#[inline]
+fn borrow_mut<T>(this: &RwLock<T>) -> WriteLock<T> {
+    this.borrow_mut()
+}
+#[inline]
+fn try_borrow<T>(this: &RwLock<T>) -> Option<ReadLock<'_, T>> {
+    this.try_borrow().ok()
+}
+#[inline]
+fn try_borrow_mut<T>(this: &RwLock<T>) -> Option<WriteLock<'_, T>> {
+    this.try_borrow_mut().ok()
+}
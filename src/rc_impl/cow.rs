@@ -0,0 +1,264 @@
+use super::*;
+use std::ops::Deref;
+
+/// A *copy-on-write* [`Tree`], obtained by calling [`Tree::into_cow()`].
+///
+/// [`Clone`]ing a [`CowTree`] is `O(1)`, because the clones share the same [`CowNode`]s,
+/// so it can be used to take cheap *snapshots* of a [`Tree`].
+///
+/// The *subtrees* are shared through [`Rc`]s, so modifying a [`CowTree`] only copies the [`CowNode`]s on the *path* from the **root**
+/// to the modified [`CowNode`] (and only those that are still shared), and the rest of the [`Tree`] stays shared with the clones.
+/// [`CowNode`]s don't have a **parent** (unlike [`Node`]s), which is what allows a *subtree* to be in multiple [`CowTree`]s.
+///
+/// A [`CowTree`] can only be modified through the [`CowNodeMut`] returned by [`Self::root_mut()`] or [`Self::get_mut()`],
+/// which borrows the [`CowTree`] mutably, so no handle to a [`CowNode`] can outlive the modification and affect a later clone.
+///
+/// # Example
+/// ```
+/// # use tree_struct::rc::Node;
+/// let mut tree = Node::builder(1).child(Node::builder(2)).child(Node::builder(3)).build().into_cow();
+/// let snapshot = tree.clone();
+/// assert!(tree.is_shared());
+///
+/// *tree.get_mut(&[0]).unwrap().content_mut() = 20;
+/// assert!(!tree.is_shared());
+/// assert_eq!(*tree.root().get(0).unwrap().content(), 20);
+/// assert_eq!(*snapshot.root().get(0).unwrap().content(), 2);
+/// // The second child was not modified, so it is still shared with the snapshot.
+/// assert!(tree.root().get(1).unwrap().is_same_as(snapshot.root().get(1).unwrap()));
+/// ```
+pub struct CowTree<T> {
+    root: Rc<CowNode<T>>,
+}
+impl<T> CowTree<T> {
+    /// A [`CowTree`] with only a **root**.
+    #[inline]
+    pub fn new(content: T) -> Self {
+        Self {
+            root: Rc::new(CowNode::new(content, vec![])),
+        }
+    }
+
+    #[inline]
+    pub fn root(&self) -> &CowNode<T> {
+        &self.root
+    }
+    /// Get the [`CowNode`] at **path** (the indices of the *children* to follow from the **root**), if any.
+    pub fn get(&self, path: &[usize]) -> Option<&CowNode<T>> {
+        path.iter().try_fold(self.root(), |node, &index| node.get(index))
+    }
+    /// Whether the **root** is shared with other clones of this [`CowTree`],
+    /// in which case modifying the [`CowTree`] will copy it.
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        Rc::strong_count(&self.root) > 1
+    }
+
+    /// Get the [`Tree`] with ownership.
+    /// The **contents** of the [`CowNode`]s that are not shared with other clones of this [`CowTree`] are moved, and the rest are cloned.
+    ///
+    /// Doesn't recurse, so [`CowTree`]s of any depth can be converted.
+    pub fn into_tree(self) -> Tree<T>
+    where T: Clone {
+        // The content and number of children of each CowNode, in Depth-First order.
+        let mut nodes = vec![];
+        let mut stack = vec![self.root];
+        while let Some(node) = stack.pop() {
+            let (content, children) = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_parts(),
+                Err(node) => (node.content.clone(), node.children.clone()),
+            };
+            nodes.push((content, children.len()));
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(children.into_iter().rev());
+        }
+
+        // Build the Nodes in reverse Depth-First order, so each one is built after its subtree.
+        // The children of a Node are always the last ones pending (in reverse order).
+        let mut pending = vec![];
+        for (content, children) in nodes.into_iter().rev() {
+            let mut children = pending.split_off(pending.len() - children);
+            children.reverse();
+            pending.push(Node::with_children(content, children));
+        }
+        pending.pop().unwrap()
+    }
+}
+impl<T> CowTree<T>
+where T: Clone {
+    /// Get the **root** to modify it, copying it first if it is shared with other clones of this [`CowTree`]
+    /// (like [`Rc::make_mut()`]), so they are not affected.
+    #[inline]
+    pub fn root_mut(&mut self) -> CowNodeMut<'_, T> {
+        CowNodeMut(Rc::make_mut(&mut self.root))
+    }
+    /// Get the [`CowNode`] at **path** to modify it, copying it and its *ancestors* first if they are shared with other clones of this [`CowTree`].
+    /// Returns [`None`] (and doesn't copy anything) if there is no [`CowNode`] at **path**.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<CowNodeMut<'_, T>> {
+        self.get(path)?;
+        let mut node = self.root_mut();
+        for &index in path {
+            node = node.into_child_mut(index).unwrap();
+        }
+        Some(node)
+    }
+}
+impl<T> Clone for CowTree<T> {
+    /// Shares the **root** with the clone, without copying any [`CowNode`]s.
+    fn clone(&self) -> Self {
+        Self {
+            root: Rc::clone(&self.root),
+        }
+    }
+}
+impl<T> Debug for CowTree<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CowTree")
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+/// A [`Node`] of a [`CowTree`], which can be shared by multiple clones of the [`CowTree`].
+/// It can only be modified through a [`CowNodeMut`].
+pub struct CowNode<T> {
+    content: T,
+    children: Vec<Rc<Self>>,
+}
+impl<T> CowNode<T> {
+    fn new(content: T, children: Vec<Rc<Self>>) -> Self {
+        Self { content, children }
+    }
+
+    /// Moves the **content** and **children** out of the [`CowNode`], which can't be done directly because it implements [`Drop`].
+    fn into_parts(self) -> (T, Vec<Rc<Self>>) {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        let children = std::mem::take(&mut this.children);
+        // The fields are only read once, and the CowNode is not dropped.
+        let content = unsafe {
+            drop(std::ptr::read(&this.children));
+            std::ptr::read(&this.content)
+        };
+        (content, children)
+    }
+
+    #[inline]
+    pub fn content(&self) -> &T {
+        &self.content
+    }
+    /// Iterate over the **children**.
+    #[inline]
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = &Self> + ExactSizeIterator + '_ {
+        self.children.iter().map(|child| &**child)
+    }
+    /// Get the *child* at **index**, if any.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Self> {
+        self.children.get(index).map(|child| &**child)
+    }
+    /// Whether **self** and **other** are the same [`CowNode`] (e.g. a *subtree* that is shared by 2 clones of a [`CowTree`]), not just equal.
+    #[inline]
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+impl<T> Clone for CowNode<T>
+where T: Clone {
+    /// Copies the **content**, but the **children** are shared.
+    fn clone(&self) -> Self {
+        Self::new(self.content.clone(), self.children.clone())
+    }
+}
+impl<T> Drop for CowNode<T> {
+    /// Drops the [`CowNode`]s that are not shared with another [`CowTree`] without recursion, so that very deep [`CowTree`]s don't overflow the stack.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(child) = stack.pop() {
+            if let Ok(mut child) = Rc::try_unwrap(child) {
+                stack.append(&mut child.children);
+            }
+        }
+    }
+}
+impl<T> Debug for CowNode<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CowNode")
+            .field("content", &self.content)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+/// Mutable access to a [`CowNode`] that is not shared with any other [`CowTree`].
+/// Obtained by calling [`CowTree::root_mut()`] or [`CowTree::get_mut()`].
+///
+/// It borrows the [`CowTree`] mutably, so it can't be kept after the [`CowTree`] is cloned.
+pub struct CowNodeMut<'a, T>(&'a mut CowNode<T>);
+impl<'a, T> CowNodeMut<'a, T>
+where T: Clone {
+    #[inline]
+    pub fn content_mut(&mut self) -> &mut T {
+        &mut self.0.content
+    }
+    /// Get the *child* at **index** to modify it, copying it first if it is shared with other clones of the [`CowTree`].
+    #[inline]
+    pub fn child_mut(&mut self, index: usize) -> Option<CowNodeMut<'_, T>> {
+        self.0.children.get_mut(index).map(|child| CowNodeMut(Rc::make_mut(child)))
+    }
+    /// Like [`Self::child_mut()`], but keeps the borrow of the [`CowTree`], so it can be used to walk down the [`CowTree`].
+    #[inline]
+    pub fn into_child_mut(self, index: usize) -> Option<CowNodeMut<'a, T>> {
+        self.0.children.get_mut(index).map(|child| CowNodeMut(Rc::make_mut(child)))
+    }
+
+    /// Inserts the **root** of **child** at **index**, sharing its [`CowNode`]s with the clones of **child**.
+    ///
+    /// # Panics
+    /// If **index** is greater than the number of *children*.
+    #[inline]
+    pub fn insert_child(&mut self, child: CowTree<T>, index: usize) {
+        self.0.children.insert(index, child.root)
+    }
+    /// Appends the **root** of **child**, sharing its [`CowNode`]s with the clones of **child**.
+    #[inline]
+    pub fn append_child(&mut self, child: CowTree<T>) {
+        self.0.children.push(child.root)
+    }
+    /// Removes the *child* at **index** (along with its *subtree*) and returns it as a [`CowTree`], if there was one.
+    #[inline]
+    pub fn remove_child(&mut self, index: usize) -> Option<CowTree<T>> {
+        (index < self.0.children.len()).then(|| CowTree {
+            root: self.0.children.remove(index),
+        })
+    }
+}
+impl<T> Deref for CowNodeMut<'_, T> {
+    type Target = CowNode<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T> Tree<T> {
+    /// Convert the [`Tree`] into a [`CowTree`], whose clones share the [`CowNode`]s until they are modified.
+    ///
+    /// The **contents** are cloned, because the [`Node`]s of the [`Tree`] may still be referenced somewhere else.
+    /// Doesn't recurse, so [`Tree`]s of any depth can be converted.
+    pub fn into_cow(self) -> CowTree<T>
+    where T: Clone {
+        // Build the CowNodes in reverse Depth-First order, so each one is built after its subtree.
+        // The children of a CowNode are always the last ones pending (in reverse order).
+        let mut pending = vec![];
+        let nodes = self.iter_dfs().collect::<Vec<_>>();
+        for node in nodes.into_iter().rev() {
+            let mut children = pending.split_off(pending.len() - node.children_ref().len());
+            children.reverse();
+            pending.push(Rc::new(CowNode::new(node.content().clone(), children)));
+        }
+        CowTree { root: pending.pop().unwrap() }
+    }
+}
@@ -0,0 +1,106 @@
+use super::*;
+use crate::TreeFormatter;
+use std::fmt::{Display, Formatter, Result};
+
+/// [`Display`]s a [`Node`] (and its subtree) with a [`TreeFormatter`], like [`crate::FormatTree`].
+/// Obtained by calling [`Node::format()`].
+///
+/// Each [`Node`] is only borrowed (or read-locked) while its **content** is written or its *children* are cloned,
+/// not during the whole traversal.
+pub struct FormatTree<'a, T>
+where T: Display {
+    formatter: &'a TreeFormatter,
+    node: Node<T>,
+}
+impl<'a, T> Display for FormatTree<'a, T>
+where T: Display {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.formatter.fmt_with(self.node.ref_clone(), |node| node.content().to_string(), Node::children, f)
+    }
+}
+
+impl<T> Display for Tree<T>
+where T: Display {
+    /// Prints the [`Tree`] with the [`Default`] [`TreeFormatter`].
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.root().format(&TreeFormatter::default()).fmt(f)
+    }
+}
+
+/// Displays a [`Node`] (and its subtree) on a single line, as its **content** followed by its comma-separated **children** in parentheses,
+/// like `a(b(c),d)`. Obtained by calling [`Node::display_compact()`] or [`Tree::display_compact()`].
+///
+/// Each [`Node`] is only borrowed (or read-locked) while its **content** or one of its *children* is read,
+/// not during the whole traversal.
+pub struct DisplayCompact<T>
+where T: Display {
+    node: Node<T>,
+}
+impl<T> Display for DisplayCompact<T>
+where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", *self.node.content())?;
+        // Each ancestor with the index of its next child to write.
+        let mut stack = vec![(self.node.ref_clone(), 0usize)];
+
+        while let Some((node, index)) = stack.last_mut() {
+            let child = node.children_ref().get(*index).map(Node::ref_clone);
+            match child {
+                Some(child) => {
+                    f.write_str(if *index == 0 { "(" } else { "," })?;
+                    *index += 1;
+                    write!(f, "{}", *child.content())?;
+                    stack.push((child, 0));
+                }
+                None => {
+                    if *index > 0 {
+                        f.write_str(")")?;
+                    }
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Node<T>
+where T: Display {
+    /// Get an object that [`Display`]s **self** and its subtree with **formatter**. See [`TreeFormatter`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{rc::Node, IndentStyle, TreeFormatter};
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    /// let formatter = TreeFormatter::new().style(IndentStyle::Ascii).indent(3);
+    /// assert_eq!(tree.root().format(&formatter).to_string(), "a\n|- b\n|  `- d\n`- c\n");
+    /// assert_eq!(tree.to_string(), "a\n├── b\n│   └── d\n└── c\n");
+    /// ```
+    #[inline]
+    pub fn format<'a>(&self, formatter: &'a TreeFormatter) -> FormatTree<'a, T> {
+        FormatTree { formatter, node: self.ref_clone() }
+    }
+    /// Get an object that [`Display`]s **self** and its subtree on a single line. See [`DisplayCompact`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).child(Node::builder('d')).build();
+    /// assert_eq!(tree.root().display_compact().to_string(), "a(b(c),d)");
+    /// ```
+    #[inline]
+    pub fn display_compact(&self) -> DisplayCompact<T> {
+        DisplayCompact { node: self.ref_clone() }
+    }
+}
+impl<T> Tree<T>
+where T: Display {
+    /// Get an object that [`Display`]s the [`Tree`] on a single line. See [`Node::display_compact()`].
+    #[inline]
+    pub fn display_compact(&self) -> DisplayCompact<T> {
+        self.root().display_compact()
+    }
+}
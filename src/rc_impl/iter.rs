@@ -27,7 +27,7 @@ impl<T> Iterator for IterBFS<T> {
         let popped = self.queue.pop_front();
         if let Some(popped) = &popped {
             // Step 3: Enqueue its children.
-            self.queue.extend(popped.children().into_vec());
+            self.queue.extend(popped.children_ref().iter().map(Node::ref_clone));
         }
         popped
     }
@@ -58,7 +58,7 @@ impl<T> Iterator for IterDFS<T> {
         if let Some(popped) = &popped {
             // Step 3: Push its children.
             // Reverse because the first child should be popped next from the stack, so it must go last in the stack.
-            self.stack.extend(popped.children().into_vec().into_iter().rev());
+            self.stack.extend(popped.children_ref().iter().rev().map(Node::ref_clone));
         }
         popped
     }
@@ -0,0 +1,320 @@
+//! The implementation shared by the `rc` and `sync` modules.
+//! Each of them includes this module with its own *reference counted pointer* and *lock* types (see `Rc` and `RwLock` in the parent module).
+mod cow;
+mod display;
+mod iter;
+mod node;
+mod ops;
+mod read_only;
+
+pub use cow::{CowNode, CowNodeMut, CowTree};
+pub use display::{DisplayCompact, FormatTree};
+pub use iter::{IterBFS, IterDFS};
+pub use node::{Node, NodeBuilder, WeakNode};
+pub use read_only::{ReadOnlyNode, ReadOnlyTree};
+use node::InnerNode;
+use crate::TreeError;
+use std::{convert::Infallible, fmt::Debug};
+use super::{borrow, borrow_mut, try_borrow, try_borrow_mut};
+use super::{Rc, WeakRc, RwLock, ReadLock, WriteLock, ContentReadLock, ContentWriteLock};
+
+/// A Tree of [`Node`]s.
+/// The root of the Tree has *no parent*.
+///
+/// ### Ownership
+/// When a [`Node`] method *returns* this type, it means it is **passing ownership** of the [`Node`]s.
+///
+/// When a [`Node`] method *asks* for this type as argument, it means it is **taking ownership** of the [`Node`]s.
+/// 
+/// Although [`Node`]s use shared ownership though [`Reference Counting`](Rc), a [`Tree`] implies more explicitly that the specific [`Node`] is owned.
+#[derive(Default)]
+pub struct Tree<T> {
+    root: Node<T>,
+}
+impl<T> Tree<T> {
+    #[inline]
+    pub fn builder(content: T) -> NodeBuilder<T> {
+        NodeBuilder::new(content)
+    }
+
+    #[inline]
+    pub fn root(&self) -> Node<T> {
+        self.root.ref_clone()
+    }
+
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> IterBFS<T> {
+        IterBFS::new(self.root())
+    }
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> IterDFS<T> {
+        IterDFS::new(self.root())
+    }
+
+    /// Converts the *content* of every [`Node`] with **f**, keeping the same structure.
+    /// **f** is called on the [`Node`]s in **Depth-First** order.
+    /// Doesn't recurse, so [`Tree`]s of any depth can be converted.
+    ///
+    /// Returns [`TreeError::SharedNode`] (and the [`Tree`] back, unchanged) if any [`Node`] of the [`Tree`] is still referenced somewhere else
+    /// (e.g. a [`Node`] obtained from [`Tree::root()`] or a [`WeakNode`] is still alive), because its *content* can't be moved out.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{rc::Node, TreeError};
+    /// let tree = Node::builder(1).child(Node::builder(2)).build();
+    /// let root = tree.root();
+    /// let (error, tree) = tree.map(|n| n.to_string()).unwrap_err();
+    /// assert_eq!(error, TreeError::SharedNode);
+    ///
+    /// drop(root);
+    /// let tree = tree.map(|n| n.to_string()).unwrap();
+    /// assert_eq!(tree, Node::builder("1".to_string()).child(Node::builder("2".to_string())).build());
+    /// ```
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Result<Tree<U>, (TreeError, Self)> {
+        self.try_map(|content| Ok::<_, Infallible>(f(content)))
+            .map_err(|error| match error {
+                MapError::Tree(error, tree) => (error, tree),
+                MapError::Content(never) => match never {},
+            })
+    }
+    /// Like [`Tree::map()`], but stops at the first error returned by **f** (dropping all the *contents*).
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Tree<U>, MapError<T, E>> {
+        if !self.root.is_unique_subtree() {
+            return Err(MapError::Tree(TreeError::SharedNode, self));
+        }
+        self.root.try_map(f).map_err(MapError::Content)
+    }
+
+    /// Removes a **descendant** of the **root** from its **parent** and returns it with ownership (aka a [`Tree`]).
+    /// Like [`Tree::detach_descendant()`](crate::Tree::detach_descendant()) of the *boxed* [`Tree`], but takes a [`Node`] handle.
+    ///
+    /// Returns [`None`] if it is not a **descendant** of the **root**, or **root** [`is_same_as`](Node::is_same_as()) **descendant**.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder(0).child(Node::builder(1)).child(Node::builder(2)).build();
+    /// let target = tree.root().children()[1].ref_clone();
+    /// let detached = tree.detach_descendant(&target).unwrap();
+    /// assert!(detached.root().is_same_as(&target));
+    /// assert!(tree.detach_descendant(&target).is_none());
+    /// ```
+    pub fn detach_descendant(&self, descendant: &Node<T>) -> Option<Self> {
+        if descendant.is_same_as(&self.root) || !descendant.path_to_root().last()?.is_same_as(&self.root) {
+            return None;
+        }
+        descendant.detach()
+    }
+    /// Like [`Tree::detach_descendant()`], but returns [`TreeError::NotADescendant`] instead of [`None`].
+    #[inline]
+    pub fn try_detach_descendant(&self, descendant: &Node<T>) -> Result<Self, TreeError> {
+        self.detach_descendant(descendant).ok_or(TreeError::NotADescendant)
+    }
+
+    /// Checks that every *child* in the [`Tree`] refers back to the [`Node`] that has it in its *children* (i.e. its **parent**),
+    /// and that the **root** has no **parent**.
+    /// Returns [`TreeError::InconsistentParent`] otherwise.
+    ///
+    /// Every mutation links the **child** and the **parent** one after the other (see the *Threads* section of the [module docs](self)),
+    /// so if a thread panics in between, or another thread reads the [`Node`]s in between, the links can be found in this state.
+    /// Use [`Tree::repair()`] to fix them.
+    pub fn validate(&self) -> Result<(), TreeError> {
+        if self.root.parent().is_some() {
+            return Err(TreeError::InconsistentParent);
+        }
+        for node in self.iter_bfs() {
+            if !node.children_ref().iter().all(|child| child.parent().is_some_and(|parent| parent.is_same_as(&node))) {
+                return Err(TreeError::InconsistentParent);
+            }
+        }
+        Ok(())
+    }
+    /// Fixes the links that [`Tree::validate()`] checks,
+    /// by making every [`Node`] the **parent** of its *children* and removing the **parent** of the **root**.
+    /// Returns the number of links that were fixed.
+    pub fn repair(&self) -> usize {
+        let mut repaired = usize::from(self.root.unlink_parent());
+        for node in self.iter_bfs() {
+            repaired += node.relink_children();
+        }
+        repaired
+    }
+
+    /// Removes every [`Node`] (along with its subtree) whose **content** does not satisfy the **predicate**.
+    /// The **root** is never removed. See [`Node::retain()`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder(0)
+    ///     .child(Node::builder(1).child(Node::builder(2)))
+    ///     .child(Node::builder(3).child(Node::builder(4)))
+    ///     .build();
+    /// let removed = tree.retain(|n| n % 3 != 0);
+    /// assert_eq!(tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(removed[0].iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec![3, 4]);
+    /// ```
+    #[inline]
+    pub fn retain(&self, predicate: impl FnMut(&T) -> bool) -> Vec<Self> {
+        self.root.retain(predicate)
+    }
+
+    /// Find the first [`Node`] (using **Breadth-First Search**) whose *content* satisfies the **predicate**.
+    ///
+    /// The *content* of each [`Node`] is only borrowed (or read-locked) while the **predicate** is called,
+    /// so the **predicate** can't hold on to it.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).build();
+    /// let found = tree.find(|c| *c == 'c').unwrap();
+    /// assert!(found.is_same_as(&tree.root().children()[0].children()[0]));
+    /// ```
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Node<T>> {
+        self.iter_bfs().find(|node| predicate(&node.content()))
+    }
+}
+
+/* Only Tree should implement IntoIter because , semantically, it makes sense to iterate through a Tree, but doesn't make sense to iterate through a Node.
+Node still has iter_bfs() and iter_dfs() in case the user wants to use it that way. */
+impl<T> IntoIterator for &Tree<T> {
+    type Item = Node<T>;
+    type IntoIter = IterBFS<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_bfs()
+    }
+}
+
+impl<T> From<NodeBuilder<T>> for Tree<T> {
+    #[inline]
+    fn from(builder: NodeBuilder<T>) -> Self {
+        builder.build()
+    }
+}
+impl<T> Clone for Tree<T>
+where T: Clone {
+    /// Clones the entire [`Tree`] by calling [`Node::clone_deep()`] on the **root**.
+    fn clone(&self) -> Self {
+        self.root.clone_deep()
+    }
+}
+impl<T> Debug for Tree<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tree")
+            .field("root", &self.root().debug_tree())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for Tree<T>
+where T: PartialEq {
+    /// Two [`Tree`]s are equal if they have the same shape and the same **content** in every position
+    /// (unlike [`Node`]s, which only compare their own **content**).
+    ///
+    /// Each [`Node`] is only borrowed (or read-locked) while it is compared.
+    fn eq(&self, other: &Self) -> bool {
+        let (mut a, mut b) = (self.iter_dfs(), other.iter_dfs());
+        loop {
+            match (a.next(), b.next()) {
+                (Some(a), Some(b)) => {
+                    if a.children_ref().len() != b.children_ref().len() || *a.content() != *b.content() {
+                        return false;
+                    }
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+impl<T> Eq for Tree<T>
+where T: Eq {}
+impl<T> PartialOrd for Tree<T>
+where T: PartialOrd {
+    /// See [`Tree::cmp()`](Ord::cmp()).
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.cmp_contents(other, |a, b| a.partial_cmp(b))? {
+            std::cmp::Ordering::Equal => Some(self.shape().cmp(other.shape())),
+            ordering => Some(ordering),
+        }
+    }
+}
+impl<T> Ord for Tree<T>
+where T: Ord {
+    /// Compares the **contents** of the [`Tree`]s in **Depth-First** order, lexicographically (like [`Vec`]s).
+    /// If they are all equal, the [`Tree`] whose [`Node`]s have fewer *children* first (in the same order) is the lesser one,
+    /// so that only equal [`Tree`]s are [`Equal`](std::cmp::Ordering::Equal).
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.cmp_contents(other, |a, b| Some(a.cmp(b)))
+            .unwrap()
+            .then_with(|| self.shape().cmp(other.shape()))
+    }
+}
+impl<T> Tree<T> {
+    /// Compares the **contents** of the [`Tree`]s lexicographically in **Depth-First** order with **cmp**,
+    /// only borrowing (or read-locking) the two [`Node`]s that are being compared.
+    fn cmp_contents(&self, other: &Self, cmp: impl Fn(&T, &T) -> Option<std::cmp::Ordering>) -> Option<std::cmp::Ordering> {
+        let (mut a, mut b) = (self.iter_dfs(), other.iter_dfs());
+        loop {
+            match (a.next(), b.next()) {
+                (Some(a), Some(b)) => match cmp(&a.content(), &b.content())? {
+                    std::cmp::Ordering::Equal => {}
+                    ordering => return Some(ordering),
+                },
+                (None, None) => return Some(std::cmp::Ordering::Equal),
+                (None, Some(_)) => return Some(std::cmp::Ordering::Less),
+                (Some(_), None) => return Some(std::cmp::Ordering::Greater),
+            }
+        }
+    }
+    /// The number of *children* of each [`Node`] in **Depth-First** order, which describes the shape of the [`Tree`].
+    fn shape(&self) -> impl Iterator<Item = usize> {
+        self.iter_dfs().map(|n| n.children_ref().len())
+    }
+}
+impl<T> std::hash::Hash for Tree<T>
+where T: std::hash::Hash {
+    /// Hashes the **content** and the number of *children* of every [`Node`] in **Depth-First** order,
+    /// which is consistent with the [`PartialEq`] implementation.
+    ///
+    /// Because [`Node`]s can be modified through a shared reference, a [`Tree`] must not be modified while it is the key of a map or set.
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for node in self.iter_dfs() {
+            node.content().hash(state);
+            node.children_ref().len().hash(state);
+        }
+    }
+}
+
+/// Obtained by calling [`Node::debug_tree()`].
+pub struct DebugTree<'a, T>
+where T: Debug {
+    root: ReadLock<'a, InnerNode<T>>,
+}
+impl<'a, T> Debug for DebugTree<'a, T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("content", &self.root.content)
+            .field("children", &self.root
+                .children
+                .iter()
+                .map(|c| c.debug_tree())
+                .collect::<Box<_>>()
+            )
+            .finish()
+    }
+}
+
+/// Returned by [`Tree::try_map()`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum MapError<T, E> {
+    /// The [`Tree`] couldn't be taken apart (see [`Tree::map()`]), so it is returned unchanged, and **f** was never called.
+    Tree(TreeError, Tree<T>),
+    /// **f** returned an error, so the rest of the *contents* were dropped.
+    Content(E),
+}
@@ -0,0 +1,806 @@
+use super::*;
+use std::{
+    pin::Pin,
+    marker::PhantomPinned,
+};
+type Weak<T> = WeakRc<RwLock<T>>;
+
+/// Helper struct to build a [`Tree`] of [`Node`]s.
+///
+/// ### Examples
+/// Can be used as a [Builder Pattern](https://rust-unofficial.github.io/patterns/patterns/creational/builder.html),
+/// or something similar, but by assigning the fields.
+///
+/// ```
+/// # use tree_struct::{Node, NodeBuilder};
+/// let tree1 = Node::builder("parent")
+///     .child(Node::builder("child a"))
+///     .child(Node::builder("child b")
+///         .child(Node::builder("child c")))
+///     .build();
+///
+/// // Or:
+///
+/// let tree2 = NodeBuilder {
+///     content: "parent",
+///     children: vec![
+///         NodeBuilder {
+///             content: "child a",
+///             children: vec![]
+///         },
+///         NodeBuilder {
+///             content: "child b",
+///             children: vec![
+///                 NodeBuilder {
+///                     content: "child c",
+///                     children: vec![]
+///                 }
+///             ]
+///         },
+///     ],
+/// }.build();
+///
+/// assert_eq!(tree1, tree2);
+/// ```
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NodeBuilder<T> {
+    pub content: T,
+    pub children: Vec<Self>,
+}
+impl<T> NodeBuilder<T> {
+    /// New [`NodeBuilder`] using [Builder Pattern](https://rust-unofficial.github.io/patterns/patterns/creational/builder.html).
+    pub fn new(content: T) -> Self {
+        NodeBuilder {
+            content,
+            children: vec![],
+        }
+    }
+    /// New [`NodeBuilder`] with space for at least **capacity** *children* without reallocating.
+    /// The built [`Node`] keeps the capacity, so appending children to it later doesn't reallocate either.
+    pub fn with_children_capacity(content: T, capacity: usize) -> Self {
+        NodeBuilder {
+            content,
+            children: Vec::with_capacity(capacity),
+        }
+    }
+    pub fn child(mut self, child: Self) -> Self {
+        self.children.push(child);
+        self
+    }
+    /// Adds all the **children** at once, in order. Also see [`Self::child()`].
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder(0).children((1..=3).map(Node::builder)).child_content(4).build();
+    /// assert_eq!(tree.root().children().len(), 4);
+    /// ```
+    pub fn children(mut self, children: impl IntoIterator<Item = Self>) -> Self {
+        self.children.extend(children);
+        self
+    }
+    /// Adds a *child* with **content** and no *children* of its own. Shortcut for `.child(NodeBuilder::new(content))`.
+    #[inline]
+    pub fn child_content(self, content: T) -> Self {
+        self.child(Self::new(content))
+    }
+
+    /// Create a new [`Tree`] from nodes with **children** and **content**.
+    /// The children will be made into [`Pin`]ned [`Node`]s with the proper **parent**.
+    ///
+    /// Doesn't recurse, so [`Tree`]s of any depth can be built.
+    pub fn build(self) -> Tree<T> {
+        // Do not pin at first to be able to `Rc::downgrade()` freely.
+        let root = Rc::new(RwLock::new(InnerNode::new(self.content)));
+        // The Nodes whose children haven't been built yet.
+        // These Rcs are only used to lock the Nodes, which doesn't move them.
+        let mut stack = vec![(Rc::clone(&root), self.children)];
+
+        while let Some((parent, children)) = stack.pop() {
+            let mut built = Vec::with_capacity(children.capacity());
+
+            for builder in children {
+                let child = Rc::new(RwLock::new(InnerNode::new(builder.content)));
+                // When using RwLock: Don't need to unlock for other threads because the Node hasn't been released yet.
+                borrow_mut(&child).parent = Some(Rc::downgrade(&parent));
+                stack.push((Rc::clone(&child), builder.children));
+                // Can be pinned here because the other unpinned Rc is never moved out of
+                built.push(Node(unsafe { Pin::new_unchecked(child) }));
+            }
+
+            borrow_mut(&parent).children = built;
+        }
+
+        // Can be pinned here because no other unpinned Rcs exist
+        Tree { root: Node(unsafe { Pin::new_unchecked(root) }) }
+    }
+}
+
+#[derive(Default)]
+pub(in super::super) struct InnerNode<T> {
+    pub content: T,
+    parent: Option<Weak<Self>>,
+    pub(super) children: Vec<Node<T>>,
+    _pin: PhantomPinned,
+}
+impl<T> InnerNode<T> {
+    fn new(content: T) -> Self {
+        Self {
+            content,
+            parent: None,
+            children: vec![],
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// Moves the **content** and **children** out of the [`InnerNode`], which can't be done directly because it implements [`Drop`].
+    fn into_parts(self) -> (T, Vec<Node<T>>) {
+        let mut this = std::mem::ManuallyDrop::new(self);
+        let children = std::mem::take(&mut this.children);
+        // The fields are only read once, and the InnerNode is not dropped.
+        let content = unsafe {
+            drop(std::ptr::read(&this.parent));
+            drop(std::ptr::read(&this.children));
+            std::ptr::read(&this.content)
+        };
+        (content, children)
+    }
+
+    // /// A [`Node`] is a **descendant** of another [`Node`] if:
+    // /// 1. The two [`Node`]s are not the same ([`std::ptr::eq()`]).
+    // /// 2. Looking up the [`Tree`] from `other`, `self` is found to be one of `other`'s ancestors. (Not recursive).
+    // fn is_descendant(&self, other: Ref<Self>) -> bool {
+    //     if ptr_eq(self, &*other) {
+    //         return false;
+    //     }
+    //
+    //     let mut ancestor = other.parent();
+    //
+    //     while let Some(node) = ancestor {
+    //         if ptr_eq(self, node.as_ptr()) {
+    //             return true;
+    //         }
+    //         ancestor = node.borrow().parent();
+    //     }
+    //
+    //     false
+    // }
+}
+impl<T> Drop for InnerNode<T> {
+    /// Drops the *subtree* without recursion, so that very deep [`Tree`]s don't overflow the stack.
+    /// [`Node`]s that are still referenced somewhere else are not dropped, and keep their **children**.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(node) = stack.pop() {
+            // Casting Pin<P> to P is ok because the Node is only moved out of the Rc if it is about to be destroyed.
+            if let Ok(inner) = Rc::try_unwrap(unsafe { Pin::into_inner_unchecked(node.0) }) {
+                stack.append(&mut inner.into_inner().into_parts().1);
+            }
+        }
+    }
+}
+impl<T> Debug for InnerNode<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Node")
+            .field("content", &self.content)
+            .field("children", &self
+                .children
+                .iter()
+                .map(|c| ReadLock::map(c.borrow(), |c| &c.content))
+                .collect::<Box<_>>()
+            )
+            .finish()
+    }
+}
+impl<T> PartialEq for InnerNode<T>
+where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.content == other.content
+    }
+}
+impl<T> Eq for InnerNode<T>
+where T: Eq {}
+
+
+/// The outward-facing Node is the node struct wrapped in a [`cell`](std::cell::RefCell) (or [`RwLock`]) and `reference counted pointer` ([`Rc`](std::rc::Rc) or [`Arc`](std::sync::Arc)).
+/// 
+/// A [`Node`] has 1 [`parent`](Self::parent()) and multiple [`children`](Self::children()).
+/// It also stores [`content`](Self::content()) of type **`T`**.
+pub struct Node<T>(Pin<Rc<RwLock<InnerNode<T>>>>);
+impl<T> Node<T> {
+    #[inline]
+    fn borrow(&self) -> ReadLock<InnerNode<T>> {
+        borrow(&self.0)
+    }
+    fn borrow_mut(&self) -> Pin<WriteLock<InnerNode<T>>> {
+        unsafe { Pin::new_unchecked(borrow_mut(&self.0)) }
+    }
+
+    /// Must not be used to move the [`InnerNode`].
+    pub(in super::super) fn rc(&self) -> &Rc<RwLock<InnerNode<T>>> {
+        // Casting Pin<P> to P is ok as long as nothing is moved later
+        unsafe { &*(&self.0 as *const _ as *const Rc<_>) }
+    }
+    /// Must be immediately made into [`Self`] when upgraded.
+    unsafe fn weak(&self) -> Weak<InnerNode<T>> {
+        Rc::downgrade(self.rc())
+    }
+
+    /// Whether no [`Node`] of the *subtree* is referenced outside of it (by a [`Node`] or [`WeakNode`] handle),
+    /// assuming that **self** is only referenced by its owner (e.g. the [`Tree`]).
+    pub(super) fn is_unique_subtree(&self) -> bool {
+        let mut stack = vec![self.ref_clone()];
+
+        while let Some(node) = stack.pop() {
+            let inner = node.borrow();
+            // Only referenced by its owner (its parent or the Tree) and by this stack, and weakly by its children.
+            if Rc::strong_count(node.rc()) != 2 || Rc::weak_count(node.rc()) != inner.children.len() {
+                return false;
+            }
+            stack.extend(inner.children.iter().map(Self::ref_clone));
+        }
+
+        true
+    }
+    /// Takes the **content** out of each [`Node`] in the *subtree* and converts it with **f**, in **Depth-First** order.
+    /// None of the [`Node`]s can be referenced anywhere else (see [`Self::is_unique_subtree()`]).
+    ///
+    /// Doesn't recurse, so [`Node`]s of any depth can be converted.
+    pub(super) fn try_map<U, E>(self, mut f: impl FnMut(T) -> Result<U, E>) -> Result<Tree<U>, E> {
+        let mut root = None;
+        // Each Node is paired with the converted Node that will be the parent of its conversion.
+        // These Rcs are only used to lock the Nodes, which doesn't move them.
+        let mut stack = vec![(self, None::<Rc<RwLock<InnerNode<U>>>>)];
+
+        while let Some((node, parent)) = stack.pop() {
+            // Casting Pin<P> to P is ok because the Node will be destroyed.
+            let inner = Rc::try_unwrap(unsafe { Pin::into_inner_unchecked(node.0) })
+                .unwrap_or_else(|_| unreachable!("The Node is only referenced by its parent"));
+            let (content, children) = inner.into_inner().into_parts();
+
+            let mapped = Rc::new(RwLock::new(InnerNode::new(f(content)?)));
+            // Reverse because the first child should be popped (and converted) next from the stack.
+            stack.extend(children.into_iter().rev().map(|child| (child, Some(Rc::clone(&mapped)))));
+            match parent {
+                Some(parent) => {
+                    // When using RwLock: Don't need to unlock for other threads because the Node hasn't been released yet.
+                    borrow_mut(&mapped).parent = Some(Rc::downgrade(&parent));
+                    // Can be pinned here because the other unpinned Rcs are never moved out of
+                    borrow_mut(&parent).children.push(Node(unsafe { Pin::new_unchecked(mapped) }));
+                },
+                None => root = Some(mapped),
+            }
+        }
+
+        // Can be pinned here because no other unpinned Rcs exist
+        Ok(Tree { root: Node(unsafe { Pin::new_unchecked(root.unwrap()) }) })
+    }
+
+    /// Check through all children of parent until `self` is found.
+    fn find_self_next<'a>(&'a self, mut iter: impl Iterator<Item = &'a Self>) -> Option<Self> {
+        iter.find(|sib| self.is_same_as(sib));
+        iter.next().map(Node::ref_clone)
+    }
+
+    // vvv Public Functions vvv
+
+    #[inline]
+    pub fn builder(content: T) -> NodeBuilder<T> {
+        NodeBuilder::new(content)
+    }
+
+    /// Get a [`WeakNode`] that refers to **self** without keeping it alive.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).build();
+    /// let weak = tree.root().children()[0].downgrade();
+    /// assert_eq!(*weak.upgrade().unwrap().content(), 'b');
+    ///
+    /// drop(tree);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn downgrade(&self) -> WeakNode<T> {
+        WeakNode(unsafe { self.weak() })
+    }
+
+    /// The number of [`Node`]s (including **self**) that refer to the same [`Node`] as **self**,
+    /// including the one held by its **parent** (or [`Tree`]). Useful to find out what is keeping a [`Node`] alive.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).build();
+    /// let root = tree.root();
+    /// assert_eq!(root.strong_count(), 2);
+    /// // Each child refers to its parent with a weak reference.
+    /// assert_eq!(root.weak_count(), 1);
+    /// ```
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        Rc::strong_count(self.rc())
+    }
+    /// The number of [`WeakNode`]s that refer to **self**, including the **parent** references of **self**'s *children*.
+    #[inline]
+    pub fn weak_count(&self) -> usize {
+        Rc::weak_count(self.rc())
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        self.borrow()
+            .parent
+            .as_ref()
+            .and_then(|p| unsafe {
+                Some(Self(Pin::new_unchecked(Weak::upgrade(p)?)))
+            })
+    }
+    /// Allocates a *slice* of all of [`Node`]'s children, increasing all of their *reference counter*.
+    pub fn children(&self) -> Box<[Self]> {
+        self.borrow()
+            .children
+            .iter()
+            .map(|c| c.ref_clone())
+            .collect()
+    }
+    /// Get the *child* at **index**, increasing only its *reference counter*.
+    /// Unlike `self.children()[index]`, this doesn't allocate a slice of all the *children*.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).child(Node::builder('c')).build();
+    /// assert_eq!(*tree.root().get(1).unwrap().content(), 'c');
+    /// assert_eq!(*tree.root().first_child().unwrap().content(), 'b');
+    /// assert!(tree.root().get(2).is_none());
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Self> {
+        self.borrow().children.get(index).map(Self::ref_clone)
+    }
+    /// Get the first *child*, if any. See [`Self::get()`].
+    #[inline]
+    pub fn first_child(&self) -> Option<Self> {
+        self.borrow().children.first().map(Self::ref_clone)
+    }
+    /// Get the last *child*, if any. See [`Self::get()`].
+    #[inline]
+    pub fn last_child(&self) -> Option<Self> {
+        self.borrow().children.last().map(Self::ref_clone)
+    }
+    /// Borrows (or read-locks) **self** and gets its *children* without cloning them, so their *reference counters* don't change.
+    ///
+    /// **self**'s *children* can't be modified while the returned guard is alive.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).child(Node::builder('c')).build();
+    /// let root = tree.root();
+    /// let children = root.children_ref();
+    /// assert_eq!(children.iter().map(|n| *n.content()).collect::<String>(), "bc");
+    /// ```
+    pub fn children_ref(&self) -> ContentReadLock<[Self]> {
+        ReadLock::map(self.borrow(), |n| n.children.as_slice())
+    }
+    /// Calls **f** with each of **self**'s *children*, in order, without cloning them. See [`Self::children_ref()`].
+    ///
+    /// **self** is borrowed (or read-locked) during the whole iteration, so **f** can't modify **self**'s *children*.
+    pub fn for_each_child(&self, f: impl FnMut(&Self)) {
+        self.children_ref().iter().for_each(f)
+    }
+    pub fn content(&self) -> ContentReadLock<'_, T> {
+        ReadLock::map(self.borrow(), |n| &n.content)
+    }
+    pub fn content_mut(&self) -> ContentWriteLock<'_, T> {
+        WriteLock::map(unsafe { Pin::into_inner_unchecked(self.borrow_mut()) }, |n| &mut n.content)
+    }
+    /// Like [`Self::content()`], but returns [`None`] instead of blocking (or panicking, with [`RefCell`](std::cell::RefCell))
+    /// if **self** is currently locked for writing.
+    pub fn try_content(&self) -> Option<ContentReadLock<'_, T>> {
+        try_borrow(&self.0).map(|n| ReadLock::map(n, |n| &n.content))
+    }
+    /// Like [`Self::content_mut()`], but returns [`None`] instead of blocking (or panicking, with [`RefCell`](std::cell::RefCell))
+    /// if **self** is currently locked.
+    pub fn try_content_mut(&self) -> Option<ContentWriteLock<'_, T>> {
+        try_borrow_mut(&self.0).map(|n| WriteLock::map(n, |n| &mut n.content))
+    }
+
+    /// Swaps the [`content`](Self::content()) of **self** and **other**, without changing their positions in the [`Tree`].
+    ///
+    /// Both [`Node`]s are locked (always in the same order), so unlike swapping through two [`content_mut()`](Self::content_mut()) guards,
+    /// this can't deadlock when other threads swap the same [`Node`]s.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::rc::Node;
+    /// let tree = Node::builder('a').child(Node::builder('b')).build();
+    /// tree.root().swap_content(&tree.root().children()[0]);
+    /// assert_eq!(*tree.root().content(), 'b');
+    /// assert_eq!(*tree.root().children()[0].content(), 'a');
+    /// ```
+    pub fn swap_content(&self, other: &Self) {
+        if self.is_same_as(other) {
+            return;
+        }
+
+        let (first, second) = if (&*self.0 as *const RwLock<_>) < (&*other.0 as *const RwLock<_>) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut first = first.borrow_mut();
+        let mut second = second.borrow_mut();
+        // Moving the content out of the Node is fine because only the InnerNode is pinned.
+        unsafe {
+            std::mem::swap(
+                &mut first.as_mut().get_unchecked_mut().content,
+                &mut second.as_mut().get_unchecked_mut().content,
+            )
+        }
+    }
+
+    /// Collects `self` and all of its ancestors, starting from `self` and ending at the **root** of the [`Tree`].
+    pub fn path_to_root(&self) -> Vec<Self> {
+        let mut path = vec![self.ref_clone()];
+        while let Some(parent) = path.last().unwrap().parent() {
+            path.push(parent);
+        }
+        path
+    }
+
+    /// Returns the [`Node`] immediately following this one in the **parent**'s [`children`](Node::children).
+    /// Otherwise returns [`None`] if `self` has no **parent**, or if it is the *last* child of the **parent**.
+    pub fn next_sibling(&self) -> Option<Self> {
+        self.find_self_next(self.parent()?.children_ref().iter())
+    }
+    /// Returns the [`Node`] immediately preceeding this one in the **parent**'s [`children`](Node::children).
+    /// Otherwise returns [`None`] if `self` has no **parent**, or if it is the *first* child of the **parent**.
+    pub fn prev_sibling(&self) -> Option<Self> {
+        self.find_self_next(self.parent()?.children_ref().iter().rev())
+    }
+
+    /// Pushes the **child** to the end of **self**'s *children*.
+    /// **child** is required to be a **root** (i.e. have no *parent*), and [`Tree`] guarantees that.
+    /// 
+    /// Also see [`Self::insert_child()`].
+    ///
+    /// # Panics
+    /// If **self** is in **child**'s [`Tree`] (e.g. a [`Node`] obtained from **child** before it was passed),
+    /// because it would create a *cycle*. See [`Self::try_append_child()`].
+    pub fn append_child(&self, child: Tree<T>) {
+        assert!(!self.is_in(&child), "appending the child would create a cycle");
+        unsafe {
+            child.root.borrow_mut().as_mut().get_unchecked_mut().parent = Some(self.weak());
+            self.borrow_mut().as_mut().get_unchecked_mut().children.push(child.root)
+        }
+    }
+    /// Like [`Self::append_child()`], but returns [`TreeError::WouldCreateCycle`] (and the **child** back) instead of panicking.
+    pub fn try_append_child(&self, child: Tree<T>) -> Result<(), (TreeError, Tree<T>)> {
+        if self.is_in(&child) {
+            return Err((TreeError::WouldCreateCycle, child));
+        }
+        self.append_child(child);
+        Ok(())
+    }
+    /// Inserts the **child** to **self**'s *children* at some index.
+    /// **child** is required to be a **root** (i.e. have no *parent*), and [`Tree`] guarantees that.
+    /// 
+    /// Also see [`Self::append_child()`].
+    ///
+    /// # Panics
+    /// If **index** is greater than the number of *children*,
+    /// or if **self** is in **child**'s [`Tree`], like [`Self::append_child()`].
+    /// See [`Self::try_insert_child()`].
+    pub fn insert_child(&self, child: Tree<T>, index: usize) {
+        assert!(!self.is_in(&child), "inserting the child would create a cycle");
+        let len = self.borrow().children.len();
+        assert!(index <= len, "child index {index} is out of bounds for {len} children");
+        unsafe {
+            child.root.borrow_mut().as_mut().get_unchecked_mut().parent = Some(self.weak());
+            self.borrow_mut().as_mut().get_unchecked_mut().children.insert(index, child.root)
+        }
+    }
+    /// Like [`Self::insert_child()`], but returns [`TreeError::IndexOutOfBounds`] if **index** is greater than the number of *children*,
+    /// or [`TreeError::WouldCreateCycle`] if **self** is in **child**'s [`Tree`] (and the **child** back), instead of panicking.
+    pub fn try_insert_child(&self, child: Tree<T>, index: usize) -> Result<(), (TreeError, Tree<T>)> {
+        if self.is_in(&child) {
+            return Err((TreeError::WouldCreateCycle, child));
+        }
+        let len = self.borrow().children.len();
+        if index > len {
+            return Err((TreeError::IndexOutOfBounds { index, len }, child));
+        }
+        self.insert_child(child, index);
+        Ok(())
+    }
+    /// Makes **self** the **parent** of all of its *children*, and returns how many of them had a different **parent**.
+    /// See [`Tree::repair()`].
+    pub(super) fn relink_children(&self) -> usize {
+        let mut relinked = 0;
+        for child in self.children_ref().iter() {
+            if !child.parent().is_some_and(|parent| parent.is_same_as(self)) {
+                unsafe { child.borrow_mut().as_mut().get_unchecked_mut() }.parent = Some(unsafe { self.weak() });
+                relinked += 1;
+            }
+        }
+        relinked
+    }
+    /// Removes the **parent** of **self**, without removing **self** from the **parent**'s *children*. See [`Tree::repair()`].
+    pub(super) fn unlink_parent(&self) -> bool {
+        unsafe { self.borrow_mut().as_mut().get_unchecked_mut() }.parent.take().is_some()
+    }
+    /// Whether **self** is one of the [`Node`]s of **tree** (by walking up to the **root** from **self**).
+    fn is_in(&self, tree: &Tree<T>) -> bool {
+        let mut ancestor = Some(self.ref_clone());
+        while let Some(node) = ancestor {
+            if node.is_same_as(&tree.root) {
+                return true;
+            }
+            ancestor = node.parent();
+        }
+        false
+    }
+
+    /// Reserves space for at least **additional** more *children*, so that many can be appended without reallocating.
+    pub fn reserve_children(&self, additional: usize) {
+        unsafe { self.borrow_mut().as_mut().get_unchecked_mut() }.children.reserve(additional)
+    }
+
+    /// Swaps two of **self**'s *children* in place.
+    /// The *parent* of the **children** doesn't change, so nothing else is modified.
+    ///
+    /// # Panics
+    /// If **a** or **b** are out of bounds.
+    pub fn swap_children(&self, a: usize, b: usize) {
+        unsafe { self.borrow_mut().as_mut().get_unchecked_mut() }.children.swap(a, b)
+    }
+
+    /// Sorts **self**'s *children* with a comparator function on their **content**.
+    /// The sort is *stable*, and the *children*'s subtrees are not sorted.
+    ///
+    /// **self** is locked for writing only once, during the whole sort.
+    pub fn sort_children_by(&self, mut compare: impl FnMut(&T, &T) -> std::cmp::Ordering) {
+        unsafe { self.borrow_mut().as_mut().get_unchecked_mut() }
+            .children
+            .sort_by(|a, b| compare(&a.content(), &b.content()))
+    }
+
+    /// Removes all of **self**'s *children* and returns them as [`Tree`]s (with no *parent*), in the same order.
+    pub fn clear_children(&self) -> Vec<Tree<T>> {
+        let children = std::mem::take(&mut unsafe { self.borrow_mut().as_mut().get_unchecked_mut() }.children);
+
+        children.into_iter()
+            .map(|root| {
+                unsafe { root.borrow_mut().as_mut().get_unchecked_mut().parent = None };
+                Tree { root }
+            })
+            .collect()
+    }
+
+    /// Removes every **descendant** (along with its subtree) whose **content** does not satisfy the **predicate**,
+    /// and returns the removed subtrees as [`Tree`]s with no *parent*.
+    ///
+    /// The **predicate** is not called for `self`, or for the **descendants** of a [`Node`] that was removed.
+    /// All the **children** of a [`Node`] are checked (and returned) before moving on to their own **children**.
+    ///
+    /// Each [`Node`] is locked for writing once, while the **predicate** is called for its **children**,
+    /// so the **predicate** must not lock that [`Node`] (e.g. through [`Self::parent()`]).
+    pub fn retain(&self, mut predicate: impl FnMut(&T) -> bool) -> Vec<Tree<T>> {
+        let mut removed = vec![];
+        let mut stack = vec![self.ref_clone()];
+
+        while let Some(node) = stack.pop() {
+            let mut inner = node.borrow_mut();
+            let inner = unsafe { inner.as_mut().get_unchecked_mut() };
+            let children = std::mem::take(&mut inner.children);
+            let first_kept = stack.len();
+
+            // Split the children into the kept and removed ones in a single pass, keeping their order.
+            for child in children {
+                if predicate(&child.content()) {
+                    stack.push(child.ref_clone());
+                    inner.children.push(child);
+                } else {
+                    unsafe { child.borrow_mut().as_mut().get_unchecked_mut().parent = None };
+                    removed.push(Tree { root: child });
+                }
+            }
+            // The first kept child should be popped next from the stack.
+            stack[first_kept..].reverse();
+        }
+
+        removed
+    }
+
+    /// Removes **this** [`Node`] from its **parent** and returns the *detached [`Node`]* with ownership (aka a [`Tree`]).
+    /// If `self` has no **parent**, either because it is a *root* or it is not part of a [`Tree`], this will return [`None`].
+    pub fn detach(&self) -> Option<Tree<T>> {
+        let parent = self.parent()?;
+
+        // The index is found and the child removed under the same lock,
+        // so another thread can't change the parent's children in between.
+        let mut parent = parent.borrow_mut();
+        let children = &mut unsafe { parent.as_mut().get_unchecked_mut() }.children;
+        // With arc, another thread could have detached self after its parent was read.
+        let index = children.iter().position(|child| self.is_same_as(child))?;
+        let root = children.remove(index);
+        drop(parent);
+
+        unsafe { root.borrow_mut().as_mut().get_unchecked_mut().parent = None };
+        Some(Tree { root })
+    }
+    /// Removes the child at **index** from **self**'s *children* and returns it as a [`Tree`] (with no *parent*).
+    /// Returns [`None`] if **index** is out of bounds.
+    ///
+    /// Unlike [`Self::detach()`], this doesn't need to search for the child in **self**'s *children*.
+    pub fn remove_child(&self, index: usize) -> Option<Tree<T>> {
+        let mut this = self.borrow_mut();
+        let children = &mut unsafe { this.as_mut().get_unchecked_mut() }.children;
+        if index >= children.len() {
+            return None;
+        }
+
+        let root = children.remove(index);
+        drop(this);
+        unsafe { root.borrow_mut().as_mut().get_unchecked_mut().parent = None };
+        Some(Tree { root })
+    }
+
+    #[inline]
+    /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> IterBFS<T> {
+        IterBFS::new(self.ref_clone())
+    }
+    #[inline]
+    /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> IterDFS<T> {
+        IterDFS::new(self.ref_clone())
+    }
+
+    /// Clones the [`Rc`] and increments the internal reference counter of this [`Node`].
+    pub fn ref_clone(&self) -> Self {
+        Self(Pin::clone(&self.0))
+    }
+
+    #[inline]
+    /// Whether two [`Node`]s are the same (that is, they reference the same object).
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        unsafe {
+            Rc::<RwLock<InnerNode<T>>>::ptr_eq(
+                // Casting Pin<P> to P is ok as long as nothing is moved later
+                &*(&self.0 as *const _ as *const Rc<_>),
+                &*(&other.0 as *const _ as *const Rc<_>)
+            )
+        }
+    }
+}
+impl<T> Node<T>
+where T: Clone {
+    /// Copies the [`Node`]'s [`content`](Node::content) and the whole subtree of its [`children`](Node::children).
+    /// The resulting cloned [`Node`] will have no **parent**.
+    ///
+    /// For a method that clones the [`Node`] but *not* its subtree, see [`Node::clone`].
+    ///
+    /// Doesn't recurse, so [`Node`]s of any depth can be cloned.
+    pub fn clone_deep(&self) -> Tree<T> {
+        // Do not pin at first to be able to `Rc::downgrade()` freely.
+        let root = Rc::new(RwLock::new(InnerNode::new(self.borrow().content.clone())));
+        // Each Node is paired with its clone, whose children haven't been cloned yet.
+        // These Rcs are only used to lock the Nodes, which doesn't move them.
+        let mut stack = vec![(self.ref_clone(), Rc::clone(&root))];
+
+        while let Some((node, cloned)) = stack.pop() {
+            let children = node.children();
+            let mut cloned_children = Vec::with_capacity(children.len());
+
+            for child in children.iter() {
+                let cloned_child = Rc::new(RwLock::new(InnerNode::new(child.borrow().content.clone())));
+                // When using RwLock: Don't need to unlock for other threads because the Node hasn't been released yet.
+                borrow_mut(&cloned_child).parent = Some(Rc::downgrade(&cloned));
+                stack.push((child.ref_clone(), Rc::clone(&cloned_child)));
+                // Can be pinned here because the other unpinned Rc is never moved out of
+                cloned_children.push(Self(unsafe { Pin::new_unchecked(cloned_child) }));
+            }
+
+            borrow_mut(&cloned).children = cloned_children;
+        }
+
+        // Can be pinned here because no other unpinned Rcs exist
+        Tree { root: Self(unsafe { Pin::new_unchecked(root) }) }
+    }
+    /// Makes a [`Tree`] with a new **root** with **content**, whose *children* are the **roots** of **children** (in order).
+    /// Used to build copies bottom-up: unlike [`Self::append_child()`], there is no *cycle* check,
+    /// because the new **root** can't be in any of the **children**'s [`Tree`]s.
+    pub(in super::super) fn with_children(content: T, children: Vec<Tree<T>>) -> Tree<T> {
+        // Do not pin at first to be able to `Rc::downgrade()` freely.
+        let root = Rc::new(RwLock::new(InnerNode::new(content)));
+        let children = children.into_iter()
+            .map(|child| {
+                unsafe { child.root.borrow_mut().as_mut().get_unchecked_mut() }.parent = Some(Rc::downgrade(&root));
+                child.root
+            })
+            .collect();
+        borrow_mut(&root).children = children;
+
+        // Can be pinned here because no other unpinned Rcs exist
+        Tree { root: Self(unsafe { Pin::new_unchecked(root) }) }
+    }
+}
+impl<T> Node<T>
+where T: Debug {
+    /// [`Debug`] the entire subtree (`self` and its **children**).
+    #[inline]
+    pub fn debug_tree(&self) -> DebugTree<T> {
+        DebugTree { root: self.borrow() }
+    }
+}
+
+impl<T> Default for Node<T>
+where T: Default {
+    fn default() -> Self {
+        Self(Rc::pin(RwLock::new(InnerNode::default())))
+    }
+}
+impl<T> Clone for Node<T>
+where T: Clone {
+    /// Copies the [`Node`]'s [`content`](Node::content), but not its [`children`](Node::children).
+    /// The resulting cloned [`Node`] will have no **parent** or **children**.
+    ///
+    /// For a method that clones the [`Node`] *and* its subtree, see [`Node::clone_deep`].
+    fn clone(&self) -> Self {
+        Self(Rc::pin(RwLock::new(InnerNode::new(self.borrow().content.clone()))))
+    }
+}
+impl<T> PartialEq for Node<T>
+where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.borrow().eq(&*other.borrow())
+    }
+}
+impl<T> Eq for Node<T>
+where T: Eq {}
+impl<T> Debug for Node<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.borrow(), f)
+    }
+}
+
+/// A *non-owning* reference to a [`Node`], obtained by calling [`Node::downgrade()`].
+///
+/// Unlike a [`Node`], holding a [`WeakNode`] doesn't keep the [`Node`] (and its subtree) alive,
+/// so it can be used by observers that should not prevent a [`Tree`] from being dropped.
+pub struct WeakNode<T>(Weak<InnerNode<T>>);
+impl<T> WeakNode<T> {
+    /// A [`WeakNode`] that doesn't refer to any [`Node`], so [`Self::upgrade()`] always returns [`None`].
+    #[inline]
+    pub fn new() -> Self {
+        Self(Weak::new())
+    }
+    /// Get the [`Node`] back, or [`None`] if it was already dropped.
+    pub fn upgrade(&self) -> Option<Node<T>> {
+        // The Rc was pinned when the Node was created.
+        self.0.upgrade().map(|rc| Node(unsafe { Pin::new_unchecked(rc) }))
+    }
+}
+impl<T> Default for WeakNode<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> Clone for WeakNode<T> {
+    /// Refers to the same [`Node`], without increasing its *strong reference count*.
+    fn clone(&self) -> Self {
+        Self(Weak::clone(&self.0))
+    }
+}
+impl<T> Debug for WeakNode<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(WeakNode)")
+    }
+}
@@ -0,0 +1,41 @@
+use super::*;
+use crate::{NodeOps, TreeOps};
+
+impl<T> NodeOps<T> for Node<T> {
+    type Content<'a> = ContentReadLock<'a, T>
+    where Self: 'a;
+
+    #[inline]
+    fn content(&self) -> ContentReadLock<'_, T> {
+        Node::content(self)
+    }
+    #[inline]
+    fn parent(&self) -> Option<Self> {
+        Node::parent(self)
+    }
+    #[inline]
+    fn children(&self) -> Box<[Self]> {
+        Node::children(self)
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<Self> {
+        Node::get(self, index)
+    }
+    #[inline]
+    fn child_count(&self) -> usize {
+        self.children_ref().len()
+    }
+    #[inline]
+    fn ref_clone(&self) -> Self {
+        Node::ref_clone(self)
+    }
+}
+impl<T> TreeOps<T> for Tree<T> {
+    type Node<'a> = Node<T>
+    where Self: 'a;
+
+    #[inline]
+    fn root(&self) -> Node<T> {
+        Tree::root(self)
+    }
+}
@@ -0,0 +1,201 @@
+//! Parallel algorithms that split the work by *subtrees* using [`rayon`].
+use super::*;
+use rayon::prelude::*;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+impl<T> Tree<T>
+where T: Send + Sync {
+    #[inline]
+    /// Whether two [`Tree`]s have the same *shape* and **content**, comparing the subtrees of each [`Node`]'s **children** in parallel.
+    /// See [`Node::par_eq()`].
+    pub fn par_eq(&self, other: &Self) -> bool
+    where T: PartialEq {
+        self.root().par_eq(&other.root())
+    }
+    #[inline]
+    /// Hashes the *shape* and **content** of the [`Tree`], hashing the subtrees of each [`Node`]'s **children** in parallel.
+    /// See [`Node::par_hash()`].
+    pub fn par_hash(&self) -> u64
+    where T: Hash {
+        self.root().par_hash()
+    }
+    #[inline]
+    /// Copies the whole [`Tree`], cloning the subtrees of each [`Node`]'s **children** in parallel.
+    /// See [`Node::clone_deep_parallel()`].
+    pub fn clone_deep_parallel(&self) -> Self
+    where T: Clone {
+        self.root().clone_deep_parallel()
+    }
+}
+
+/// How many times the work can be split by *children* before the rest of a subtree is traversed sequentially (without recursing).
+/// This keeps the recursion shallow for [`Tree`]s of any depth, while still making more tasks than there are threads.
+const MAX_FORKS: usize = 16;
+
+impl<T> Node<T>
+where T: Send + Sync {
+    /// Whether the *subtrees* of two [`Node`]s have the same *shape* and **content**.
+    /// Unlike [`PartialEq`] for [`Node`], which only compares the **content**, the **descendants** are also compared.
+    ///
+    /// The **children**'s subtrees are compared *concurrently*, but only where a [`Node`] has more than one *child*,
+    /// and only for the first few levels of such [`Node`]s. The rest is compared without recursing, so [`Tree`]s of any depth can be compared.
+    #[inline]
+    pub fn par_eq(&self, other: &Self) -> bool
+    where T: PartialEq {
+        self.par_eq_forked(other, 0)
+    }
+    fn par_eq_forked(&self, other: &Self, forks: usize) -> bool
+    where T: PartialEq {
+        if forks >= MAX_FORKS {
+            return self.subtree_eq(other);
+        }
+
+        let (mut node, mut other) = (self.ref_clone(), other.ref_clone());
+        // Walk down while there is only one child, since there is nothing to split.
+        loop {
+            if node.is_same_as(&other) {
+                return true;
+            }
+            if *node.content() != *other.content() {
+                return false;
+            }
+
+            let (children, other_children) = (node.children(), other.children());
+            match (&children[..], &other_children[..]) {
+                ([child], [other_child]) => (node, other) = (child.ref_clone(), other_child.ref_clone()),
+                _ => return children.len() == other_children.len()
+                    && children
+                        .par_iter()
+                        .zip(other_children.par_iter())
+                        .all(|(child, other)| child.par_eq_forked(other, forks + 1)),
+            }
+        }
+    }
+    /// Compares the *subtrees* sequentially with a stack.
+    fn subtree_eq(&self, other: &Self) -> bool
+    where T: PartialEq {
+        let mut stack = vec![(self.ref_clone(), other.ref_clone())];
+
+        while let Some((node, other)) = stack.pop() {
+            if node.is_same_as(&other) {
+                continue;
+            }
+            if *node.content() != *other.content() {
+                return false;
+            }
+            let (children, other_children) = (node.children(), other.children());
+            if children.len() != other_children.len() {
+                return false;
+            }
+            stack.extend(children.iter().map(Node::ref_clone).zip(other_children.iter().map(Node::ref_clone)));
+        }
+
+        true
+    }
+
+    /// Hashes the *shape* and **content** of the *subtree*.
+    /// The **children**'s subtrees are hashed *concurrently* (like in [`par_eq()`](Self::par_eq())), and then combined in order,
+    /// so two *subtrees* that are [`par_eq()`](Self::par_eq()) always have the same hash.
+    #[inline]
+    pub fn par_hash(&self) -> u64
+    where T: Hash {
+        self.par_hash_forked(0)
+    }
+    fn par_hash_forked(&self, forks: usize) -> u64
+    where T: Hash {
+        if forks >= MAX_FORKS {
+            return self.subtree_hash();
+        }
+
+        // Walk down while there is only one child, and combine the hashes on the way back up.
+        let mut spine = vec![];
+        let mut node = self.ref_clone();
+        let mut hash = loop {
+            let children = node.children();
+            if let [child] = &children[..] {
+                let child = child.ref_clone();
+                spine.push(std::mem::replace(&mut node, child));
+                continue;
+            }
+
+            let hashes = children
+                .par_iter()
+                .map(|child| child.par_hash_forked(forks + 1))
+                .collect::<Vec<_>>();
+            break node.combine_hashes(&hashes);
+        };
+        for node in spine.into_iter().rev() {
+            hash = node.combine_hashes(&[hash]);
+        }
+        hash
+    }
+    /// Hashes the *subtree* sequentially, starting from the deepest [`Node`]s, without recursing.
+    fn subtree_hash(&self) -> u64
+    where T: Hash {
+        // Every Node in Breadth-First order, along with the position of its first child and how many children it has.
+        // The children of each Node are next to each other in Breadth-First order.
+        let mut nodes = vec![(self.ref_clone(), 0, 0)];
+        let mut i = 0;
+        while i < nodes.len() {
+            let children = nodes[i].0.children();
+            nodes[i].1 = nodes.len();
+            nodes[i].2 = children.len();
+            nodes.extend(children.iter().map(|child| (child.ref_clone(), 0, 0)));
+            i += 1;
+        }
+
+        let mut hashes = vec![0; nodes.len()];
+        for (i, (node, first, len)) in nodes.iter().enumerate().rev() {
+            hashes[i] = node.combine_hashes(&hashes[*first..first + len]);
+        }
+        hashes[0]
+    }
+    /// The hash of a *subtree*, given the hashes of the **children**'s subtrees.
+    fn combine_hashes(&self, children: &[u64]) -> u64
+    where T: Hash {
+        let mut hasher = DefaultHasher::new();
+        self.content().hash(&mut hasher);
+        children.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Like [`Node::clone_deep()`], but the **children**'s subtrees are cloned *concurrently* (like in [`par_eq()`](Self::par_eq())).
+    /// The resulting cloned [`Node`] will have no **parent**.
+    ///
+    /// Only worth it for large and wide [`Tree`]s, since every subtree near the **root** becomes a task for [`rayon`].
+    pub fn clone_deep_parallel(&self) -> Tree<T>
+    where T: Clone {
+        self.clone_deep_forked(0)
+    }
+    fn clone_deep_forked(&self, forks: usize) -> Tree<T>
+    where T: Clone {
+        if forks >= MAX_FORKS {
+            return self.clone_deep();
+        }
+
+        // Walk down while there is only one child, and build the copy on the way back up.
+        let mut spine = vec![];
+        let mut node = self.ref_clone();
+        let mut tree = loop {
+            let children = node.children();
+            if let [child] = &children[..] {
+                let child = child.ref_clone();
+                spine.push(std::mem::replace(&mut node, child).content().clone());
+                continue;
+            }
+
+            let children = children
+                .par_iter()
+                .map(|child| child.clone_deep_forked(forks + 1))
+                .collect::<Vec<_>>();
+            break Self::with_children(node.content().clone(), children);
+        };
+        for content in spine.into_iter().rev() {
+            tree = Self::with_children(content, vec![tree]);
+        }
+        tree
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+/// A view of a [`Tree`] that only exposes *non-mutating* methods, obtained by calling [`Tree::read_only()`].
+///
+/// Unlike [`Node`], which allows anyone holding it to [`mutate its content`](Node::content_mut()) or [`detach`](Node::detach()) it,
+/// the [`ReadOnlyNode`]s obtained from a [`ReadOnlyTree`] can't be used to modify the [`Tree`] in any way,
+/// so it is safe to pass to code that should only *read* the [`Tree`] (e.g. plugins).
+pub struct ReadOnlyTree<'a, T> {
+    tree: &'a Tree<T>,
+}
+impl<'a, T> ReadOnlyTree<'a, T> {
+    #[inline]
+    pub fn root(&self) -> ReadOnlyNode<T> {
+        ReadOnlyNode(self.tree.root())
+    }
+
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = ReadOnlyNode<T>> {
+        self.tree.iter_bfs().map(ReadOnlyNode)
+    }
+    /// Iterate over all the [`Node`]s of the [`Tree`] using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = ReadOnlyNode<T>> {
+        self.tree.iter_dfs().map(ReadOnlyNode)
+    }
+
+    /// See [`Tree::find()`].
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<ReadOnlyNode<T>> {
+        self.tree.find(predicate).map(ReadOnlyNode)
+    }
+}
+impl<T> Clone for ReadOnlyTree<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for ReadOnlyTree<'_, T> {}
+impl<T> Debug for ReadOnlyTree<'_, T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadOnlyTree")
+            .field("root", &self.tree.root().debug_tree())
+            .finish()
+    }
+}
+
+/// A [`Node`] that only exposes *non-mutating* methods.
+/// Obtained from a [`ReadOnlyTree`] or by calling [`Node::read_only()`].
+///
+/// There is no way to get the [`Node`] back from a [`ReadOnlyNode`].
+pub struct ReadOnlyNode<T>(Node<T>);
+impl<T> ReadOnlyNode<T> {
+    pub fn parent(&self) -> Option<Self> {
+        self.0.parent().map(Self)
+    }
+    /// Allocates a *slice* of all of [`Node`]'s children, increasing all of their *reference counter*.
+    pub fn children(&self) -> Box<[Self]> {
+        self.0.children()
+            .into_vec()
+            .into_iter()
+            .map(Self)
+            .collect()
+    }
+    pub fn content(&self) -> ContentReadLock<'_, T> {
+        self.0.content()
+    }
+
+    /// See [`Node::next_sibling()`].
+    pub fn next_sibling(&self) -> Option<Self> {
+        self.0.next_sibling().map(Self)
+    }
+    /// See [`Node::prev_sibling()`].
+    pub fn prev_sibling(&self) -> Option<Self> {
+        self.0.prev_sibling().map(Self)
+    }
+    /// See [`Node::path_to_root()`].
+    pub fn path_to_root(&self) -> Vec<Self> {
+        self.0.path_to_root()
+            .into_iter()
+            .map(Self)
+            .collect()
+    }
+
+    /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = Self> {
+        self.0.iter_bfs().map(Self)
+    }
+    /// Iterate over all the [`Node`]s of the *subtree* (including `self`) using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = Self> {
+        self.0.iter_dfs().map(Self)
+    }
+
+    #[inline]
+    /// Whether two [`ReadOnlyNode`]s are the same (that is, they reference the same object).
+    pub fn is_same_as(&self, other: &Self) -> bool {
+        self.0.is_same_as(&other.0)
+    }
+}
+impl<T> ReadOnlyNode<T>
+where T: Clone {
+    /// Copies the subtree into a new [`Tree`], which is *not* read-only. See [`Node::clone_deep()`].
+    #[inline]
+    pub fn clone_deep(&self) -> Tree<T> {
+        self.0.clone_deep()
+    }
+}
+impl<T> Clone for ReadOnlyNode<T> {
+    /// Unlike [`Node::clone()`], this only clones the *reference* to the same [`Node`] (see [`Node::ref_clone()`]).
+    fn clone(&self) -> Self {
+        Self(self.0.ref_clone())
+    }
+}
+impl<T> PartialEq for ReadOnlyNode<T>
+where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+impl<T> Eq for ReadOnlyNode<T>
+where T: Eq {}
+impl<T> Debug for ReadOnlyNode<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> Tree<T> {
+    #[inline]
+    /// Get a [`ReadOnlyTree`] view of the [`Tree`], which can't be used to modify it.
+    pub fn read_only(&self) -> ReadOnlyTree<'_, T> {
+        ReadOnlyTree { tree: self }
+    }
+}
+impl<T> Node<T> {
+    #[inline]
+    /// Get a [`ReadOnlyNode`] handle to this [`Node`], which can't be used to modify it.
+    pub fn read_only(&self) -> ReadOnlyNode<T> {
+        ReadOnlyNode(self.ref_clone())
+    }
+}
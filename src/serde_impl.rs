@@ -0,0 +1,236 @@
+//! [`Serialize`] and [`Deserialize`] implementations for the [`Tree`]s.
+//! Every [`Node`] is represented like a [`NodeBuilder`]: as a *struct* with a `content` and a sequence of `children`.
+use super::*;
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::marker::PhantomData;
+
+/// The maximum depth of a [`Tree`] that is deserialized with [`Deserialize`].
+/// Deserializing is *recursive*, so *untrusted* input could otherwise overflow the stack.
+const DEFAULT_MAX_DEPTH: usize = 256;
+const FIELDS: &[&str] = &["content", "children"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Content,
+    Children,
+}
+
+impl<T> Serialize for Node<T>
+where T: Serialize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut node = serializer.serialize_struct("Node", 2)?;
+        node.serialize_field("content", &self.content)?;
+        node.serialize_field("children", &*self.children())?;
+        node.end()
+    }
+}
+impl<T> Serialize for Tree<T>
+where T: Serialize {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.root().serialize(serializer)
+    }
+}
+
+/// The two kinds of [`NodeBuilder`]s can be deserialized with the same code.
+trait Builder<T>: Sized {
+    fn from_parts(content: T, children: Vec<Self>) -> Self;
+}
+impl<T> Builder<T> for NodeBuilder<T> {
+    #[inline]
+    fn from_parts(content: T, children: Vec<Self>) -> Self {
+        Self { content, children }
+    }
+}
+
+/// Deserializes a [`Node`] at **depth**, and fails if it (or its subtree) is deeper than **max_depth**.
+struct NodeSeed<T, B> {
+    depth: usize,
+    max_depth: usize,
+    _marker: PhantomData<fn() -> (T, B)>,
+}
+impl<T, B> NodeSeed<T, B> {
+    fn new(depth: usize, max_depth: usize) -> Self {
+        Self {
+            depth,
+            max_depth,
+            _marker: PhantomData,
+        }
+    }
+}
+impl<'de, T, B> DeserializeSeed<'de> for NodeSeed<T, B>
+where T: Deserialize<'de>, B: Builder<T> {
+    type Value = B;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.depth > self.max_depth {
+            return Err(de::Error::custom(format_args!("the tree is deeper than the maximum depth of {}", self.max_depth)));
+        }
+        deserializer.deserialize_struct("Node", FIELDS, self)
+    }
+}
+impl<'de, T, B> Visitor<'de> for NodeSeed<T, B>
+where T: Deserialize<'de>, B: Builder<T> {
+    type Value = B;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a Node with content and children")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let content = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let children = seq
+            .next_element_seed(ChildrenSeed(NodeSeed::new(self.depth + 1, self.max_depth)))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(B::from_parts(content, children))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut content, mut children) = (None, None);
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Content if content.is_some() => return Err(de::Error::duplicate_field("content")),
+                Field::Children if children.is_some() => return Err(de::Error::duplicate_field("children")),
+                Field::Content => content = Some(map.next_value()?),
+                Field::Children => {
+                    children = Some(map.next_value_seed(ChildrenSeed(NodeSeed::new(self.depth + 1, self.max_depth)))?)
+                }
+            }
+        }
+
+        Ok(B::from_parts(
+            content.ok_or_else(|| de::Error::missing_field("content"))?,
+            children.ok_or_else(|| de::Error::missing_field("children"))?,
+        ))
+    }
+}
+
+/// Deserializes the **children** of a [`Node`] with the [`NodeSeed`] for their depth.
+struct ChildrenSeed<T, B>(NodeSeed<T, B>);
+impl<'de, T, B> DeserializeSeed<'de> for ChildrenSeed<T, B>
+where T: Deserialize<'de>, B: Builder<T> {
+    type Value = Vec<B>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+impl<'de, T, B> Visitor<'de> for ChildrenSeed<T, B>
+where T: Deserialize<'de>, B: Builder<T> {
+    type Value = Vec<B>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a sequence of Nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = vec![];
+        while let Some(child) = seq.next_element_seed(NodeSeed::new(self.0.depth, self.0.max_depth))? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NodeBuilder<T>
+where T: Deserialize<'de> {
+    /// Fails if the [`Tree`] is deeper than 256 levels. Use [`Tree::deserialize_with_max_depth()`] to allow deeper [`Tree`]s.
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        NodeSeed::new(0, DEFAULT_MAX_DEPTH).deserialize(deserializer)
+    }
+}
+impl<'de, T> Deserialize<'de> for Tree<T>
+where T: Deserialize<'de> {
+    /// Fails if the [`Tree`] is deeper than 256 levels. Use [`Tree::deserialize_with_max_depth()`] to allow deeper [`Tree`]s.
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with_max_depth(deserializer, DEFAULT_MAX_DEPTH)
+    }
+}
+impl<T> Tree<T> {
+    /// Like [`Deserialize::deserialize()`], but fails if the [`Tree`] has [`Node`]s deeper than **max_depth** (the **root** is at depth `0`),
+    /// instead of the default of 256.
+    ///
+    /// Deserializing is *recursive*, so a **max_depth** that is too large could let *untrusted* input overflow the stack.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let json = r#"{"content":1,"children":[{"content":2,"children":[]}]}"#;
+    /// assert!(Tree::<i32>::deserialize_with_max_depth(&mut serde_json::Deserializer::from_str(json), 1).is_ok());
+    /// assert!(Tree::<i32>::deserialize_with_max_depth(&mut serde_json::Deserializer::from_str(json), 0).is_err());
+    /// ```
+    pub fn deserialize_with_max_depth<'de, D>(deserializer: D, max_depth: usize) -> Result<Self, D::Error>
+    where T: Deserialize<'de>, D: Deserializer<'de> {
+        NodeSeed::<T, NodeBuilder<T>>::new(0, max_depth)
+            .deserialize(deserializer)
+            .map(NodeBuilder::build)
+    }
+}
+
+/// Implements [`Serialize`] and [`Deserialize`] for the [`Tree`]s of the `rc` and `sync` modules, which have the same API.
+#[cfg(feature = "rc")]
+macro_rules! impl_rc {
+    ($rc:ident) => {
+        impl<T> Builder<T> for $rc::NodeBuilder<T> {
+            #[inline]
+            fn from_parts(content: T, children: Vec<Self>) -> Self {
+                Self { content, children }
+            }
+        }
+        impl<T> Serialize for $rc::Node<T>
+        where T: Serialize {
+            /// The **content** of each *Node* is only locked while it is being serialized.
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut node = serializer.serialize_struct("Node", 2)?;
+                node.serialize_field("content", &*self.content())?;
+                node.serialize_field("children", &*self.children())?;
+                node.end()
+            }
+        }
+        impl<T> Serialize for $rc::Tree<T>
+        where T: Serialize {
+            #[inline]
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.root().serialize(serializer)
+            }
+        }
+        impl<'de, T> Deserialize<'de> for $rc::NodeBuilder<T>
+        where T: Deserialize<'de> {
+            /// Fails if the *Tree* is deeper than 256 levels. Use `Tree::deserialize_with_max_depth()` to allow deeper *Tree*s.
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                NodeSeed::new(0, DEFAULT_MAX_DEPTH).deserialize(deserializer)
+            }
+        }
+        impl<'de, T> Deserialize<'de> for $rc::Tree<T>
+        where T: Deserialize<'de> {
+            /// Fails if the *Tree* is deeper than 256 levels. Use `Tree::deserialize_with_max_depth()` to allow deeper *Tree*s.
+            #[inline]
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Self::deserialize_with_max_depth(deserializer, DEFAULT_MAX_DEPTH)
+            }
+        }
+        impl<T> $rc::Tree<T> {
+            /// See [`Tree::deserialize_with_max_depth()`].
+            pub fn deserialize_with_max_depth<'de, D>(deserializer: D, max_depth: usize) -> Result<Self, D::Error>
+            where T: Deserialize<'de>, D: Deserializer<'de> {
+                NodeSeed::<T, $rc::NodeBuilder<T>>::new(0, max_depth)
+                    .deserialize(deserializer)
+                    .map($rc::NodeBuilder::build)
+            }
+        }
+    };
+}
+#[cfg(feature = "rc")]
+impl_rc!(rc);
+#[cfg(feature = "arc")]
+impl_rc!(sync);
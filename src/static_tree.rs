@@ -0,0 +1,92 @@
+use super::*;
+use std::collections::VecDeque;
+
+/// An immutable [`Node`] that can be built at *compile time*, in a `static` or `const`,
+/// for lookup tables that never change (e.g. a hierarchy of commands).
+/// Its **children** are a `'static` slice, so it doesn't allocate.
+///
+/// Build it with [`static_tree!`](crate::static_tree!), or with a struct literal.
+/// Use [`StaticNode::to_tree()`] to get a [`Tree`] that can be modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticNode<T: 'static> {
+    pub content: T,
+    pub children: &'static [StaticNode<T>],
+}
+impl<T> StaticNode<T> {
+    /// Iterate over all the [`StaticNode`]s of the subtree (including `self`) using **Breadth-First Search**.
+    pub fn iter_bfs(&self) -> impl Iterator<Item = &Self> {
+        let mut queue = VecDeque::from([self]);
+        std::iter::from_fn(move || {
+            let node = queue.pop_front()?;
+            queue.extend(node.children);
+            Some(node)
+        })
+    }
+    /// Iterate over all the [`StaticNode`]s of the subtree (including `self`) using **Depth-First Search**.
+    pub fn iter_dfs(&self) -> impl Iterator<Item = &Self> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// Copies the subtree into a [`Tree`].
+    pub fn to_tree(&self) -> Tree<T>
+    where T: Clone {
+        let builder = |node: &Self| NodeBuilder::with_children_capacity(node.content.clone(), node.children.len());
+        // The builders that are still missing children, with the children they are missing.
+        let mut stack = vec![(builder(self), self.children.iter())];
+
+        loop {
+            let (_, children) = stack.last_mut().unwrap();
+            if let Some(child) = children.next() {
+                stack.push((builder(child), child.children.iter()));
+                continue;
+            }
+
+            let (finished, _) = stack.pop().unwrap();
+            match stack.last_mut() {
+                Some((parent, _)) => parent.children.push(finished),
+                None => return finished.build(),
+            }
+        }
+    }
+}
+
+/// Builds a [`StaticNode`] in a `static` or `const`.
+///
+/// Each [`Node`] is written as its **content** (any expression), optionally followed by `=>` and its **children** in brackets.
+///
+/// # Example
+/// ```
+/// # use tree_struct::{static_tree, StaticNode};
+/// static COMMANDS: StaticNode<&str> = static_tree!("git" => [
+///     "commit",
+///     "remote" => ["add", "remove"],
+/// ]);
+///
+/// assert_eq!(COMMANDS.children[1].children[0].content, "add");
+/// assert_eq!(COMMANDS.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), ["git", "commit", "remote", "add", "remove"]);
+/// ```
+#[macro_export]
+macro_rules! static_tree {
+    // The children are parsed one by one, accumulating the finished ones in brackets.
+    (@children [$($done:expr),*]) => {
+        &[$($done),*]
+    };
+    (@children [$($done:expr),*] $content:expr => [$($children:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::static_tree!(@children [$($done,)* $crate::static_tree!($content => [$($children)*])] $($($rest)*)?)
+    };
+    (@children [$($done:expr),*] $content:expr $(, $($rest:tt)*)?) => {
+        $crate::static_tree!(@children [$($done,)* $crate::static_tree!($content)] $($($rest)*)?)
+    };
+    ($content:expr $(=> [$($children:tt)*])?) => {
+        $crate::StaticNode {
+            content: $content,
+            children: $crate::static_tree!(@children [] $($($children)*)?),
+        }
+    };
+}
@@ -0,0 +1,60 @@
+#![doc = include_str!("./rc_impl/README.md")]
+//!
+//! The Nodes in this module use [`Arc`](std::sync::Arc) and [`RwLock`], so they can be shared between threads.
+//! It has the same API as the [`rc`](crate::rc) module, and both can be used in the same build.
+// The same implementation is also included by the `rc` module, with different types.
+#[allow(clippy::duplicate_mod)]
+#[path = "rc_impl/mod.rs"]
+mod rc_impl;
+#[cfg(feature = "rayon")]
+#[path = "rc_impl/par.rs"]
+mod par;
+
+pub use rc_impl::*;
+use std::{
+    sync::{Arc as Rc, Weak as WeakRc},
+    time::Duration,
+};
+use parking_lot::{
+    RwLock,
+    RwLockReadGuard as ReadLock, RwLockWriteGuard as WriteLock,
+    MappedRwLockReadGuard as ContentReadLock, MappedRwLockWriteGuard as ContentWriteLock
+};
+
+// Helper functions that allow writing the same code between RefCell and RwLock.
+#[inline]
+fn borrow<T>(this: &RwLock<T>) -> ReadLock<T> {
+    this.read()
+}
+#[inline]
+fn borrow_mut<T>(this: &RwLock<T>) -> WriteLock<T> {
+    this.write()
+}
+#[inline]
+fn try_borrow<T>(this: &RwLock<T>) -> Option<ReadLock<'_, T>> {
+    this.try_read()
+}
+#[inline]
+fn try_borrow_mut<T>(this: &RwLock<T>) -> Option<WriteLock<'_, T>> {
+    this.try_write()
+}
+
+// Nodes and Trees can be shared between threads, because all their state is behind an Arc and a RwLock.
+fn _assert_send_sync<T: Send + Sync>() {
+    fn check<S: Send + Sync>() {}
+    check::<Node<T>>();
+    check::<WeakNode<T>>();
+    check::<Tree<T>>();
+    check::<CowTree<T>>();
+}
+
+impl<T> Node<T> {
+    /// Like [`Self::try_content()`], but waits up to **timeout** for the lock to be released.
+    pub fn try_content_for(&self, timeout: Duration) -> Option<ContentReadLock<'_, T>> {
+        self.rc().try_read_for(timeout).map(|n| ReadLock::map(n, |n| &n.content))
+    }
+    /// Like [`Self::try_content_mut()`], but waits up to **timeout** for the lock to be released.
+    pub fn try_content_mut_for(&self, timeout: Duration) -> Option<ContentWriteLock<'_, T>> {
+        self.rc().try_write_for(timeout).map(|n| WriteLock::map(n, |n| &mut n.content))
+    }
+}
@@ -0,0 +1,508 @@
+//! Building [`Tree`]s from (and converting them to) text formats.
+use super::*;
+
+/// Returned by [`Tree::from_indented()`] when the text is not a valid outline.
+/// **line** is the (`1`-based) number of the invalid line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndentedError {
+    /// The text has no (non-blank) lines.
+    Empty,
+    /// A line is not indented, but the first line (the **root**) was already found.
+    MultipleRoots { line: usize },
+    /// The indentation of a line is not a whole number of *indent units*.
+    Misaligned { line: usize },
+    /// A line is indented more than one level deeper than the previous line.
+    TooDeep { line: usize },
+}
+impl std::fmt::Display for IndentedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the text is empty"),
+            Self::MultipleRoots { line } => write!(f, "line {line} is a second root"),
+            Self::Misaligned { line } => write!(f, "line {line} is not indented by a whole number of indent units"),
+            Self::TooDeep { line } => write!(f, "line {line} is indented more than one level deeper than its parent"),
+        }
+    }
+}
+impl std::error::Error for IndentedError {}
+
+impl Tree<String> {
+    /// Builds a [`Tree`] from an *outline*, where each line is a [`Node`]
+    /// and the **children** of a [`Node`] are the following lines that are indented one level deeper.
+    /// Each level is indented by 4 spaces. Blank lines are ignored.
+    ///
+    /// See [`Tree::from_indented_with()`] to use a different *indent unit*.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let tree = Tree::from_indented("
+    /// parent
+    ///     child a
+    ///         child c
+    ///     child b
+    /// ").unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(), vec!["parent", "child a", "child b", "child c"]);
+    /// ```
+    #[inline]
+    pub fn from_indented(text: &str) -> Result<Self, IndentedError> {
+        Self::from_indented_with(text, "    ")
+    }
+    /// Like [`Tree::from_indented()`], but each level is indented by **unit** (e.g. `"\t"`).
+    ///
+    /// This is the inverse of printing a [`Tree`] with a [`TreeFormatter`] with [`IndentStyle::Spaces`](crate::IndentStyle::Spaces)
+    /// (as long as the **contents** have a single line and **unit** has the same width as the [`TreeFormatter`]'s *indent*).
+    ///
+    /// # Panics
+    /// If **unit** is empty.
+    pub fn from_indented_with(text: &str, unit: &str) -> Result<Self, IndentedError> {
+        assert!(!unit.is_empty(), "The indent unit can't be empty");
+
+        // The Nodes from the root to the last Node that was found, which can still get more children.
+        let mut stack = Vec::<NodeBuilder<String>>::new();
+        let fold = |stack: &mut Vec<NodeBuilder<String>>, depth: usize| {
+            while stack.len() > depth.max(1) {
+                let child = stack.pop().unwrap();
+                stack.last_mut().unwrap().children.push(child);
+            }
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+
+            let mut content = line;
+            let mut depth = 0;
+            while let Some(rest) = content.strip_prefix(unit) {
+                content = rest;
+                depth += 1;
+            }
+            if content.starts_with(char::is_whitespace) {
+                return Err(IndentedError::Misaligned { line: line_number });
+            }
+
+            fold(&mut stack, depth);
+            if depth == 0 && !stack.is_empty() {
+                return Err(IndentedError::MultipleRoots { line: line_number });
+            }
+            if depth > stack.len() {
+                return Err(IndentedError::TooDeep { line: line_number });
+            }
+            stack.push(NodeBuilder::new(content.to_string()));
+        }
+
+        fold(&mut stack, 0);
+        stack.pop().map(NodeBuilder::build).ok_or(IndentedError::Empty)
+    }
+}
+
+/// Returned by [`Tree::from_sexpr()`] when the text is not a valid *S-expression*,
+/// or when the **content** of a [`Node`] can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SexprError<E> {
+    /// The text ended before all the lists were closed (or it had no *atoms*).
+    UnexpectedEnd,
+    /// Found an unexpected character at the (byte) **position** in the text.
+    Unexpected { position: usize },
+    /// The **content** of a [`Node`] failed to parse.
+    Content(E),
+}
+impl<E> std::fmt::Display for SexprError<E>
+where E: std::fmt::Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of S-expression"),
+            Self::Unexpected { position } => write!(f, "unexpected character at position {position}"),
+            Self::Content(error) => write!(f, "invalid content: {error}"),
+        }
+    }
+}
+impl<E> std::error::Error for SexprError<E>
+where E: std::error::Error {}
+
+impl<T> Tree<T>
+where T: std::fmt::Display {
+    /// Converts the [`Tree`] to an *S-expression*, the inverse of [`Tree::from_sexpr()`].
+    ///
+    /// A [`Node`] with **children** is written as a list of its **content** followed by its **children**, e.g. `(a (b d) c)`.
+    /// A [`Node`] without **children** is written as just its **content**.
+    /// The **content** is written with [`Display`](std::fmt::Display), and is wrapped in quotes
+    /// if it is empty or has spaces, parentheses, quotes or backslashes (which are escaped with a backslash).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Node;
+    /// let tree = Node::builder("a").child(Node::builder("b").child(Node::builder("d"))).child(Node::builder("c c")).build();
+    /// assert_eq!(tree.to_sexpr(), r#"(a (b d) "c c")"#);
+    /// ```
+    pub fn to_sexpr(&self) -> String {
+        fn write_atom(out: &mut String, content: String) {
+            if !content.is_empty() && !content.contains(|c: char| c.is_whitespace() || "()\"\\".contains(c)) {
+                out.push_str(&content);
+                return;
+            }
+            out.push('"');
+            for c in content.chars() {
+                if c == '"' || c == '\\' {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            out.push('"');
+        }
+        fn write_node<T: std::fmt::Display>(out: &mut String, node: &Node<T>) {
+            let children = node.children();
+            if children.is_empty() {
+                write_atom(out, node.content.to_string());
+                return;
+            }
+            out.push('(');
+            write_atom(out, node.content.to_string());
+            for child in children.iter() {
+                out.push(' ');
+                write_node(out, child);
+            }
+            out.push(')');
+        }
+
+        let mut out = String::new();
+        write_node(&mut out, self.root());
+        out
+    }
+}
+impl<T> Tree<T>
+where T: std::str::FromStr {
+    /// Builds a [`Tree`] from an *S-expression* written by [`Tree::to_sexpr()`].
+    ///
+    /// A list is a [`Node`] whose **content** is the first element, and whose **children** are the rest of the elements.
+    /// An *atom* outside the first position of a list is a [`Node`] with no **children**.
+    /// The **contents** are parsed with [`FromStr`](std::str::FromStr).
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let tree = Tree::<u32>::from_sexpr("(1 (2 4) 3)").unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn from_sexpr(text: &str) -> Result<Self, SexprError<T::Err>> {
+        enum Token {
+            Open,
+            Close,
+            Atom(String),
+        }
+        let mut chars = text.char_indices().peekable();
+        let mut next_token = || -> Result<Option<(usize, Token)>, SexprError<T::Err>> {
+            while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            let Some((position, c)) = chars.next() else {
+                return Ok(None);
+            };
+            let token = match c {
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '"' => {
+                    let mut atom = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '"')) => break,
+                            Some((_, '\\')) => atom.push(chars.next().ok_or(SexprError::UnexpectedEnd)?.1),
+                            Some((_, c)) => atom.push(c),
+                            None => return Err(SexprError::UnexpectedEnd),
+                        }
+                    }
+                    Token::Atom(atom)
+                }
+                c => {
+                    let mut atom = String::from(c);
+                    while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && !"()\"".contains(*c)) {
+                        atom.push(c);
+                    }
+                    Token::Atom(atom)
+                }
+            };
+            Ok(Some((position, token)))
+        };
+        let parse = |atom: String| atom.parse::<T>().map(NodeBuilder::new).map_err(SexprError::Content);
+
+        // The lists that are still open.
+        let mut stack = Vec::<NodeBuilder<T>>::new();
+        let mut root = None;
+
+        while let Some((position, token)) = next_token()? {
+            if root.is_some() {
+                return Err(SexprError::Unexpected { position });
+            }
+            let finished = match token {
+                Token::Open => match next_token()? {
+                    Some((_, Token::Atom(atom))) => {
+                        stack.push(parse(atom)?);
+                        continue;
+                    }
+                    Some((position, _)) => return Err(SexprError::Unexpected { position }),
+                    None => return Err(SexprError::UnexpectedEnd),
+                },
+                Token::Close => stack.pop().ok_or(SexprError::Unexpected { position })?,
+                Token::Atom(atom) => parse(atom)?,
+            };
+
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(finished),
+                None => root = Some(finished),
+            }
+        }
+
+        root.map(NodeBuilder::build).ok_or(SexprError::UnexpectedEnd)
+    }
+}
+
+/// Returned by [`Tree::from_brackets()`] when the text is not valid *bracket notation*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BracketError {
+    /// The text ended before all the brackets were closed (or it had no labels).
+    UnexpectedEnd,
+    /// Found an unexpected character at the (byte) **position** in the text.
+    Unexpected { position: usize },
+}
+impl std::fmt::Display for BracketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of bracket notation"),
+            Self::Unexpected { position } => write!(f, "unexpected character at position {position}"),
+        }
+    }
+}
+impl std::error::Error for BracketError {}
+
+/// Parses *bracket notation*, where a [`Node`] is a label optionally followed by its **children** in parentheses.
+/// With **single_chars**, every character (other than whitespace and parentheses) is a label, and there are no separators.
+/// Otherwise, labels are separated by commas, and whitespace around them is trimmed.
+fn parse_brackets(text: &str, single_chars: bool) -> Result<NodeBuilder<String>, BracketError> {
+    // The Nodes whose children are still open.
+    let mut stack = Vec::<NodeBuilder<String>>::new();
+    // The last label, which can still get children.
+    let mut last = None::<NodeBuilder<String>>;
+    let mut root = None;
+    // Whether a Node (a label or a closing bracket) just ended.
+    let mut after_node = false;
+    let mut chars = text.char_indices().peekable();
+
+    let flush = |stack: &mut Vec<NodeBuilder<String>>, root: &mut Option<NodeBuilder<String>>, node: NodeBuilder<String>| match stack.last_mut() {
+        Some(parent) => parent.children.push(node),
+        None => *root = Some(node),
+    };
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '(' => {
+                stack.push(last.take().ok_or(BracketError::Unexpected { position })?);
+                after_node = false;
+            }
+            ')' => {
+                if stack.is_empty() || (!single_chars && !after_node && !stack.last().unwrap().children.is_empty()) {
+                    return Err(BracketError::Unexpected { position });
+                }
+                if let Some(node) = last.take() {
+                    flush(&mut stack, &mut root, node);
+                }
+                let node = stack.pop().unwrap();
+                flush(&mut stack, &mut root, node);
+                after_node = true;
+            }
+            ',' if !single_chars => {
+                if !after_node || stack.is_empty() {
+                    return Err(BracketError::Unexpected { position });
+                }
+                if let Some(node) = last.take() {
+                    flush(&mut stack, &mut root, node);
+                }
+                after_node = false;
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let mut label = String::from(c);
+                if !single_chars {
+                    while let Some((_, c)) = chars.next_if(|(_, c)| !"(),".contains(*c)) {
+                        label.push(c);
+                    }
+                    label.truncate(label.trim_end().len());
+                }
+                // Another Node at the top level would be a second root.
+                if (after_node && !single_chars) || (stack.is_empty() && (root.is_some() || last.is_some())) {
+                    return Err(BracketError::Unexpected { position });
+                }
+                if let Some(node) = last.replace(NodeBuilder::new(label)) {
+                    flush(&mut stack, &mut root, node);
+                }
+                after_node = true;
+            }
+        }
+    }
+
+    if let Some(node) = last {
+        flush(&mut stack, &mut root, node);
+    }
+    if !stack.is_empty() {
+        return Err(BracketError::UnexpectedEnd);
+    }
+    root.ok_or(BracketError::UnexpectedEnd)
+}
+fn write_brackets<T>(out: &mut String, node: &Node<T>, separator: &str)
+where T: std::fmt::Display {
+    use std::fmt::Write;
+    write!(out, "{}", node.content).unwrap();
+
+    let children = node.children();
+    if !children.is_empty() {
+        out.push('(');
+        for (i, child) in children.iter().enumerate() {
+            if i > 0 {
+                out.push_str(separator);
+            }
+            write_brackets(out, child, separator);
+        }
+        out.push(')');
+    }
+}
+
+impl Tree<char> {
+    /// Builds a [`Tree`] from *bracket notation*, where each character is a [`Node`],
+    /// optionally followed by its **children** in parentheses (e.g. `a(b(d)c)`). Whitespace is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let tree = Tree::<char>::from_brackets("a(b(d)c)").unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "abcd");
+    /// assert_eq!(tree.to_brackets(), "a(b(d)c)");
+    /// ```
+    pub fn from_brackets(text: &str) -> Result<Self, BracketError> {
+        // Labels have exactly 1 char.
+        Ok(parse_brackets(text, true)?.build().map(|label| label.chars().next().unwrap()))
+    }
+    /// Converts the [`Tree`] to *bracket notation*, the inverse of [`Tree::<char>::from_brackets()`](Tree::from_brackets()).
+    pub fn to_brackets(&self) -> String {
+        let mut out = String::new();
+        write_brackets(&mut out, self.root(), "");
+        out
+    }
+}
+impl Tree<String> {
+    /// Builds a [`Tree`] from *bracket notation*, where each [`Node`] is a label (which can't contain parentheses or commas),
+    /// optionally followed by its comma-separated **children** in parentheses (e.g. `root(child a(child c), child b)`).
+    /// Whitespace around the labels is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::Tree;
+    /// let tree = Tree::<String>::from_brackets("root(child a(child c), child b)").unwrap();
+    /// assert_eq!(tree.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(), vec!["root", "child a", "child b", "child c"]);
+    /// assert_eq!(tree.to_brackets(), "root(child a(child c),child b)");
+    /// ```
+    #[inline]
+    pub fn from_brackets(text: &str) -> Result<Self, BracketError> {
+        Ok(parse_brackets(text, false)?.build())
+    }
+    /// Converts the [`Tree`] to *bracket notation*, the inverse of [`Tree::<String>::from_brackets()`](Tree::from_brackets()).
+    /// The **contents** are written as is, so the result can't be parsed back if they contain parentheses or commas.
+    pub fn to_brackets(&self) -> String {
+        let mut out = String::new();
+        write_brackets(&mut out, self.root(), ",");
+        out
+    }
+}
+
+/// The text formats that a [`Tree<String>`] can be parsed from.
+/// Used by the [`FromStr`](std::str::FromStr) and [`TryFrom<&str>`] implementations of [`Tree<String>`],
+/// which pick the format with [`TextFormat::detect()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextFormat {
+    /// An *outline*, as in [`Tree::from_indented_with()`], where the *indent unit* is the indentation of the first indented line.
+    Indented,
+    /// *Bracket notation*, as in [`Tree::<String>::from_brackets()`](Tree::from_brackets()).
+    Brackets,
+    /// An *S-expression*, as in [`Tree::from_sexpr()`].
+    Sexpr,
+}
+impl TextFormat {
+    /// Guesses the format of **text**:
+    /// [`Sexpr`](Self::Sexpr) if it starts with `(`, [`Indented`](Self::Indented) if it has more than 1 (non-blank) line,
+    /// and [`Brackets`](Self::Brackets) otherwise (so a single label is a [`Tree`] with only a **root**).
+    pub fn detect(text: &str) -> Self {
+        let text = text.trim();
+        if text.starts_with('(') {
+            Self::Sexpr
+        } else if text.contains('\n') {
+            Self::Indented
+        } else {
+            Self::Brackets
+        }
+    }
+
+    /// Builds a [`Tree`] from **text** in this format.
+    ///
+    /// # Example
+    /// ```
+    /// # use tree_struct::{TextFormat, Tree};
+    /// let tree = TextFormat::Indented.parse("a\n  b\n  c").unwrap();
+    /// assert_eq!(tree, TextFormat::Brackets.parse("a(b, c)").unwrap());
+    /// assert_eq!(tree, "(a b c)".parse::<Tree<String>>().unwrap());
+    /// ```
+    pub fn parse(self, text: &str) -> Result<Tree<String>, TextError> {
+        match self {
+            Self::Indented => {
+                let unit = text
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| &line[..line.len() - line.trim_start().len()])
+                    .find(|indent| !indent.is_empty())
+                    .unwrap_or("    ");
+                Tree::from_indented_with(text, unit).map_err(TextError::Indented)
+            }
+            Self::Brackets => Tree::<String>::from_brackets(text).map_err(TextError::Brackets),
+            Self::Sexpr => Tree::from_sexpr(text).map_err(|error| {
+                TextError::Sexpr(match error {
+                    SexprError::UnexpectedEnd => SexprError::UnexpectedEnd,
+                    SexprError::Unexpected { position } => SexprError::Unexpected { position },
+                    SexprError::Content(never) => match never {},
+                })
+            }),
+        }
+    }
+}
+
+/// Returned when parsing a [`Tree<String>`] with a [`TextFormat`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextError {
+    Indented(IndentedError),
+    Brackets(BracketError),
+    Sexpr(SexprError<std::convert::Infallible>),
+}
+impl std::fmt::Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Indented(error) => write!(f, "invalid outline: {error}"),
+            Self::Brackets(error) => write!(f, "invalid bracket notation: {error}"),
+            Self::Sexpr(error) => write!(f, "invalid S-expression: {error}"),
+        }
+    }
+}
+impl std::error::Error for TextError {}
+
+impl std::str::FromStr for Tree<String> {
+    type Err = TextError;
+
+    /// Parses **text** in the format guessed by [`TextFormat::detect()`].
+    #[inline]
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        TextFormat::detect(text).parse(text)
+    }
+}
+impl TryFrom<&str> for Tree<String> {
+    type Error = TextError;
+
+    #[inline]
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
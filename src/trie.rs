@@ -0,0 +1,167 @@
+//! A *trie* (or *prefix tree*) that maps *paths* (sequences of *keys* of type **`K`**) to values of type **`V`**,
+//! built on the [`keyed`](crate::keyed) [`Tree`](keyed::Tree).
+//!
+//! Each [`Node`](keyed::Node) is a *key* in a *path*, and it has a value if a *path* ends at it.
+//! *Paths* that share a *prefix* share the [`Node`](keyed::Node)s of the *prefix*, so all the *paths* that start with a *prefix* are found quickly.
+//!
+//! # Example
+//! ```
+//! # use tree_struct::trie::Trie;
+//! let mut trie = Trie::new();
+//! for path in ["src/lib.rs", "src/node.rs", "tests/node.rs"] {
+//!     trie.insert(path.split('/'), path.len());
+//! }
+//!
+//! assert_eq!(trie.get(["src", "node.rs"]), Some(&11));
+//! assert_eq!(trie.get(["src"]), None);
+//! let files = trie.iter_prefix(["src"]).map(|(path, _)| path.into_iter().copied().collect::<Vec<_>>().join("/")).collect::<Vec<_>>();
+//! assert_eq!(files, ["src/lib.rs", "src/node.rs"]);
+//! ```
+use crate::keyed;
+use std::borrow::Borrow;
+
+/// Maps *paths* of *keys* to values. See the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trie<K, V> {
+    tree: keyed::Tree<K, Option<V>>,
+    len: usize,
+}
+impl<K, V> Trie<K, V>
+where K: Ord {
+    /// A [`Trie`] with no values.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            tree: keyed::Tree::new(None),
+            len: 0,
+        }
+    }
+
+    /// The number of values (not [`Node`](keyed::Node)s) in the [`Trie`]. This is `O(1)`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The [`Tree`](keyed::Tree) that the [`Trie`] is built on,
+    /// where the *keys* of a *path* are the **children** of each other, and a [`Node`](keyed::Node)'s **content** is the value of the *path* that ends there.
+    #[inline]
+    pub fn as_tree(&self) -> &keyed::Tree<K, Option<V>> {
+        &self.tree
+    }
+
+    /// Sets the value of **path**, creating the [`Node`](keyed::Node)s of the *path* that don't exist yet.
+    /// Returns the previous value of **path**, if any.
+    pub fn insert(&mut self, path: impl IntoIterator<Item = K>, value: V) -> Option<V> {
+        let node = path
+            .into_iter()
+            .fold(self.tree.root_mut(), |node, key| node.child_or_insert_with(key, || None));
+        let old = node.content.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+    /// Get the value of **path**, if any.
+    #[inline]
+    pub fn get<'a, Q>(&self, path: impl IntoIterator<Item = &'a Q>) -> Option<&V>
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        self.tree.get_path(path)?.content.as_ref()
+    }
+    /// Get the value of **path** as `mutable`, if any.
+    #[inline]
+    pub fn get_mut<'a, Q>(&mut self, path: impl IntoIterator<Item = &'a Q>) -> Option<&mut V>
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        self.tree.get_path_mut(path)?.content.as_mut()
+    }
+    /// Whether **path** has a value.
+    #[inline]
+    pub fn contains<'a, Q>(&self, path: impl IntoIterator<Item = &'a Q>) -> bool
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        self.get(path).is_some()
+    }
+    /// Removes the value of **path** and returns it.
+    /// The [`Node`](keyed::Node)s of the *path* that are left with no value and no **children** are also removed.
+    pub fn remove<'a, Q>(&mut self, path: impl IntoIterator<Item = &'a Q>) -> Option<V>
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        let path = path.into_iter().collect::<Vec<_>>();
+
+        // The depth of the highest Node of the path that would be left with no value and no children,
+        // i.e. the Nodes below the last one that has a value or another child.
+        let mut cut = 0;
+        let mut node = self.tree.root();
+        for (depth, key) in path.iter().enumerate() {
+            if node.content.is_some() || node.child_count() > 1 {
+                cut = depth;
+            }
+            node = node.get_child(*key)?;
+        }
+        node.content.as_ref()?;
+
+        let value = if node.child_count() == 0 && !path.is_empty() {
+            let parent = self.tree.get_path_mut(path[..cut].iter().copied()).unwrap();
+            let mut removed = parent.remove_child(path[cut]).unwrap();
+            removed.get_path_mut(path[cut + 1..].iter().copied()).unwrap().content.take()
+        } else {
+            self.tree.get_path_mut(path).unwrap().content.take()
+        };
+        self.len -= 1;
+        value
+    }
+
+    /// Iterate over all the *paths* that have a value (and their values), in the order of their *keys*.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<&K>, &V)> + '_ {
+        self.iter_prefix(std::iter::empty::<&K>())
+    }
+    /// Iterate over the *paths* that start with **prefix** (including **prefix** itself) and have a value, in the order of their *keys*.
+    pub fn iter_prefix<'a, Q>(&self, prefix: impl IntoIterator<Item = &'a Q>) -> impl Iterator<Item = (Vec<&K>, &V)> + '_
+    where K: Borrow<Q>, Q: Ord + ?Sized + 'a {
+        let mut path = vec![];
+        let start = prefix.into_iter().try_fold(self.tree.root(), |node, key| {
+            let (key, child) = node.get_child_key_value(key)?;
+            path.push(key);
+            Some(child)
+        });
+
+        let mut stack = start.map(|node| (path, node)).into_iter().collect::<Vec<_>>();
+        std::iter::from_fn(move || loop {
+            let (path, node) = stack.pop()?;
+            // Reverse because the first child should be popped next from the stack.
+            stack.extend(node.children().rev().map(|(key, child)| {
+                let mut path = path.clone();
+                path.push(key);
+                (path, child)
+            }));
+            if let Some(value) = &node.content {
+                return Some((path, value));
+            }
+        })
+    }
+}
+impl<K, V> Default for Trie<K, V>
+where K: Ord {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<K, V, P> FromIterator<(P, V)> for Trie<K, V>
+where K: Ord, P: IntoIterator<Item = K> {
+    fn from_iter<I: IntoIterator<Item = (P, V)>>(iter: I) -> Self {
+        let mut trie = Self::new();
+        trie.extend(iter);
+        trie
+    }
+}
+impl<K, V, P> Extend<(P, V)> for Trie<K, V>
+where K: Ord, P: IntoIterator<Item = K> {
+    fn extend<I: IntoIterator<Item = (P, V)>>(&mut self, iter: I) {
+        for (path, value) in iter {
+            self.insert(path, value);
+        }
+    }
+}
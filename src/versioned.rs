@@ -0,0 +1,260 @@
+use super::*;
+
+/// A *logical timestamp* that orders changes made to a [`VersionedTree`] by different *actors* (e.g. replicas of the same [`Tree`]).
+///
+/// [`Version`]s are compared by **counter** first, and then by **actor** to break ties,
+/// so every change has a unique place in the order as long as each *actor* has a unique id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Version {
+    pub counter: u64,
+    pub actor: u64,
+}
+
+/// What happened to a [`Node`] of a [`VersionedTree`]. Obtained from [`VersionedTree::changes_since()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The [`Node`] (and its subtree) was added to the [`Tree`].
+    Inserted,
+    /// The **content** of the [`Node`] was mutated.
+    Modified,
+    /// The [`Node`] (and its subtree) was removed from the [`Tree`].
+    Removed,
+}
+
+/// The **content** of a [`Node`] in a [`VersionedTree`], along with the [`Version`]s of the changes made to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Versioned<T> {
+    pub content: T,
+    inserted: Version,
+    modified: Version,
+}
+impl<T> Versioned<T> {
+    fn new(content: T, version: Version) -> Self {
+        Self {
+            content,
+            inserted: version,
+            modified: version,
+        }
+    }
+
+    /// The [`Version`] at which the [`Node`] was added to the [`Tree`].
+    pub fn inserted(&self) -> Version {
+        self.inserted
+    }
+    /// The [`Version`] of the last change to the [`Node`]'s **content** (or when it was inserted if it was never mutated).
+    pub fn modified(&self) -> Version {
+        self.modified
+    }
+}
+
+/// An entry of the log of a [`VersionedTree`].
+#[derive(Debug, Clone)]
+struct Change {
+    /// The *path* of the [`Node`] at the time of the change.
+    path: Vec<usize>,
+    kind: ChangeKind,
+    version: Version,
+    /// The [`Version`] at which the changed [`Node`] was inserted,
+    /// to know whether a [`Removed`](ChangeKind::Removed) [`Node`] existed at some earlier [`Version`].
+    inserted: Version,
+}
+
+/// A [`Tree`] that records a [`Version`] on every [`Node`] when it is inserted or its **content** is mutated,
+/// so that the changes made after some [`Version`] can be collected with [`VersionedTree::changes_since()`].
+///
+/// This can be used as the foundation for *synchronizing* replicas of the same [`Tree`].
+///
+/// All mutations must go through the [`VersionedTree`] (which is why it only gives out an *immutable* [`Tree`]),
+/// and [`Node`]s are referenced by their *path*:
+/// the index of each [`Node`] in its **parent**'s [`children`](Node::children()), starting from the **root**.
+///
+/// # Example
+/// ```
+/// # use tree_struct::{ChangeKind, Node, VersionedTree};
+/// let mut tree = VersionedTree::new(Node::builder("a").child(Node::builder("b")).build(), 1);
+/// let synced = tree.version();
+///
+/// *tree.content_mut(&[0]).unwrap() = "B";
+/// tree.append_child(&[], Node::builder("c").build()).unwrap();
+///
+/// assert_eq!(
+///     tree.changes_since(synced),
+///     vec![(vec![0], ChangeKind::Modified), (vec![1], ChangeKind::Inserted)]
+/// );
+/// ```
+pub struct VersionedTree<T> {
+    tree: Tree<Versioned<T>>,
+    actor: u64,
+    counter: u64,
+    /// Every change in the order it was made. See [`Change`].
+    log: Vec<Change>,
+}
+impl<T> VersionedTree<T> {
+    /// All the [`Node`]s of **tree** start with the *default* [`Version`] (all `0`s).
+    /// Changes made through this [`VersionedTree`] will be recorded with **actor** as the [`Version::actor`].
+    pub fn new(tree: Tree<T>, actor: u64) -> Self {
+        Self {
+            tree: tree.map(|content| Versioned::new(content, Version::default())),
+            actor,
+            counter: 0,
+            log: vec![],
+        }
+    }
+    /// Stop tracking changes and get the [`Tree`] back.
+    pub fn into_tree(self) -> Tree<T> {
+        self.tree.map(|versioned| versioned.content)
+    }
+
+    /// The underlying [`Tree`], which can only be mutated through the [`VersionedTree`].
+    pub fn tree(&self) -> &Tree<Versioned<T>> {
+        &self.tree
+    }
+    /// The [`Version`] of the *latest* change.
+    pub fn version(&self) -> Version {
+        Version {
+            counter: self.counter,
+            actor: self.actor,
+        }
+    }
+    /// Make sure that the changes made after this call come after **version**, which came from another *actor*.
+    pub fn observe(&mut self, version: Version) {
+        self.counter = self.counter.max(version.counter);
+    }
+    fn bump(&mut self) -> Version {
+        self.counter += 1;
+        self.version()
+    }
+
+    fn find(&self, path: &[usize]) -> Option<&Node<Versioned<T>>> {
+        let mut node = self.tree.root();
+        for &index in path {
+            node = *node.children().get(index)?;
+        }
+        Some(node)
+    }
+    fn find_mut(&mut self, path: &[usize]) -> Option<Pin<&mut Node<Versioned<T>>>> {
+        if path.is_empty() {
+            return Some(self.tree.root_mut());
+        }
+        let node = self.find(path)?.ptr();
+        self.tree.borrow_descendant(node)
+    }
+
+    /// Get a *mutable reference* to the **content** of the [`Node`] at **path**, which marks it as [`Modified`](ChangeKind::Modified).
+    /// Returns [`None`] if there is no [`Node`] at **path**.
+    pub fn content_mut(&mut self, path: &[usize]) -> Option<&mut T> {
+        let inserted = self.find(path)?.content.inserted;
+        let version = self.bump();
+        self.log.push(Change {
+            path: path.to_vec(),
+            kind: ChangeKind::Modified,
+            version,
+            inserted,
+        });
+        let versioned = &mut unsafe { self.find_mut(path)?.get_unchecked_mut() }.content;
+        versioned.modified = version;
+        Some(&mut versioned.content)
+    }
+    /// Pushes the **child** to the end of the *children* of the [`Node`] at **path**, and marks it (and its subtree) as [`Inserted`](ChangeKind::Inserted).
+    /// Returns the **child** back if there is no [`Node`] at **path**.
+    pub fn append_child(&mut self, path: &[usize], child: Tree<T>) -> Result<(), Tree<T>> {
+        let index = match self.find(path) {
+            Some(parent) => parent.children_iter().count(),
+            None => return Err(child),
+        };
+        let version = self.bump();
+        let mut child_path = path.to_vec();
+        child_path.push(index);
+        self.log.push(Change {
+            path: child_path,
+            kind: ChangeKind::Inserted,
+            version,
+            inserted: version,
+        });
+        let child = child.map(|content| Versioned::new(content, version));
+        self.find_mut(path).unwrap().append_child(child);
+        Ok(())
+    }
+    /// Removes the [`Node`] at **path** and returns it (with ownership), recording it as [`Removed`](ChangeKind::Removed).
+    /// Returns [`None`] if there is no [`Node`] at **path**, or if **path** is the **root**.
+    pub fn detach(&mut self, path: &[usize]) -> Option<Tree<T>> {
+        if path.is_empty() {
+            return None;
+        }
+        let node = self.find(path)?;
+        let inserted = node.content.inserted;
+        let detached = self.tree.detach_descendant(node.ptr())?;
+        let version = self.bump();
+        self.log.push(Change {
+            path: path.to_vec(),
+            kind: ChangeKind::Removed,
+            version,
+            inserted,
+        });
+        Some(detached.map(|versioned| versioned.content))
+    }
+
+    /// Lists the changes made after **since**, in the order they were made,
+    /// so that a replica that was at **since** can *replay* them one by one.
+    ///
+    /// Each change has the *path* that the [`Node`] had at the time of the change (not its current *path*).
+    /// An [`Inserted`](ChangeKind::Inserted) [`Node`] is listed without its **descendants**.
+    ///
+    /// [`Node`]s that were inserted *and* removed after **since** are left out (along with the changes made to them),
+    /// because the replica never had them.
+    /// The *paths* of the other changes are adjusted as if those [`Node`]s never existed.
+    pub fn changes_since(&self, since: Version) -> Vec<(Vec<usize>, ChangeKind)> {
+        let mut changes = self
+            .log
+            .iter()
+            .filter(|change| change.version > since)
+            .map(|change| Some((change.path.clone(), change.kind)))
+            .collect::<Vec<_>>();
+
+        for (removed, change) in self.log.iter().filter(|change| change.version > since).enumerate() {
+            if change.kind != ChangeKind::Removed || change.inserted <= since {
+                continue;
+            }
+            // The Node was not in the Tree at `since`, so drop the removal and walk back to the insertion,
+            // keeping track of the Node's path at the time of each earlier change.
+            let (mut path, _) = changes[removed].take().unwrap();
+            let depth = path.len() - 1;
+            for earlier in changes[..removed].iter_mut().rev() {
+                let Some((other, kind)) = earlier else { continue };
+                if *kind == ChangeKind::Inserted && path.starts_with(other) {
+                    // Either the insertion of the Node, or of the subtree the Node was in (which stays).
+                    if *other == path {
+                        *earlier = None;
+                    }
+                    break;
+                }
+                if *kind == ChangeKind::Removed && other.len() <= path.len() {
+                    let last = other.len() - 1;
+                    if other[..last] == path[..last] && other[last] <= path[last] {
+                        // A previous sibling of the Node (or of an ancestor) was still there before it was removed.
+                        path[last] += 1;
+                        continue;
+                    }
+                }
+                if other.starts_with(&path) {
+                    // A change to the Node or one of its descendants.
+                    *earlier = None;
+                } else if other.len() > depth && other[..depth] == path[..depth] && other[depth] > path[depth] {
+                    // The Node was before this one, so it shifted it.
+                    other[depth] -= 1;
+                }
+            }
+        }
+
+        changes.into_iter().flatten().collect()
+    }
+}
+impl<T> Debug for VersionedTree<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VersionedTree")
+            .field("version", &self.version())
+            .field("tree", &self.tree)
+            .finish()
+    }
+}
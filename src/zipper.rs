@@ -0,0 +1,149 @@
+use super::*;
+
+/// Edits a [`Tree`] that it owns around a *focused* [`Node`], which is moved with [`down()`](Self::down()), [`up()`](Self::up()),
+/// [`left()`](Self::left()) and [`right()`](Self::right()), without having to hold pointers to the [`Node`]s.
+/// Obtained by calling [`Tree::zipper()`].
+///
+/// The focus starts at the **root**, and the [`Tree`] is given back by [`Self::finish()`].
+///
+/// # Example
+/// ```
+/// # use tree_struct::Node;
+/// let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('c'))).child(Node::builder('d')).build();
+/// let mut zipper = tree.zipper();
+///
+/// assert!(zipper.down(0) && zipper.down(0));
+/// zipper.replace(Node::builder('x').build());
+/// assert!(zipper.up() && zipper.right());
+/// zipper.insert_sibling(Node::builder('e').build()).unwrap();
+/// zipper.focus_mut().replace_content('y');
+///
+/// let tree = zipper.finish();
+/// assert_eq!(tree.display_compact().to_string(), "a(b(x),y,e)");
+/// ```
+pub struct TreeZipper<T> {
+    tree: Tree<T>,
+    focus: NodePath,
+}
+impl<T> TreeZipper<T> {
+    /// Focuses the **root** of **tree**.
+    pub fn new(tree: Tree<T>) -> Self {
+        Self {
+            tree,
+            focus: NodePath::root(),
+        }
+    }
+
+    /// The [`NodePath`] of the *focused* [`Node`].
+    #[inline]
+    pub fn path(&self) -> &NodePath {
+        &self.focus
+    }
+    /// The *focused* [`Node`].
+    pub fn focus(&self) -> &Node<T> {
+        self.tree.get_by_path(&self.focus).expect("The focus is always in the Tree")
+    }
+    /// The *focused* [`Node`] as `mutable`, e.g. to change its **content** or add **children**.
+    pub fn focus_mut(&mut self) -> Pin<&mut Node<T>> {
+        Self::node_mut(&mut self.tree, &self.focus)
+    }
+    fn node_mut<'a>(tree: &'a mut Tree<T>, path: &[usize]) -> Pin<&'a mut Node<T>> {
+        if path.is_empty() {
+            return tree.root_mut();
+        }
+        let node = tree.get_by_path(path).expect("The focus is always in the Tree").ptr();
+        tree.borrow_descendant(node).unwrap()
+    }
+
+    /// Moves the focus to the *child* at **index**.
+    /// Returns `false` (and keeps the focus) if there is no such *child*.
+    pub fn down(&mut self, index: usize) -> bool {
+        if self.focus().get(index).is_none() {
+            return false;
+        }
+        self.focus.push(index);
+        true
+    }
+    /// Moves the focus to the **parent**.
+    /// Returns `false` (and keeps the focus) if the **root** is focused.
+    #[inline]
+    pub fn up(&mut self) -> bool {
+        self.focus.pop().is_some()
+    }
+    /// Moves the focus to the previous **sibling**.
+    /// Returns `false` (and keeps the focus) if there is no such **sibling**.
+    pub fn left(&mut self) -> bool {
+        match self.focus.pop() {
+            Some(index) if index > 0 => {
+                self.focus.push(index - 1);
+                true
+            }
+            Some(index) => {
+                self.focus.push(index);
+                false
+            }
+            None => false,
+        }
+    }
+    /// Moves the focus to the next **sibling**.
+    /// Returns `false` (and keeps the focus) if there is no such **sibling**.
+    pub fn right(&mut self) -> bool {
+        let Some(index) = self.focus.pop() else {
+            return false;
+        };
+        let exists = self.focus().get(index + 1).is_some();
+        self.focus.push(if exists { index + 1 } else { index });
+        exists
+    }
+
+    /// Replaces the *focused* [`Node`] (and its subtree) with **subtree**, which becomes the focus,
+    /// and returns the replaced subtree.
+    pub fn replace(&mut self, subtree: Tree<T>) -> Tree<T> {
+        match self.focus.split_last() {
+            Some((&index, parent)) => Self::node_mut(&mut self.tree, parent).replace_child(subtree, index),
+            None => std::mem::replace(&mut self.tree, subtree),
+        }
+    }
+    /// Inserts **sibling** right *after* the *focused* [`Node`], without moving the focus.
+    /// Returns the **sibling** back if the **root** is focused, because it can't have **siblings**.
+    pub fn insert_sibling(&mut self, sibling: Tree<T>) -> Result<(), Tree<T>> {
+        match self.focus.split_last() {
+            Some((&index, parent)) => {
+                Self::node_mut(&mut self.tree, parent).insert_child(sibling, index + 1);
+                Ok(())
+            }
+            None => Err(sibling),
+        }
+    }
+    /// Removes the *focused* [`Node`] (and its subtree), and moves the focus to its **parent**.
+    /// Returns [`None`] if the **root** is focused, because it can't be removed.
+    pub fn remove(&mut self) -> Option<Tree<T>> {
+        let node = self.focus().ptr();
+        let removed = self.tree.detach_descendant(node)?;
+        self.focus.pop();
+        Some(removed)
+    }
+
+    /// Gives back the [`Tree`] with all the edits.
+    #[inline]
+    pub fn finish(self) -> Tree<T> {
+        self.tree
+    }
+}
+impl<T> Debug for TreeZipper<T>
+where T: Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TreeZipper")
+            .field("tree", &self.tree)
+            .field("focus", &self.focus)
+            .finish()
+    }
+}
+
+impl<T> Tree<T> {
+    /// Get a [`TreeZipper`] that takes ownership of the [`Tree`] to edit it around a *focused* [`Node`].
+    #[inline]
+    pub fn zipper(self) -> TreeZipper<T> {
+        TreeZipper::new(self)
+    }
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "allocator_api")]
+#![feature(allocator_api)]
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    cell::Cell,
+    ptr::NonNull,
+    rc::Rc,
+};
+use tree_struct::Node;
+
+#[derive(Default)]
+struct Counter {
+    allocs: Cell<usize>,
+    deallocs: Cell<usize>,
+}
+struct CountingAlloc(Rc<Counter>);
+unsafe impl Allocator for CountingAlloc {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocs.set(self.0.allocs.get() + 1);
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.deallocs.set(self.0.deallocs.get() + 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn build_in() {
+    let counter = Rc::new(Counter::default());
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build_in(CountingAlloc(Rc::clone(&counter)));
+
+    // 4 Nodes and 2 (non-empty) children lists.
+    assert_eq!(counter.allocs.get(), 6);
+    assert_eq!(counter.deallocs.get(), 0);
+
+    // Nodes from different allocators can be mixed.
+    tree.root_mut().append_child(Node::builder("child d").build());
+    let target = tree.root().children()[0].ptr();
+    let detached = tree.detach_descendant(target).unwrap();
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "child b", "child d"]
+    );
+    drop(tree);
+    assert!(counter.deallocs.get() < counter.allocs.get());
+
+    // The allocator is dropped along with the last Node allocated with it.
+    drop(detached);
+    assert_eq!(counter.deallocs.get(), counter.allocs.get());
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn copies_keep_allocator() {
+    let counter = Rc::new(Counter::default());
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build_in(CountingAlloc(Rc::clone(&counter)));
+    assert_eq!(counter.allocs.get(), 6);
+
+    // Copies and conversions are allocated like the original, including the Nodes that are appended to them.
+    let mut cloned = tree.clone();
+    assert_eq!(counter.allocs.get(), 12);
+    let mapped = tree.map_ref(|content| content.len());
+    assert_eq!(counter.allocs.get(), 18);
+    drop(tree.zip(&mapped).unwrap());
+    assert_eq!(counter.allocs.get(), 24);
+    let cloned_child = cloned.root().children()[1].ptr();
+    cloned.borrow_descendant(cloned_child).unwrap().append_child(Node::builder("child d").build());
+    assert_eq!(counter.allocs.get(), 25);
+    let mapped = mapped.map(|len| len * 2);
+    assert_eq!(counter.allocs.get(), 31);
+
+    drop((tree, cloned, mapped));
+    assert_eq!(counter.deallocs.get(), counter.allocs.get());
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
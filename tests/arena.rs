@@ -0,0 +1,46 @@
+#![cfg(feature = "arena")]
+use tree_struct::{arena, diff::diff, Node, Tree};
+
+#[test]
+fn generations() {
+    let mut tree = arena::Tree::new("parent");
+    let a = tree.append_child(tree.root(), "child a").unwrap();
+    let b = tree.append_child(tree.root(), "child b").unwrap();
+    let c = tree.append_child(a, "child c").unwrap();
+    let d = tree.insert_child(tree.root(), 1, "child d").unwrap();
+    assert_eq!(tree.len(), 5);
+    assert_eq!(tree.children(tree.root()).unwrap(), [a, d, b]);
+    assert_eq!(tree.parent(c), Some(a));
+    assert_eq!(tree.parent(tree.root()), None);
+    assert!(tree.insert_child(tree.root(), 4, "child e").is_none());
+    assert!(tree.detach(tree.root()).is_none());
+
+    let detached = tree.detach(a).unwrap();
+    assert_eq!(detached.iter_bfs().map(|id| detached[id]).collect::<Vec<_>>(), ["child a", "child c"]);
+    assert_eq!(tree.len(), 3);
+    assert!(!tree.contains(a) && !tree.contains(c));
+
+    // The slots of the detached Nodes are reused, but the stale ids don't find the new Nodes.
+    let e = tree.append_child(d, "child e").unwrap();
+    let f = tree.append_child(d, "child f").unwrap();
+    assert_eq!(tree.len(), 5);
+    assert!(tree.get(a).is_none() && tree.get(c).is_none());
+    assert!(tree.append_child(a, "child g").is_none());
+    tree[e] = "child E";
+    assert_eq!(tree.iter_dfs().map(|id| tree[id]).collect::<Vec<_>>(), ["parent", "child d", "child E", "child f", "child b"]);
+    assert_eq!(tree.children(d).unwrap(), [e, f]);
+}
+
+#[test]
+fn convert() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let arena = arena::Tree::from(tree.clone());
+    assert_eq!(arena.len(), 4);
+    assert_eq!(arena.iter_bfs().map(|id| arena[id]).collect::<Vec<_>>(), ["parent", "child a", "child b", "child c"]);
+    assert!(diff(&Tree::from(arena), &tree).is_empty());
+}
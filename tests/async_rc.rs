@@ -0,0 +1,63 @@
+#![cfg(feature = "async")]
+use tree_struct::async_rc::{Node, Tree};
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_multi_thread().worker_threads(4).build().unwrap().block_on(future)
+}
+
+#[test]
+fn async_rc() {
+    block_on(async {
+        let tree = Node::builder(0)
+            .child(Node::builder(1)
+                .child(Node::builder(2)))
+            .build();
+        let root = tree.root();
+
+        // Append from many tasks; each one holds a guard across an await point.
+        let tasks = (10..20)
+            .map(|i| {
+                let root = root.ref_clone();
+                tokio::spawn(async move {
+                    let mut content = root.content_mut().await;
+                    tokio::task::yield_now().await;
+                    *content += 1;
+                    drop(content);
+                    root.append_child(Node::builder(i).build()).await;
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(*root.content().await, 10);
+        assert_eq!(root.children().await.len(), 11);
+
+        // A guard blocks the writers but not try_content of other Nodes.
+        let one = root.children().await[0].ref_clone();
+        let guard = one.content_mut().await;
+        assert!(one.try_content().is_none());
+        assert!(root.try_content().is_some());
+        assert_eq!(format!("{one:?}"), "Node(<locked>)");
+        drop(guard);
+        assert_eq!(format!("{one:?}"), "Node(1)");
+
+        let detached = one.detach().await.unwrap();
+        assert!(one.detach().await.is_none());
+        assert_eq!(root.children().await.len(), 10);
+        let two = detached.root().children().await[0].ref_clone();
+        assert!(two.parent().await.unwrap().is_same_as(&detached.root()));
+        root.append_child(detached).await;
+        assert!(one.parent().await.unwrap().is_same_as(&root));
+    });
+}
+
+#[test]
+#[should_panic]
+fn async_rc_cycle() {
+    block_on(async {
+        let tree = Tree::from(Node::builder(0).child(Node::builder(1)));
+        let child = tree.root().children().await[0].ref_clone();
+        child.append_child(tree).await;
+    });
+}
@@ -0,0 +1,79 @@
+use tree_struct::{CachedTree, EvictionPolicy, Node, NodeBuilder};
+
+fn tiles() -> CachedTree<String> {
+    CachedTree::new(
+        Node::builder(String::new())
+            .child(Node::builder("a".repeat(100))
+                .child(Node::builder("aa".repeat(100))))
+            .child(Node::builder("b".repeat(300)))
+            .child(Node::builder("c".repeat(100)))
+            .build(),
+    )
+}
+
+#[test]
+fn heap_size() {
+    let mut cache = tiles();
+    let node = cache.heap_size() - 700;
+    assert_eq!(cache.tree().root().children()[0].content.heap_size(), 300 + node * 2 / 5);
+
+    cache.modify(&[0, 0], |s| s.push_str(&"x".repeat(200)));
+    assert_eq!(cache.heap_size(), node + cache.get(&[0, 0]).unwrap().content.content.capacity() + 500);
+    cache.append_child(&[2], Node::builder("d".repeat(50)).build()).unwrap();
+    assert_eq!(cache.get(&[2]).unwrap().content.heap_size(), 150 + node * 2 / 5);
+    assert!(cache.append_child(&[9], Node::builder(String::new()).build()).is_err());
+    assert!(!cache.modify(&[9], |_| ()));
+}
+
+#[test]
+fn evict_least_recently_visited() {
+    let mut cache = tiles();
+    cache.iter_dfs().find(|n| n.content.content.len() == 200);
+    cache.get(&[2]);
+
+    // "b" was visited the longest time ago.
+    let total = cache.heap_size();
+    let evicted = cache.evict_until(total - 1, EvictionPolicy::LeastRecentlyVisited);
+    assert_eq!(
+        evicted.iter().map(|t| t.root().content.chars().next().unwrap()).collect::<Vec<_>>(),
+        vec!['b']
+    );
+    assert!(cache.heap_size() < total);
+
+    // Then "a" (with its child), then "c".
+    let evicted = cache.evict_until(0, EvictionPolicy::LeastRecentlyVisited);
+    assert_eq!(
+        evicted.iter().map(|t| t.root().content.chars().next().unwrap()).collect::<Vec<_>>(),
+        vec!['a', 'c']
+    );
+    assert_eq!(evicted[0].iter_bfs().count(), 2);
+    assert_eq!(cache.tree().iter_bfs().count(), 1);
+    assert_eq!(cache.heap_size(), cache.tree().root().content.heap_size());
+}
+
+#[test]
+fn evict_largest_first() {
+    let mut cache = tiles();
+    let evicted = cache.evict_until(cache.heap_size() - 1, EvictionPolicy::LargestFirst);
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].root().content, "a".repeat(100));
+    assert_eq!(
+        cache.into_tree().iter_bfs().map(|n| n.content.len()).collect::<Vec<_>>(),
+        vec![0, 300, 100]
+    );
+}
+
+#[test]
+fn deep() {
+    // Deep enough to overflow the stack if wrapping or evicting recursed.
+    const DEPTH: usize = 100_000;
+    let tree = (0..DEPTH).rev().fold(NodeBuilder::new(DEPTH), |child, n| NodeBuilder::new(n).child(child)).build();
+    let mut cache = CachedTree::new(tree);
+    let node = cache.tree().root().content.heap_size() / (DEPTH + 1);
+    assert_eq!(cache.get(&[0]).unwrap().content.heap_size(), node * DEPTH);
+
+    let evicted = cache.evict_until(node * DEPTH / 2, EvictionPolicy::LargestFirst);
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].len(), DEPTH);
+    assert_eq!(cache.heap_size(), node);
+}
@@ -0,0 +1,69 @@
+#![cfg(any(feature = "indextree", feature = "petgraph"))]
+use tree_struct::{diff::diff, Node, Tree};
+
+#[cfg(feature = "indextree")]
+#[test]
+fn indextree() {
+    use indextree::Arena;
+    use tree_struct::ArenaError;
+
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let arena = Arena::from(tree.clone());
+    let root = arena.roots().next().unwrap();
+    assert_eq!(*arena[root].get(), "parent");
+    assert_eq!(
+        root.descendants(&arena).map(|id| *arena[id].get()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child c", "child b"]
+    );
+
+    let back = Tree::try_from(arena).unwrap();
+    assert!(diff(&back, &tree).is_empty());
+
+    let mut forest = Arena::from(tree.clone());
+    let other = forest.new_node("other");
+    assert_eq!(Tree::try_from(forest.clone()), Err(ArenaError::MultipleRoots));
+    assert_eq!(Tree::try_from(Arena::<&str>::new()), Err(ArenaError::Empty));
+    assert!(Tree::from_arena(forest.clone(), other).unwrap().root().children().is_empty());
+
+    other.remove(&mut forest);
+    assert!(Tree::from_arena(forest, other).is_none());
+}
+
+#[cfg(feature = "petgraph")]
+#[test]
+fn petgraph() {
+    use petgraph::{algo::dijkstra, graph::NodeIndex};
+    use tree_struct::GraphError;
+
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let graph = tree.to_petgraph();
+    let distances = dijkstra(&graph, NodeIndex::new(0), None, |_| 1);
+    assert_eq!(distances[&NodeIndex::new(3)], 2);
+    assert!(diff(&Tree::from_petgraph(graph.clone()).unwrap(), &tree).is_empty());
+
+    let mut cycle = graph.clone();
+    let d = cycle.add_node("d");
+    let e = cycle.add_node("e");
+    cycle.add_edge(d, e, ());
+    cycle.add_edge(e, d, ());
+    assert_eq!(Tree::from_petgraph(cycle), Err(GraphError::Unreachable { node: 4 }));
+
+    let mut forest = graph.clone();
+    forest.add_node("other");
+    assert_eq!(Tree::from_petgraph(forest), Err(GraphError::MultipleRoots));
+
+    let mut looped = graph;
+    looped.add_edge(NodeIndex::new(3), NodeIndex::new(0), ());
+    assert_eq!(Tree::from_petgraph(looped), Err(GraphError::NoRoot));
+    assert_eq!(Tree::from_petgraph(petgraph::graph::DiGraph::<(), ()>::new()), Err(GraphError::Empty));
+}
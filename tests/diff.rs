@@ -0,0 +1,125 @@
+use tree_struct::{
+    diff::{diff, Edit, PatchError},
+    Node,
+};
+
+/// Turn the edits into something that can be compared.
+fn describe(edits: &[Edit<&'static str>]) -> Vec<String> {
+    edits
+        .iter()
+        .map(|edit| match edit {
+            Edit::Insert(path, tree) => format!("insert {path:?} {:?}", tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>()),
+            Edit::Delete(path) => format!("delete {path:?}"),
+            Edit::Update(path, content) => format!("update {path:?} {content}"),
+            Edit::Move(from, to) => format!("move {from:?} {to:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn edit_script() {
+    let old = Node::builder("root")
+        .child(Node::builder("a").child(Node::builder("aa")))
+        .child(Node::builder("b"))
+        .child(Node::builder("c"))
+        .child(Node::builder("d"))
+        .build();
+
+    assert!(diff(&old, &old.clone()).is_empty());
+
+    let new = Node::builder("ROOT")
+        .child(Node::builder("c"))
+        .child(Node::builder("a").child(Node::builder("AA")).child(Node::builder("ab")))
+        .child(Node::builder("x").child(Node::builder("y")))
+        .build();
+    assert_eq!(
+        describe(&diff(&old, &new)),
+        vec![
+            "update [] ROOT",
+            "move [2] [0]",
+            "update [1, 0] AA",
+            "insert [1, 1] [\"ab\"]",
+            "update [2] x",
+            "insert [2, 0] [\"y\"]",
+            "delete [3]",
+        ]
+    );
+
+    let leaf = Node::builder("root").build();
+    assert_eq!(describe(&diff(&old, &leaf)), vec!["delete [3]", "delete [2]", "delete [1]", "delete [0]"]);
+    assert_eq!(
+        describe(&diff(&leaf, &old)),
+        vec![
+            "insert [0] [\"a\", \"aa\"]",
+            "insert [1] [\"b\"]",
+            "insert [2] [\"c\"]",
+            "insert [3] [\"d\"]",
+        ]
+    );
+}
+
+#[test]
+fn apply() {
+    let old = Node::builder("root")
+        .child(Node::builder("a").child(Node::builder("aa")))
+        .child(Node::builder("b"))
+        .child(Node::builder("c"))
+        .build();
+    let new = Node::builder("ROOT")
+        .child(Node::builder("c").child(Node::builder("ca")))
+        .child(Node::builder("a"))
+        .child(Node::builder("x"))
+        .child(Node::builder("y"))
+        .build();
+
+    let mut patched = old.clone();
+    patched.apply(diff(&old, &new)).unwrap();
+    assert!(diff(&patched, &new).is_empty());
+    let child = patched.root().children()[0];
+    assert!(child.parent().unwrap().is_same_as(patched.root()));
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+
+    // Invalid patches don't modify the Tree.
+    let mut patched = old.clone();
+    let invalid = vec![
+        Edit::Update(vec![], "ROOT"),
+        Edit::Move(vec![2], vec![0]),
+        // "b" was moved to index 2 by the previous edit.
+        Edit::Insert(vec![2, 0, 0], Node::builder("z").build()),
+    ];
+    assert_eq!(patched.apply(invalid), Err(PatchError { edit: 2 }));
+    assert!(diff(&patched, &old).is_empty());
+    assert_eq!(patched.apply(vec![Edit::Delete(vec![])]), Err(PatchError { edit: 0 }));
+    assert_eq!(patched.apply(vec![Edit::Insert(vec![4], Node::builder("z").build())]), Err(PatchError { edit: 0 }));
+    // The destination is relative to the Tree after the Node was removed, so there are only 2 children left.
+    assert_eq!(patched.apply(vec![Edit::Move(vec![0], vec![3])]), Err(PatchError { edit: 0 }));
+    assert!(diff(&patched, &old).is_empty());
+}
+
+#[test]
+fn apply_uneven() {
+    // The subtrees have different shapes, so the patch is only valid if each Node is checked against its own subtree.
+    let mut tree = Node::builder("a")
+        .child(Node::builder("b").child(Node::builder("c").child(Node::builder("x"))))
+        .child(Node::builder("d").child(Node::builder("e")).child(Node::builder("f")))
+        .build();
+    tree.apply(vec![Edit::Delete(vec![0, 0, 0]), Edit::Delete(vec![1, 1])]).unwrap();
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "abcde");
+}
+
+#[test]
+fn deep() {
+    // Deep enough to overflow the stack if diffing or patching recursed.
+    const DEPTH: usize = 100_000;
+    let chain = |last: &'static str| (0..DEPTH).fold(Node::builder(last), |child, _| Node::builder("node").child(child)).build();
+    let mut old = chain("old");
+    let new = chain("new");
+
+    let edits = diff(&old, &new);
+    assert_eq!(edits.len(), 1);
+    assert!(matches!(&edits[0], Edit::Update(path, "new") if path.len() == DEPTH));
+    assert!(diff(&old, &old.clone()).is_empty());
+
+    old.apply(edits).unwrap();
+    assert_eq!(old.iter_dfs().last().unwrap().content, "new");
+}
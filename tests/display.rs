@@ -0,0 +1,151 @@
+use tree_struct::{diff::diff, BracketError, IndentStyle, IndentedError, Node, SexprError, TextError, TextFormat, Tree, TreeFormatter};
+
+#[test]
+fn display() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c"))
+            .child(Node::builder("child d\nsecond line")))
+        .child(Node::builder("child b")
+            .child(Node::builder("child e")))
+        .build();
+
+    assert_eq!(
+        tree.to_string(),
+        "\
+parent
+├── child a
+│   ├── child c
+│   └── child d
+│       second line
+└── child b
+    └── child e
+"
+    );
+
+    let formatter = TreeFormatter::new().style(IndentStyle::Spaces).indent(2).max_depth(1);
+    assert_eq!(
+        formatter.format(tree.root()).to_string(),
+        "\
+parent
+  child a
+    ...
+  child b
+    ...
+"
+    );
+    assert_eq!(formatter.format(tree.root().children()[0].children()[0]).to_string(), "child c\n");
+}
+
+#[test]
+fn from_indented() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c"))
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b")
+            .child(Node::builder("child e")))
+        .build()
+        .map(String::from);
+
+    let text = TreeFormatter::new().style(IndentStyle::Spaces).format(tree.root()).to_string();
+    let parsed = Tree::from_indented(&text).unwrap();
+    assert!(diff(&parsed, &tree).is_empty());
+
+    let parsed = Tree::from_indented_with("parent\n\tchild a\n\n\t\tchild c\n\t\tchild d\n\tchild b\n\t\tchild e", "\t").unwrap();
+    assert!(diff(&parsed, &tree).is_empty());
+
+    assert_eq!(Tree::from_indented("\n  \n"), Err(IndentedError::Empty));
+    assert_eq!(Tree::from_indented("a\n    b\nc"), Err(IndentedError::MultipleRoots { line: 3 }));
+    assert_eq!(Tree::from_indented("a\n    b\n      c"), Err(IndentedError::Misaligned { line: 3 }));
+    assert_eq!(Tree::from_indented("a\n        b"), Err(IndentedError::TooDeep { line: 2 }));
+    assert_eq!(Tree::from_indented("    a"), Err(IndentedError::TooDeep { line: 1 }));
+}
+
+#[test]
+fn sexpr() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("(c)"))
+            .child(Node::builder("\"d\\")))
+        .child(Node::builder(""))
+        .build()
+        .map(String::from);
+
+    let sexpr = tree.to_sexpr();
+    assert_eq!(sexpr, r#"(parent ("child a" "(c)" "\"d\\") "")"#);
+    assert!(diff(&Tree::from_sexpr(&sexpr).unwrap(), &tree).is_empty());
+
+    let parsed = Tree::<i32>::from_sexpr(" ( 1\n(2) (3 4 5 ) ) ").unwrap();
+    assert_eq!(parsed.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(Tree::<i32>::from_sexpr("7").unwrap().root().content, 7);
+
+    assert_eq!(Tree::<i32>::from_sexpr("(1 2"), Err(SexprError::UnexpectedEnd));
+    assert_eq!(Tree::<i32>::from_sexpr(""), Err(SexprError::UnexpectedEnd));
+    assert_eq!(Tree::<i32>::from_sexpr("(1 2))"), Err(SexprError::Unexpected { position: 5 }));
+    assert_eq!(Tree::<i32>::from_sexpr("(() 2)"), Err(SexprError::Unexpected { position: 1 }));
+    assert_eq!(Tree::<i32>::from_sexpr("1 2"), Err(SexprError::Unexpected { position: 2 }));
+    assert!(matches!(Tree::<i32>::from_sexpr("(1 x)"), Err(SexprError::Content(_))));
+}
+
+#[test]
+fn brackets() {
+    let tree = Tree::<char>::from_brackets("a(b(d e) c)").unwrap();
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<String>(), "abcde");
+    assert_eq!(tree.to_brackets(), "a(b(de)c)");
+    assert_eq!(Tree::<char>::from_brackets("a").unwrap().to_brackets(), "a");
+
+    let tree = Tree::<String>::from_brackets(" parent ( child a (child c,child d), child b ) ").unwrap();
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child b", "child c", "child d"]
+    );
+    assert!(diff(&Tree::<String>::from_brackets(&tree.to_brackets()).unwrap(), &tree).is_empty());
+
+    assert_eq!(Tree::<char>::from_brackets("a(b(d)e)c"), Err(BracketError::Unexpected { position: 8 }));
+    assert_eq!(Tree::<char>::from_brackets("a(b"), Err(BracketError::UnexpectedEnd));
+    assert_eq!(Tree::<char>::from_brackets(""), Err(BracketError::UnexpectedEnd));
+    assert_eq!(Tree::<char>::from_brackets("a)"), Err(BracketError::Unexpected { position: 1 }));
+    assert_eq!(Tree::<char>::from_brackets("(a)"), Err(BracketError::Unexpected { position: 0 }));
+    assert_eq!(Tree::<String>::from_brackets("a(b c(d))").unwrap().root().children()[0].content, "b c");
+    assert_eq!(Tree::<String>::from_brackets("a(b,,c)"), Err(BracketError::Unexpected { position: 4 }));
+    assert_eq!(Tree::<String>::from_brackets("a(b,)"), Err(BracketError::Unexpected { position: 4 }));
+    assert_eq!(Tree::<String>::from_brackets("a(b)c"), Err(BracketError::Unexpected { position: 4 }));
+    assert_eq!(Tree::<String>::from_brackets("a,b"), Err(BracketError::Unexpected { position: 1 }));
+}
+
+#[test]
+fn display_compact() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c"))
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .build();
+
+    assert_eq!(tree.display_compact().to_string(), "parent(child a(child c,child d),child b)");
+    assert_eq!(tree.root().children()[1].display_compact().to_string(), "child b");
+    assert_eq!(format!("{}", Node::builder(1).child(Node::builder(2)).build().display_compact()), "1(2)");
+}
+
+#[test]
+fn from_str() {
+    let expected = Node::builder(String::from("a"))
+        .child(Node::builder(String::from("b")).child(Node::builder(String::from("d"))))
+        .child(Node::builder(String::from("c")))
+        .build();
+
+    assert_eq!(TextFormat::detect("a\n  b\n    d\n  c"), TextFormat::Indented);
+    assert_eq!(TextFormat::detect("  (a (b d) c)\n"), TextFormat::Sexpr);
+    assert_eq!(TextFormat::detect("a(b(d), c)"), TextFormat::Brackets);
+
+    assert_eq!("a\n  b\n    d\n  c".parse::<Tree<String>>().unwrap(), expected);
+    assert_eq!("\na\n\tb\n\t\td\n\tc\n".parse::<Tree<String>>().unwrap(), expected);
+    assert_eq!("(a (b d) c)".parse::<Tree<String>>().unwrap(), expected);
+    assert_eq!(Tree::try_from("a(b(d), c)").unwrap(), expected);
+    assert_eq!(TextFormat::Brackets.parse("a").unwrap(), Node::builder(String::from("a")).build());
+
+    assert_eq!("a\n  b\n c".parse::<Tree<String>>(), Err(TextError::Indented(IndentedError::Misaligned { line: 3 })));
+    assert_eq!("(a b".parse::<Tree<String>>(), Err(TextError::Sexpr(SexprError::UnexpectedEnd)));
+    assert_eq!(Tree::try_from("a(b"), Err(TextError::Brackets(BracketError::UnexpectedEnd)));
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "ffi")]
+use std::ffi::c_void;
+use tree_struct::ffi::*;
+
+fn new(content: &str) -> TreeHandle {
+    unsafe { tree_struct_new(content.as_ptr(), content.len()) }
+}
+fn content(tree: TreeHandle, node: NodeHandle) -> &'static str {
+    let mut ptr = std::ptr::null();
+    let mut len = 0;
+    assert!(unsafe { tree_struct_content(tree, node, &mut ptr, &mut len) });
+    std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).unwrap()
+}
+
+extern "C" fn collect(node: NodeHandle, content: *const u8, len: usize, user_data: *mut c_void) -> bool {
+    let nodes = unsafe { &mut *(user_data as *mut Vec<(NodeHandle, String)>) };
+    let content = unsafe { std::slice::from_raw_parts(content, len) };
+    nodes.push((node, String::from_utf8(content.to_vec()).unwrap()));
+    true
+}
+fn collect_bfs(tree: TreeHandle) -> Vec<(NodeHandle, String)> {
+    let mut nodes = vec![];
+    tree_struct_iter_bfs(tree, Some(collect), &mut nodes as *mut _ as *mut c_void);
+    nodes
+}
+
+#[test]
+fn handles() {
+    let tree = new("parent");
+    let root = tree_struct_root(tree);
+    assert_eq!(content(tree, root), "parent");
+
+    let child_a = new("child a");
+    let child_b = new("child b");
+    let child_c = new("child c");
+    assert!(tree_struct_append_child(tree, root, child_a));
+    assert!(tree_struct_append_child(tree, root, child_b));
+    // child_a was moved into tree.
+    assert!(!tree_struct_append_child(tree, root, child_a));
+    assert!(!tree_struct_free(child_a));
+
+    let nodes = collect_bfs(tree);
+    assert!(tree_struct_append_child(tree, nodes[1].0, child_c));
+    assert_eq!(
+        collect_bfs(tree).into_iter().map(|(_, c)| c).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child b", "child c"]
+    );
+    let mut nodes: Vec<(NodeHandle, String)> = vec![];
+    assert_eq!(tree_struct_iter_dfs(tree, Some(collect), &mut nodes as *mut _ as *mut c_void), 4);
+    assert_eq!(
+        nodes.into_iter().map(|(_, c)| c).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child c", "child b"]
+    );
+
+    // Detach "child a" (and "child c").
+    let target = collect_bfs(tree)[1].0;
+    let detached = tree_struct_detach(tree, target);
+    assert_ne!(detached, NULL_TREE);
+    assert_eq!(tree_struct_root(detached), target);
+    assert_eq!(collect_bfs(detached).len(), 2);
+    // Stale handles make functions fail instead of causing UB.
+    assert_eq!(tree_struct_detach(tree, target), NULL_TREE);
+    assert_eq!(tree_struct_detach(tree, root), NULL_TREE);
+    assert_eq!(tree_struct_detach(tree, NULL_NODE), NULL_TREE);
+
+    assert!(tree_struct_free(detached));
+    // The freed Nodes' handles are not reused by new Nodes, even if they are allocated at the same addresses.
+    let reused = new("child a");
+    assert!(!collect_bfs(tree).iter().chain(&collect_bfs(reused)).any(|(node, _)| *node == target));
+    let mut ptr = std::ptr::null();
+    let mut len = 0;
+    assert!(!unsafe { tree_struct_content(reused, target, &mut ptr, &mut len) });
+    assert!(tree_struct_free(reused));
+    assert!(tree_struct_free(tree));
+    assert!(!tree_struct_free(tree));
+    assert_eq!(tree_struct_root(tree), NULL_NODE);
+    assert_eq!(tree_struct_iter_bfs(tree, Some(collect), std::ptr::null_mut()), 0);
+}
+
+#[test]
+fn null_callback() {
+    let tree = new("root");
+    assert_eq!(tree_struct_iter_bfs(tree, None, std::ptr::null_mut()), 0);
+    assert_eq!(tree_struct_iter_dfs(tree, None, std::ptr::null_mut()), 0);
+    assert!(tree_struct_free(tree));
+}
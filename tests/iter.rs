@@ -1,4 +1,4 @@
-use tree_struct::Node;
+use tree_struct::{Node, TraversalOrder};
 
 #[test]
 fn breadth_first() {
@@ -51,3 +51,121 @@ fn depth_first() {
         vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o']
     );
 }
+
+#[test]
+fn post_order() {
+    let tree =
+        Node::builder('o')
+        .child(Node::builder('g')
+            .child(Node::builder('c')
+                .child(Node::builder('a'))
+                .child(Node::builder('b')))
+            .child(Node::builder('f')
+                .child(Node::builder('d'))
+                .child(Node::builder('e'))))
+        .child(Node::builder('n')
+            .child(Node::builder('j')
+                .child(Node::builder('h'))
+                .child(Node::builder('i')))
+            .child(Node::builder('m')
+                .child(Node::builder('k'))
+                .child(Node::builder('l'))))
+        .build();
+
+    assert_eq!(
+        tree.iter(TraversalOrder::PostOrder).map(|n| n.content).collect::<Vec<_>>(),
+        vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o']
+    );
+}
+
+#[test]
+fn traversal_order() {
+    let tree =
+        Node::builder('a')
+        .child(Node::builder('b')
+            .child(Node::builder('d'))
+            .child(Node::builder('e')
+                .child(Node::builder('h'))))
+        .child(Node::builder('c')
+            .child(Node::builder('f'))
+            .child(Node::builder('g')))
+        .build();
+    let contents = |order| tree.iter(order).map(|n| n.content).collect::<String>();
+
+    assert_eq!(contents(TraversalOrder::BreadthFirst), tree.iter_bfs().map(|n| n.content).collect::<String>());
+    assert_eq!(contents(TraversalOrder::DepthFirst), tree.iter_dfs().map(|n| n.content).collect::<String>());
+    assert_eq!(contents(TraversalOrder::PostOrder), "dhebfgca");
+    // Siblings are visited from left to right, regardless of their parent.
+    assert_eq!(contents(TraversalOrder::Level(0)), "a");
+    assert_eq!(contents(TraversalOrder::Level(1)), "bc");
+    assert_eq!(contents(TraversalOrder::Level(2)), "defg");
+    assert_eq!(contents(TraversalOrder::Level(3)), "h");
+    assert_eq!(contents(TraversalOrder::Level(4)), "");
+    // Order is deterministic.
+    assert_eq!(contents(TraversalOrder::PostOrder), contents(TraversalOrder::PostOrder));
+    // Works on subtrees.
+    let subtree = tree.root().children()[0];
+    assert_eq!(subtree.iter(TraversalOrder::PostOrder).map(|n| n.content).collect::<String>(), "dheb");
+}
+
+#[test]
+fn into_post_order() {
+    let tree =
+        Node::builder('g')
+        .child(Node::builder('c')
+            .child(Node::builder('a'))
+            .child(Node::builder('b')))
+        .child(Node::builder('f')
+            .child(Node::builder('d'))
+            .child(Node::builder('e')))
+        .build();
+    let expected = tree.iter(TraversalOrder::PostOrder).map(|n| n.content).collect::<Vec<_>>();
+
+    let consumed = tree.into_subtrees_postorder().collect::<Vec<_>>();
+    assert_eq!(consumed.iter().map(|(_, c)| *c).collect::<Vec<_>>(), expected);
+    assert_eq!(
+        consumed.into_iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        vec![vec![0, 0], vec![0, 1], vec![0], vec![1, 0], vec![1, 1], vec![1], vec![]]
+    );
+}
+
+#[test]
+fn fold_bottom_up() {
+    let tree = Node::builder(1)
+        .child(Node::builder(2)
+            .child(Node::builder(4))
+            .child(Node::builder(5)))
+        .child(Node::builder(3))
+        .build();
+
+    // Subtree sums
+    assert_eq!(tree.fold_bottom_up(|n, children: Vec<i32>| n + children.iter().sum::<i32>()), 15);
+    // Height
+    assert_eq!(tree.fold_bottom_up(|_, children: Vec<usize>| children.into_iter().max().map_or(0, |h| h + 1)), 2);
+    // Rebuild as a string
+    assert_eq!(
+        tree.fold_bottom_up(|n, children: Vec<String>| if children.is_empty() {
+            n.to_string()
+        } else {
+            format!("{n}({})", children.join(" "))
+        }),
+        "1(2(4 5) 3)"
+    );
+    let mut visited = vec![];
+    tree.root().children()[0].fold_bottom_up(|n, _: Vec<()>| visited.push(*n));
+    assert_eq!(visited, vec![4, 5, 2]);
+}
+
+#[test]
+fn into_vec() {
+    let tree = Node::builder(String::from("a"))
+        .child(Node::builder(String::from("b"))
+            .child(Node::builder(String::from("d")))
+            .child(Node::builder(String::from("e"))))
+        .child(Node::builder(String::from("c"))
+            .child(Node::builder(String::from("f"))))
+        .build();
+
+    assert_eq!(tree.clone().into_vec_bfs(), vec!["a", "b", "c", "d", "e", "f"]);
+    assert_eq!(tree.into_vec_dfs(), vec!["a", "b", "d", "e", "c", "f"]);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "keyed")]
+use tree_struct::keyed::Tree;
+
+#[test]
+fn keyed() {
+    let mut tree = Tree::new(0);
+    tree.insert_child("b", Tree::new(2));
+    tree.insert_child("a", Tree::new(1));
+    assert_eq!(tree.root_mut().child_or_insert_with("a", || unreachable!()).content, 1);
+    tree.get_child_mut("a").unwrap().child_or_insert_with("c", || 3);
+    assert_eq!(tree.child_count(), 2);
+    assert_eq!(tree.len(), 4);
+
+    // The children are in the order of their keys.
+    assert_eq!(tree.children().map(|(key, child)| (*key, child.content)).collect::<Vec<_>>(), [("a", 1), ("b", 2)]);
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), [0, 1, 3, 2]);
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(tree.get_path(["a", "c"]).unwrap().content, 3);
+    assert!(tree.get_path(["b", "c"]).is_none());
+    tree.get_path_mut(["a", "c"]).unwrap().content = 30;
+    assert_eq!(tree["a"]["c"].content, 30);
+
+    // Inserting with an existing key replaces the child.
+    let replaced = tree.insert_child("a", Tree::new(10)).unwrap();
+    assert_eq!(replaced.len(), 2);
+    assert_eq!(tree["a"].content, 10);
+    let removed = tree.remove_child("b").unwrap();
+    assert_eq!(removed.content, 2);
+    assert!(tree.remove_child("b").is_none());
+    assert_eq!(tree.len(), 2);
+
+    // Deep Trees are dropped without recursion.
+    let mut deep = Tree::new(0);
+    let mut node = deep.root_mut();
+    for i in 1..100_000 {
+        node = node.child_or_insert_with(i, || i);
+    }
+    assert_eq!(deep.iter_dfs().count(), 100_000);
+    drop(deep);
+}
@@ -1,4 +1,4 @@
-use tree_struct::Node;
+use tree_struct::{diff::diff, ChildEntry, DecodeError, Descend, FlatError, MergeAction, Node, NodeBuilder, NodePath, StaticNode, Tree, TreeError, TreeStats};
 
 #[test]
 fn siblings() {
@@ -62,6 +62,34 @@ fn clone() {
     assert!(clone.parent().is_none());
 }
 
+#[test]
+fn deep() {
+    // Deep enough to overflow the stack if building, cloning or dropping recursed.
+    const DEPTH: usize = 100_000;
+    let tree = (0..DEPTH).rev().fold(NodeBuilder::new(DEPTH), |child, n| NodeBuilder::new(n).child(child)).build();
+    assert_eq!(tree.len(), DEPTH + 1);
+
+    let cloned = tree.root().clone_deep();
+    assert_eq!(cloned.len(), DEPTH + 1);
+    assert_eq!(tree.iter_dfs().last().unwrap().parent().unwrap().content, DEPTH - 1);
+    assert_eq!(cloned.iter_dfs().last().unwrap().parent().unwrap().content, DEPTH - 1);
+
+    let zipped = tree.zip(&cloned).unwrap();
+    assert_eq!(zipped.len(), DEPTH + 1);
+    assert!(zipped.iter_dfs().all(|n| n.content.0 == n.content.1));
+    drop(zipped);
+
+    let doubled = tree.map_ref(|n| n * 2);
+    assert_eq!(doubled.len(), DEPTH + 1);
+    assert_eq!(doubled.iter_dfs().last().unwrap().parent().unwrap().content, (DEPTH - 1) * 2);
+    let incremented = cloned.map(|n| n + 1);
+    assert_eq!(incremented.len(), DEPTH + 1);
+    assert_eq!(incremented.iter_dfs().last().unwrap().parent().unwrap().content, DEPTH);
+    drop(tree);
+    drop(doubled);
+    drop(incremented);
+}
+
 #[test]
 fn detach() {
     let mut tree = Node::builder("parent")
@@ -90,6 +118,27 @@ fn detach() {
     );
 }
 
+#[test]
+fn try_variants() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .build();
+    let other = Node::builder("other").child(Node::builder("other child")).build();
+
+    let root = tree.root().ptr();
+    assert_eq!(tree.try_detach_descendant(root).unwrap_err(), TreeError::NotADescendant);
+    assert_eq!(tree.try_borrow_descendant(other.root().children()[0].ptr()).unwrap_err(), TreeError::NotADescendant);
+
+    let (error, child) = tree.root_mut().try_insert_child(Node::builder("child b").build(), 2).unwrap_err();
+    assert_eq!(error, TreeError::IndexOutOfBounds { index: 2, len: 1 });
+    tree.root_mut().try_insert_child(child, 1).unwrap();
+
+    let target = tree.root().children()[1].ptr();
+    assert_eq!(tree.try_borrow_descendant(target).unwrap().content, "child b");
+    assert!(tree.try_detach_descendant(target).unwrap().root().is_same_as(target));
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), ["parent", "child a"]);
+}
+
 #[test]
 fn append_child() {
     let mut tree = Node::builder("parent")
@@ -138,6 +187,684 @@ fn append_child() {
     );
 }
 
+#[test]
+fn replace_child() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b")
+            .child(Node::builder("child d")))
+        .child(Node::builder("child c"))
+        .build();
+
+    let new = Node::builder("child e")
+        .child(Node::builder("child f"))
+        .build();
+    let target = tree.root().children()[1].ptr();
+    let replaced = tree.root_mut().replace_child(new, 1);
+    assert!(replaced.root().is_same_as(target));
+    assert!(replaced.root().parent().is_none());
+    assert_eq!(
+        replaced,
+        Node::builder("child b")
+            .child(Node::builder("child d"))
+            .build()
+    );
+
+    let child = tree.root().children()[1];
+    assert!(child.parent().unwrap().is_same_as(tree.root()));
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+    assert_eq!(
+        tree,
+        Node::builder("parent")
+            .child(Node::builder("child a"))
+            .child(Node::builder("child e")
+                .child(Node::builder("child f")))
+            .child(Node::builder("child c"))
+            .build()
+    );
+}
+
+#[test]
+fn append_children() {
+    let mut tree = Node::builder(0).child(Node::builder(1)).build();
+
+    tree.root_mut().append_children((2..4).map(|n| Node::builder(n).child(Node::builder(n * 10)).build()));
+    tree.root_mut().extend([Node::builder(4).build()]);
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4, 20, 30]
+    );
+    assert!(tree.root().children().iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+    let child = tree.root().children()[1];
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+}
+
+#[test]
+fn children_capacity() {
+    let mut builder = NodeBuilder::with_children_capacity(0, 100);
+    assert!(builder.children.capacity() >= 100);
+    builder.children.extend((1..4).map(Node::builder));
+    let mut tree = builder.build();
+
+    tree.root_mut().reserve_children(10);
+    tree.root_mut().append_children((4..14).map(|n| Node::builder(n).build()));
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), (0..14).collect::<Vec<_>>());
+    assert!(tree.root().children_iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+}
+
+#[test]
+fn merge() {
+    let base = || {
+        Node::builder(("root", 0))
+            .child(Node::builder(("a", 1)).child(Node::builder(("aa", 2))))
+            .child(Node::builder(("b", 3)))
+            .build()
+    };
+    let overlay = || {
+        Node::builder(("root", 10))
+            .child(Node::builder(("b", 13)).child(Node::builder(("bb", 14))))
+            .child(Node::builder(("a", 11)).child(Node::builder(("ab", 12))))
+            .child(Node::builder(("c", 15)))
+            .build()
+    };
+    let contents = |tree: &Tree<(&'static str, i32)>| tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>();
+
+    // By position, keeping the base's keys.
+    let mut tree = base();
+    tree.merge(overlay(), |a, b| {
+        a.1 += b.1;
+        MergeAction::Combine
+    });
+    assert_eq!(
+        contents(&tree),
+        vec![("root", 10), ("a", 14), ("aa", 16), ("b", 14), ("ab", 12), ("c", 15)]
+    );
+
+    // By key, with different actions.
+    let mut tree = base();
+    tree.merge_by_key(overlay(), |(key, _)| *key, |a, b| {
+        *a = b;
+        match b.0 {
+            "a" => MergeAction::Keep,
+            "b" => MergeAction::Replace,
+            _ => MergeAction::Combine,
+        }
+    });
+    assert_eq!(
+        contents(&tree),
+        vec![("root", 10), ("a", 11), ("aa", 2), ("b", 13), ("bb", 14), ("c", 15)]
+    );
+    let b = tree.root().children()[1];
+    assert!(b.parent().unwrap().is_same_as(tree.root()));
+    assert!(b.children()[0].parent().unwrap().is_same_as(b));
+}
+
+#[test]
+fn graft() {
+    let mut tree = Node::builder("root").child(Node::builder("a")).build();
+    let parse = || {
+        Node::builder("other")
+            .child(Node::builder("b").child(Node::builder("bb")))
+            .child(Node::builder("c"))
+            .build()
+    };
+
+    tree.root_mut().graft(parse(), false);
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "a", "b", "c", "bb"]
+    );
+    assert!(tree.root().children().iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+
+    tree.root_mut().graft(parse(), true);
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "a", "b", "c", "other", "bb", "b", "c", "bb"]
+    );
+    let other = tree.root().children()[3];
+    assert!(other.parent().unwrap().is_same_as(tree.root()));
+    assert!(other.children()[0].parent().unwrap().is_same_as(other));
+}
+
+#[test]
+fn insert_child_relative() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("b"))
+        .child(Node::builder("d"))
+        .build();
+    let b = tree.root().children()[0].ptr();
+    let d = tree.root().children()[1].ptr();
+
+    tree.root_mut().insert_child_before(b, Node::builder("a").build()).unwrap();
+    tree.root_mut().insert_child_after(b, Node::builder("c").build()).unwrap();
+    tree.root_mut().insert_child_after(d, Node::builder("e").build()).unwrap();
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "a", "b", "c", "d", "e"]
+    );
+    assert!(tree.root().children().iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+
+    // Not a child
+    let root = tree.root().ptr();
+    let returned = tree.root_mut().insert_child_before(root, Node::builder("x").build()).unwrap_err();
+    assert_eq!(returned.root().content, "x");
+    assert_eq!(tree.root().children().len(), 5);
+}
+
+#[test]
+fn replace_content() {
+    let mut tree = Node::builder(String::from("parent"))
+        .child(Node::builder(String::from("child")))
+        .build();
+    let child = tree.root().children()[0].ptr();
+
+    assert_eq!(tree.root_mut().replace_content(String::from("root")), "parent");
+    assert_eq!(tree.borrow_descendant(child).unwrap().take_content(), "child");
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(),
+        vec!["root", ""]
+    );
+    assert!(tree.root().children()[0].is_same_as(child));
+}
+
+#[test]
+fn pop_child() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("a"))
+        .child(Node::builder("b").child(Node::builder("bb")))
+        .build();
+
+    let popped = tree.root_mut().pop_child().unwrap();
+    assert_eq!(popped.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["b", "bb"]);
+    assert!(popped.root().parent().is_none());
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["parent", "a"]);
+
+    tree.root_mut().append_child(popped);
+    assert_eq!(tree.root().children()[1].content, "b");
+    assert!(tree.root_mut().pop_child().is_some());
+    assert!(tree.root_mut().pop_child().is_some());
+    assert!(tree.root_mut().pop_child().is_none());
+}
+
+#[test]
+fn clear_children() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("a").child(Node::builder("aa")))
+        .child(Node::builder("b"))
+        .build();
+
+    let children = tree.root_mut().clear_children();
+    assert!(tree.root().children().is_empty());
+    assert_eq!(
+        children.iter().map(|c| c.root().content).collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+    assert!(children.iter().all(|c| c.root().parent().is_none()));
+    let aa = children[0].root().children()[0];
+    assert!(aa.parent().unwrap().is_same_as(children[0].root()));
+}
+
+#[test]
+fn splice_children() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("a"))
+        .child(Node::builder("b").child(Node::builder("bb")))
+        .child(Node::builder("c"))
+        .build();
+
+    let removed = tree.root_mut().splice_children(
+        1..2,
+        [Node::builder("x").build(), Node::builder("y").child(Node::builder("yy")).build()],
+    );
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "a", "x", "y", "c", "yy"]
+    );
+    assert!(tree.root().children().iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+    assert_eq!(removed.len(), 1);
+    assert!(removed[0].root().parent().is_none());
+    assert_eq!(removed[0].iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["b", "bb"]);
+
+    // Only insert / only remove
+    assert!(tree.root_mut().splice_children(0..0, [Node::builder("first").build()]).is_empty());
+    assert_eq!(tree.root_mut().splice_children(3.., []).len(), 2);
+    assert_eq!(
+        tree.root().children().iter().map(|c| c.content).collect::<Vec<_>>(),
+        vec!["first", "a", "x"]
+    );
+}
+
+#[test]
+fn split_children_off() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("a"))
+        .child(Node::builder("b"))
+        .child(Node::builder("c").child(Node::builder("cc")))
+        .build();
+
+    let split = tree.root_mut().split_children_off(1);
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec!["parent", "a"]);
+    assert_eq!(
+        split.iter().map(|c| c.root().content).collect::<Vec<_>>(),
+        vec!["b", "c"]
+    );
+    assert!(split.iter().all(|c| c.root().parent().is_none()));
+    assert!(split[1].root().children()[0].parent().unwrap().is_same_as(split[1].root()));
+    assert!(tree.root_mut().split_children_off(1).is_empty());
+}
+
+#[test]
+fn move_descendant() {
+    let mut tree = Node::builder("root")
+        .child(Node::builder("a")
+            .child(Node::builder("aa"))
+            .child(Node::builder("ab")))
+        .child(Node::builder("b"))
+        .build();
+    let find = |tree: &tree_struct::Tree<&'static str>, content| tree.iter_bfs().find(|n| n.content == content).unwrap().ptr();
+    let (root, a, aa, ab, b) = (tree.root().ptr(), find(&tree, "a"), find(&tree, "aa"), find(&tree, "ab"), find(&tree, "b"));
+    let dfs = |tree: &tree_struct::Tree<&'static str>| tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>();
+
+    // To another parent
+    assert!(tree.move_descendant(aa, b, 0));
+    assert_eq!(dfs(&tree), vec!["root", "a", "ab", "b", "aa"]);
+    assert!(unsafe { aa.as_ref() }.parent().unwrap().is_same_as(b));
+    // Within the same parent
+    assert!(tree.move_descendant(a, root, 1));
+    assert_eq!(dfs(&tree), vec!["root", "b", "aa", "a", "ab"]);
+    // Out of bounds
+    assert!(!tree.move_descendant(a, root, 2));
+    assert!(!tree.move_descendant(ab, b, 2));
+    // Into its own subtree
+    assert!(!tree.move_descendant(a, ab, 0));
+    assert!(!tree.move_descendant(a, a, 0));
+    // The root can't be moved
+    assert!(!tree.move_descendant(root, a, 0));
+    assert_eq!(dfs(&tree), vec!["root", "b", "aa", "a", "ab"]);
+}
+
+#[test]
+fn remove_promote() {
+    let mut tree = Node::builder("root")
+        .child(Node::builder("a"))
+        .child(Node::builder("wrapper")
+            .child(Node::builder("b").child(Node::builder("bb")))
+            .child(Node::builder("c")))
+        .child(Node::builder("d"))
+        .build();
+    let wrapper = tree.root().children()[1].ptr();
+    let leaf = tree.iter_bfs().find(|n| n.content == "bb").unwrap().ptr();
+
+    assert_eq!(tree.remove_promote(wrapper), Some("wrapper"));
+    assert_eq!(
+        tree.root().children().iter().map(|c| c.content).collect::<Vec<_>>(),
+        vec!["a", "b", "c", "d"]
+    );
+    assert!(tree.root().children().iter().all(|c| c.parent().unwrap().is_same_as(tree.root())));
+
+    // Leaf
+    assert_eq!(tree.remove_promote(leaf), Some("bb"));
+    assert_eq!(tree.iter_bfs().count(), 5);
+    // Root
+    let root = tree.root().ptr();
+    assert_eq!(tree.remove_promote(root), None);
+}
+
+#[test]
+fn swap_subtrees() {
+    let mut tree = Node::builder("root")
+        .child(Node::builder("a")
+            .child(Node::builder("aa").child(Node::builder("aaa")))
+            .child(Node::builder("ab")))
+        .child(Node::builder("b")
+            .child(Node::builder("ba")))
+        .build();
+    let find = |content| tree.iter_bfs().find(|n| n.content == content).unwrap().ptr();
+    let (aa, ab, ba, a) = (find("aa"), find("ab"), find("ba"), find("a"));
+
+    assert!(tree.swap_subtrees(aa, ba));
+    assert_eq!(
+        tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "a", "ba", "ab", "b", "aa", "aaa"]
+    );
+    let b = tree.root().children()[1];
+    assert!(b.children()[0].is_same_as(aa));
+    assert!(b.children()[0].parent().unwrap().is_same_as(b));
+    assert!(b.children()[0].children()[0].parent().unwrap().is_same_as(aa));
+    assert!(tree.root().children()[0].children()[0].parent().unwrap().is_same_as(a));
+
+    // Same parent
+    assert!(tree.swap_subtrees(ba, ab));
+    assert_eq!(
+        tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "a", "ab", "ba", "b", "aa", "aaa"]
+    );
+
+    // Overlapping
+    let root = tree.root().ptr();
+    assert!(!tree.swap_subtrees(a, ab));
+    assert!(!tree.swap_subtrees(ab, a));
+    assert!(!tree.swap_subtrees(a, a));
+    assert!(!tree.swap_subtrees(root, a));
+    assert_eq!(
+        tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "a", "ab", "ba", "b", "aa", "aaa"]
+    );
+}
+
+#[test]
+fn rotate() {
+    // A binary search tree.
+    let mut tree = Node::builder(4)
+        .child(Node::builder(2)
+            .child(Node::builder(1))
+            .child(Node::builder(3)))
+        .child(Node::builder(6)
+            .child(Node::builder(5))
+            .child(Node::builder(7)))
+        .build();
+    fn in_order(node: &Node<i32>) -> Vec<i32> {
+        let children = node.children();
+        match children.len() {
+            0 => vec![node.content],
+            1 => [in_order(children[0]), vec![node.content]].concat(),
+            _ => [in_order(children[0]), vec![node.content], in_order(children[1])].concat(),
+        }
+    }
+    fn check_parents(node: &Node<i32>) {
+        for child in node.children().iter() {
+            assert!(child.parent().unwrap().is_same_as(node));
+            check_parents(child);
+        }
+    }
+
+    assert!(tree.rotate_right());
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![2, 1, 4, 3, 6, 5, 7]);
+    assert!(tree.root().parent().is_none());
+    assert_eq!(in_order(tree.root()), vec![1, 2, 3, 4, 5, 6, 7]);
+    check_parents(tree.root());
+
+    assert!(tree.rotate_left());
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![4, 2, 6, 1, 3, 5, 7]);
+    check_parents(tree.root());
+
+    assert!(tree.root_mut().rotate_left(1));
+    assert_eq!(tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![4, 2, 7, 1, 3, 6, 5]);
+    assert_eq!(in_order(tree.root()), vec![1, 2, 3, 4, 5, 6, 7]);
+    check_parents(tree.root());
+
+    let leaf_parent = tree.root().children()[0].ptr();
+    assert!(!tree.borrow_descendant(leaf_parent).unwrap().rotate_right(0));
+}
+
+#[test]
+fn swap_children() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .child(Node::builder("child c"))
+        .build();
+    let child_a = tree.root().children()[0].ptr();
+
+    tree.root_mut().swap_children(0, 2);
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "child c", "child b", "child a", "child d"]
+    );
+    assert!(tree.root().children()[2].is_same_as(child_a));
+    assert!(tree.root().children()[2].parent().unwrap().is_same_as(tree.root()));
+}
+
+#[test]
+fn descend_by() {
+    // A trie with the words "to", "tea", and "ted".
+    let tree = Node::builder('\0')
+        .child(Node::builder('t')
+            .child(Node::builder('o'))
+            .child(Node::builder('e')
+                .child(Node::builder('a'))
+                .child(Node::builder('d'))))
+        .build();
+    let lookup = |word: &str| {
+        let mut chars = word.chars();
+        let mut depth = 0;
+        let node = tree.descend_by(|_, mut children| {
+            let Some(c) = chars.next() else {
+                return Descend::Stop;
+            };
+            match children.position(|child| child.content == c) {
+                Some(index) => {
+                    depth += 1;
+                    Descend::Child(index)
+                },
+                None => Descend::Stop,
+            }
+        });
+        (node.content, depth)
+    };
+
+    assert_eq!(lookup("ted"), ('d', 3));
+    assert_eq!(lookup("to"), ('o', 2));
+    assert_eq!(lookup("tx"), ('t', 1));
+    assert_eq!(lookup(""), ('\0', 0));
+    assert!(tree.root().descend_by(|_, _| Descend::Stop).is_same_as(tree.root()));
+}
+
+#[test]
+fn children_iter() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .child(Node::builder("child c"))
+        .build();
+    let root = tree.root();
+
+    let children = root.children_iter();
+    assert_eq!(children.len(), 3);
+    assert!(children.clone().zip(root.children().iter()).all(|(a, b)| a.is_same_as(*b)));
+    assert_eq!(children.rev().map(|n| n.content).collect::<Vec<_>>(), ["child c", "child b", "child a"]);
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), ["parent", "child a", "child d", "child b", "child c"]);
+}
+
+#[test]
+fn map() {
+    let tree = Node::builder("1")
+        .child(Node::builder("2").child(Node::builder("4")))
+        .child(Node::builder("3"))
+        .build();
+
+    let mut order = vec![];
+    let parsed = tree.map_ref(|s| {
+        order.push(*s);
+        s.parse::<i32>().unwrap()
+    });
+    assert_eq!(order, vec!["1", "2", "4", "3"]);
+    assert_eq!(parsed.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+    let doubled = parsed.map(|n| n * 2);
+    assert_eq!(doubled.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+    let child = doubled.root().children()[0];
+    assert!(child.parent().unwrap().is_same_as(doubled.root()));
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+}
+
+#[test]
+fn zip() {
+    let expected = Node::builder("a")
+        .child(Node::builder("b").child(Node::builder("d")))
+        .child(Node::builder("c"))
+        .build();
+    let actual = Node::builder(String::from("a"))
+        .child(Node::builder(String::from("B")).child(Node::builder(String::from("d"))))
+        .child(Node::builder(String::from("c")))
+        .build();
+
+    let zipped = expected.zip(&actual).unwrap();
+    assert_eq!(
+        zipped
+            .iter_dfs()
+            .filter(|n| *n.content.0 != n.content.1)
+            .map(|n| n.content)
+            .collect::<Vec<_>>(),
+        vec![(&"b", &String::from("B"))]
+    );
+    assert_eq!(zipped.stats(), expected.stats());
+
+    let mut different = actual.clone();
+    let c = different.root().children()[1].ptr();
+    different.borrow_descendant(c).unwrap().append_child(Node::builder(String::from("e")).build());
+    let error = expected.zip(&different).unwrap_err();
+    assert_eq!(error.path, vec![1]);
+    assert_eq!(error.to_string(), "the trees have a different number of children at path [1]");
+    different.root_mut().pop_child();
+    assert_eq!(expected.zip(&different).unwrap_err().path, vec![]);
+}
+
+#[test]
+fn fill_like() {
+    let shape = Node::builder("root")
+        .child(Node::builder("a")
+            .child(Node::builder("aa"))
+            .child(Node::builder("ab")))
+        .child(Node::builder("b"))
+        .build();
+
+    let filled = Tree::fill_like(&shape, |content, path| format!("{content}@{path:?}"));
+    assert_eq!(
+        filled.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(),
+        vec!["root@[]", "a@[0]", "b@[1]", "aa@[0, 0]", "ab@[0, 1]"]
+    );
+    assert_eq!(filled.stats(), shape.stats());
+    let child = filled.root().children()[0];
+    assert!(child.parent().unwrap().is_same_as(filled.root()));
+    assert!(child.children()[1].parent().unwrap().is_same_as(child));
+    assert_eq!(filled.root().children()[0].len(), 3);
+}
+
+#[test]
+fn reverse_children() {
+    let mut tree = Node::builder('a')
+        .child(Node::builder('b')
+            .child(Node::builder('d'))
+            .child(Node::builder('e')))
+        .child(Node::builder('c'))
+        .build();
+
+    tree.root_mut().reverse_children();
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "acbde");
+    tree.root_mut().reverse_children();
+    tree.root_mut().reverse_recursive();
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "acbed");
+    let b = tree.root().children()[1];
+    assert!(b.children()[0].parent().unwrap().is_same_as(b));
+}
+
+#[test]
+fn truncate_depth() {
+    let tree = Node::builder(0)
+        .child(Node::builder(1)
+            .child(Node::builder(2).child(Node::builder(3))))
+        .child(Node::builder(1).child(Node::builder(2)))
+        .build();
+
+    let mut truncated = tree.clone();
+    truncated.truncate_depth(5);
+    assert_eq!(truncated.stats(), tree.stats());
+    truncated.truncate_depth(1);
+    assert_eq!(truncated.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![0, 1, 1]);
+    truncated.truncate_depth(0);
+    assert_eq!(truncated.iter_bfs().count(), 1);
+
+    let mut truncated = tree.clone();
+    truncated.root_mut().truncate_depth(2);
+    assert_eq!(truncated.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), vec![0, 1, 1, 2, 2]);
+}
+
+#[test]
+fn retain() {
+    let mut tree = Node::builder("root")
+        .child(Node::builder("keep")
+            .child(Node::builder("drop a"))
+            .child(Node::builder("keep")))
+        .child(Node::builder("drop b")
+            .child(Node::builder("keep")))
+        .child(Node::builder("keep"))
+        .build();
+
+    let removed = tree.retain(|content| !content.starts_with("drop"));
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["root", "keep", "keep", "keep"]
+    );
+    assert_eq!(removed.len(), 2);
+    assert_eq!(removed[1].root().content, "drop a");
+    assert_eq!(
+        removed[0].iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["drop b", "keep"]
+    );
+    assert!(removed.iter().all(|tree| tree.root().parent().is_none()));
+    let child = tree.root().children()[0];
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+}
+
+#[test]
+fn sort_children() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("c")
+            .child(Node::builder("z"))
+            .child(Node::builder("y")))
+        .child(Node::builder("a"))
+        .child(Node::builder("bb"))
+        .build();
+
+    tree.root_mut().sort_children_by(|a, b| a.cmp(b));
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "a", "bb", "c", "z", "y"]
+    );
+    // Stable sort
+    tree.root_mut().sort_children_by_key(|c| c.len());
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "a", "c", "bb", "z", "y"]
+    );
+    tree.root_mut().sort_recursive_by(|a, b| b.cmp(a));
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["parent", "c", "bb", "a", "z", "y"]
+    );
+    let child = tree.root().children()[0];
+    assert!(child.parent().unwrap().is_same_as(tree.root()));
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+}
+
+#[test]
+fn merge_adjacent_children() {
+    let mut tree = Node::builder(0)
+        .child(Node::builder(1)
+            .child(Node::builder(10)))
+        .child(Node::builder(3)
+            .child(Node::builder(30)))
+        .child(Node::builder(2))
+        .child(Node::builder(5))
+        .child(Node::builder(7))
+        .child(Node::builder(4))
+        .build();
+
+    // Merge runs of odd numbers by adding them.
+    tree.root_mut().merge_adjacent_children(|a, b| a % 2 == 1 && b % 2 == 1, |a, b| *a += b);
+    assert_eq!(
+        tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec![0, 4, 2, 12, 4, 10, 30]
+    );
+    assert_eq!(tree.root().children()[0].children().len(), 2);
+    // The absorbed children are re-parented to the survivor.
+    let survivor = tree.root().children()[0];
+    assert!(survivor.children()[1].parent().unwrap().is_same_as(survivor));
+}
+
 #[test]
 fn dangling() {
     let mut tree = Node::builder("parent")
@@ -157,3 +884,575 @@ fn dangling() {
     assert_eq!(tree.detach_descendant(grandchild), None);
     assert_eq!(tree.borrow_descendant(grandchild), None);
 }
+
+#[test]
+fn node_ref() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child")
+            .child(Node::builder("grandchild")))
+        .build();
+    let other = Node::builder("other").build();
+
+    let child = tree.root().children()[0].node_ref();
+    let grandchild = tree.root().children()[0].children()[0].node_ref();
+    assert_eq!(child, tree.root().children()[0].node_ref());
+    assert_ne!(child, grandchild);
+    assert_eq!(tree.borrow_by_ref(grandchild).unwrap().content, "grandchild");
+    assert_eq!(tree.borrow_by_ref(tree.root().node_ref()).unwrap_err(), TreeError::NotADescendant);
+    assert_eq!(tree.borrow_by_ref(other.root().node_ref()).unwrap_err(), TreeError::DanglingHandle);
+
+    drop(tree.detach_by_ref(child).unwrap());
+    // The handles are never dereferenced, so they can outlive their Nodes.
+    assert_eq!(tree.detach_by_ref(child).unwrap_err(), TreeError::DanglingHandle);
+    assert_eq!(tree.borrow_by_ref(grandchild).unwrap_err(), TreeError::DanglingHandle);
+
+    // A new Node at the same address doesn't match the old handle.
+    tree.root_mut().append_child(Node::builder("new child").build());
+    assert_eq!(tree.borrow_by_ref(child).unwrap_err(), TreeError::DanglingHandle);
+
+    // Even if the new Node has a handle too.
+    let mut tree = Node::builder("parent").build();
+    let old = Node::builder("old").build();
+    let stale = old.root().node_ref();
+    let address = old.root().ptr();
+    drop(old);
+    let new = (0..100)
+        .map(|_| Node::builder("new").build())
+        .find(|new| new.root().ptr() == address)
+        .expect("the allocator reuses the address of the dropped Node");
+    let new_ref = new.root().node_ref();
+    tree.root_mut().append_child(new);
+    assert_eq!(tree.borrow_by_ref(stale).unwrap_err(), TreeError::DanglingHandle);
+    assert_eq!(tree.borrow_by_ref(new_ref).unwrap().content, "new");
+}
+
+#[test]
+fn stats() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b")
+            .child(Node::builder("child d"))
+            .child(Node::builder("child e"))
+            .child(Node::builder("child f")))
+        .child(Node::builder("child c")
+            .child(Node::builder("child g")))
+        .build();
+
+    assert_eq!(
+        tree.stats(),
+        TreeStats {
+            nodes: 8,
+            leaves: 5,
+            height: 2,
+            max_branching: 3,
+            max_width: 4,
+        }
+    );
+    assert_eq!(
+        Node::builder("root").build().stats(),
+        TreeStats {
+            nodes: 1,
+            leaves: 1,
+            height: 0,
+            max_branching: 0,
+            max_width: 1,
+        }
+    );
+}
+
+#[test]
+fn len() {
+    // The cached size of every subtree must match the number of Nodes found by traversing it.
+    fn check(tree: &Tree<i32>, len: usize) {
+        assert_eq!(tree.len(), len);
+        assert!(tree.iter_bfs().all(|node| node.len() == node.iter_bfs().count()));
+    }
+
+    let mut tree = Node::builder(0)
+        .child(Node::builder(1)
+            .child(Node::builder(2))
+            .child(Node::builder(3)))
+        .child(Node::builder(4))
+        .build();
+    check(&tree, 5);
+    check(&tree.root().children()[0].clone_deep(), 3);
+
+    let target = tree.root().children()[1].ptr();
+    tree.borrow_descendant(target).unwrap().append_child(Node::builder(5).child(Node::builder(6)).build());
+    check(&tree, 7);
+    let detached = tree.detach_descendant(tree.root().children()[0].ptr()).unwrap();
+    check(&tree, 4);
+    check(&detached, 3);
+    tree.root_mut().insert_child(detached, 0);
+    check(&tree, 7);
+
+    let (a, b) = (tree.iter_bfs().find(|n| n.content == 2).unwrap().ptr(), tree.iter_bfs().find(|n| n.content == 5).unwrap().ptr());
+    assert!(tree.swap_subtrees(a, b));
+    check(&tree, 7);
+    let new_parent = tree.iter_bfs().find(|n| n.content == 3).unwrap().ptr();
+    assert!(tree.move_descendant(b, new_parent, 0));
+    check(&tree, 7);
+    tree.remove_promote(new_parent).unwrap();
+    check(&tree, 6);
+
+    assert!(tree.rotate_right());
+    check(&tree, 6);
+    tree.root_mut().splice_children(0..1, [Node::builder(7).build(), Node::builder(8).build()]);
+    check(&tree, 3);
+    tree.root_mut().append_children([Node::builder(9).child(Node::builder(10)).build()]);
+    check(&tree, 5);
+    tree.retain(|&n| n != 10);
+    check(&tree, 4);
+    tree.root_mut().replace_child(Node::builder(11).child(Node::builder(12)).build(), 0);
+    check(&tree, 5);
+    tree.root_mut().merge_adjacent_children(|_, _| true, |a, b| *a += b);
+    check(&tree, 3);
+    tree.root_mut().append_child(Node::builder(13).build());
+    tree.root_mut().pop_child().unwrap();
+    check(&tree, 3);
+    tree.truncate_depth(0);
+    check(&tree, 1);
+}
+
+#[test]
+fn drop_hook() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let dropped = Rc::new(RefCell::new(vec![]));
+    let hook = {
+        let dropped = Rc::clone(&dropped);
+        move |content: &mut String| {
+            content.push('!');
+            dropped.borrow_mut().push(content.clone())
+        }
+    };
+    let mut tree = Node::builder(String::from("a"))
+        .child(Node::builder(String::from("b"))
+            .child(Node::builder(String::from("c")))
+            .child(Node::builder(String::from("d"))))
+        .child(Node::builder(String::from("e")))
+        .build()
+        .with_drop_hook(hook);
+
+    // Still usable as a Tree
+    tree.root_mut().append_child(Node::builder(String::from("f")).build());
+    assert_eq!(tree.iter_bfs().count(), 6);
+    drop(tree);
+    assert_eq!(*dropped.borrow(), vec!["c!", "d!", "b!", "e!", "f!", "a!"]);
+
+    // The hook is not called after it is removed.
+    dropped.borrow_mut().clear();
+    let hook = {
+        let dropped = Rc::clone(&dropped);
+        move |content: &mut String| dropped.borrow_mut().push(content.clone())
+    };
+    let tree = Node::builder(String::from("a")).build().with_drop_hook(hook).into_tree();
+    drop(tree);
+    assert!(dropped.borrow().is_empty());
+    assert_eq!(Rc::strong_count(&dropped), 1);
+}
+
+#[test]
+fn freeze() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d"))
+            .child(Node::builder("child e")))
+        .child(Node::builder("child b"))
+        .child(Node::builder("child c")
+            .child(Node::builder("child f")))
+        .build();
+    let bfs = tree.iter_bfs().map(|n| n.content).collect::<Vec<_>>();
+    let dfs = tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>();
+
+    let frozen = tree.clone().freeze();
+    assert_eq!(frozen.len(), 7);
+    assert_eq!(frozen.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), bfs);
+    assert_eq!(frozen.iter_dfs().map(|n| *n.content()).collect::<Vec<_>>(), dfs);
+
+    let root = frozen.root();
+    assert!(root.parent().is_none());
+    let child_a = root.children().next().unwrap();
+    assert!(child_a.parent().unwrap().is_same_as(root));
+    assert_eq!(child_a.prev_sibling(), None);
+    assert_eq!(*child_a.next_sibling().unwrap().content(), "child b");
+    assert_eq!(*root.children().last().unwrap().content(), "child c");
+    assert_eq!(root.children().last().unwrap().next_sibling(), None);
+    assert_eq!(
+        child_a.children().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["child d", "child e"]
+    );
+    assert_eq!(child_a.children().last().unwrap().next_sibling(), None);
+
+    assert_eq!(frozen.thaw(), tree);
+}
+
+#[test]
+fn flat() {
+    let tree = Node::builder("a")
+        .child(Node::builder("b")
+            .child(Node::builder("d"))
+            .child(Node::builder("e")))
+        .child(Node::builder("c")
+            .child(Node::builder("f")))
+        .build();
+
+    let flat = tree.to_flat();
+    assert_eq!(
+        flat,
+        vec![(None, "a"), (Some(0), "b"), (Some(0), "c"), (Some(1), "d"), (Some(1), "e"), (Some(2), "f")]
+    );
+    let rebuilt = Tree::from_flat(flat).unwrap();
+    assert!(diff(&rebuilt, &tree).is_empty());
+    let b = rebuilt.root().children()[0];
+    assert!(b.children()[1].parent().unwrap().is_same_as(b));
+
+    assert_eq!(Tree::<char>::from_flat(vec![]), Err(FlatError::Empty));
+    assert_eq!(Tree::from_flat(vec![(Some(0), 'a')]), Err(FlatError::InvalidParent { index: 0 }));
+    assert_eq!(Tree::from_flat(vec![(None, 'a'), (None, 'b')]), Err(FlatError::InvalidParent { index: 1 }));
+    assert_eq!(Tree::from_flat(vec![(None, 'a'), (Some(1), 'b')]), Err(FlatError::InvalidParent { index: 1 }));
+}
+
+#[test]
+fn str_path() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c"))
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .build();
+    let d = tree.root().children()[0].children()[1];
+
+    assert_eq!(tree.root().str_path(), "");
+    assert_eq!(d.str_path(), "0/1");
+    assert!(tree.get_by_str_path("").unwrap().is_same_as(tree.root()));
+    assert!(tree.get_by_str_path("0/1").unwrap().is_same_as(d));
+    assert!(tree.get_by_str_path("0/2").is_none());
+    assert!(tree.get_by_str_path("0/x").is_none());
+    assert!(tree.get_by_str_path("0/").is_none());
+
+    assert_eq!(d.name_path(), "child a/child d");
+    assert!(tree.get_by_name_path("child a/child d").unwrap().is_same_as(d));
+    assert!(tree.get_by_name_path("").unwrap().is_same_as(tree.root()));
+    assert!(tree.get_by_name_path("parent/child a").is_none());
+}
+
+#[test]
+fn binary() {
+    let tree = Node::builder(String::from("parent"))
+        .child(Node::builder(String::from("child a"))
+            .child(Node::builder(String::from("child c"))))
+        .child(Node::builder(String::from("child b")))
+        .build();
+
+    let bytes = tree.to_bytes();
+    assert_eq!(&bytes[..8], b"\x02\x06parent");
+    let decoded = Tree::<String>::from_bytes(&bytes).unwrap();
+    assert!(diff(&decoded, &tree).is_empty());
+
+    // A wide Tree needs more than 1 byte for the children count.
+    let mut wide = Node::builder(0u32).build();
+    for i in 1..=300 {
+        wide.root_mut().append_child(Node::builder(i).build());
+    }
+    let decoded = Tree::<u32>::from_bytes(&wide.to_bytes()).unwrap();
+    assert_eq!(decoded.into_vec_bfs(), (0..=300).collect::<Vec<_>>());
+
+    // "child b" is the last Node, and takes 9 bytes.
+    assert_eq!(Tree::<String>::from_bytes(&bytes[..bytes.len() - 9]), Err(DecodeError::UnexpectedEnd));
+    assert_eq!(Tree::<String>::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError::Invalid { position: bytes.len() - 9 }));
+    assert_eq!(Tree::<String>::from_bytes(&[]), Err(DecodeError::UnexpectedEnd));
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(Tree::<String>::from_bytes(&trailing), Err(DecodeError::TrailingBytes { position: bytes.len() }));
+    assert_eq!(Tree::<bool>::from_bytes(&[0, 2]), Err(DecodeError::Invalid { position: 0 }));
+}
+
+#[test]
+fn static_tree() {
+    static TREE: StaticNode<&str> = tree_struct::static_tree!("parent" => [
+        "child a" => ["child c"],
+        "child b",
+    ]);
+    const LEAF: StaticNode<u8> = tree_struct::static_tree!(1);
+
+    assert_eq!(TREE.children.len(), 2);
+    assert_eq!(TREE.children[0].children[0].content, "child c");
+    assert!(LEAF.children.is_empty());
+    assert_eq!(TREE.iter_bfs().map(|n| n.content).collect::<Vec<_>>(), ["parent", "child a", "child b", "child c"]);
+
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    assert!(diff(&TREE.to_tree(), &tree).is_empty());
+    assert_eq!(LEAF.to_tree().len(), 1);
+}
+
+#[test]
+fn pointers_survive_moves() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    let target = tree.root().children()[0].children()[0].ptr();
+
+    // Moving the Tree (and its root) must not invalidate the pointers to its Nodes.
+    let mut trees = vec![tree];
+    let mut tree = trees.pop().unwrap();
+    tree.borrow_descendant(target).unwrap().append_child(Node::builder("child d").build());
+    tree.root_mut().rotate_right(0);
+
+    assert_eq!(tree.root().children()[0].children()[0].parent().unwrap().content, "child c");
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), ["parent", "child c", "child a", "child d", "child b"]);
+    assert_eq!(tree.detach_descendant(target).unwrap().len(), 3);
+    assert_eq!(tree.len(), 2);
+}
+
+#[test]
+fn index() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    assert_eq!(tree.root()[0][0].content, "child c");
+    assert_eq!(tree.root().get(1).unwrap().content, "child b");
+    assert!(tree.root().get(2).is_none());
+    assert!(tree.root()[1].get(0).is_none());
+}
+
+#[test]
+fn from_paths() {
+    let tree = [
+        (vec![], "parent"),
+        (vec![0, 0], "child c"),
+        (vec![1], "child b"),
+        (vec![0], "child a"),
+        (vec![1], "child b"),
+    ].into_iter().collect::<Tree<_>>();
+    let expected = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    assert!(diff(&tree, &expected).is_empty());
+
+    // Missing Nodes get the default content.
+    let tree = [(vec![0, 2], 5)].into_iter().collect::<Tree<i32>>();
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<Vec<_>>(), [0, 0, 0, 0, 5]);
+    assert_eq!(std::iter::empty::<(Vec<usize>, i32)>().collect::<Tree<_>>().len(), 1);
+
+    // from_paths() rejects gaps instead of filling them.
+    let tree = Tree::from_paths([(vec![], "parent"), (vec![0, 0], "child c"), (vec![1], "child b"), (vec![0], "child a")]).unwrap();
+    assert!(diff(&tree, &expected).is_empty());
+    assert_eq!(Tree::<i32>::from_paths([(vec![0, 2], 5)]).unwrap_err(), TreeError::IndexOutOfBounds { index: 2, len: 0 });
+    assert_eq!(Tree::from_paths([(vec![usize::MAX], 5)]).unwrap_err(), TreeError::IndexOutOfBounds { index: usize::MAX, len: 0 });
+}
+
+#[test]
+fn hash() {
+    use std::collections::HashSet;
+    let build = || Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    // Same contents in DFS order, but a different shape.
+    let other = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child c"))
+        .child(Node::builder("child b"))
+        .build();
+
+    assert_eq!(build(), build());
+    assert_ne!(build(), other);
+    assert_ne!(build(), Node::builder("parent").build());
+
+    let set = [build(), build(), other].into_iter().collect::<HashSet<_>>();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&build()));
+}
+
+#[test]
+fn ord() {
+    use std::collections::BTreeSet;
+    let a = Node::builder(1).child(Node::builder(2)).child(Node::builder(3)).build();
+    let b = Node::builder(1).child(Node::builder(2).child(Node::builder(3))).build();
+    let c = Node::builder(1).child(Node::builder(4)).build();
+    let d = Node::builder(1).child(Node::builder(2)).build();
+
+    // Contents are compared first, then the shape.
+    assert!(d < b && b < a && a < c);
+    let mut trees = vec![c.clone(), a.clone(), b.clone(), d.clone()];
+    trees.sort();
+    assert_eq!(trees, [d.clone(), b.clone(), a.clone(), c.clone()]);
+    assert_eq!([a.clone(), a, d].into_iter().collect::<BTreeSet<_>>().len(), 2);
+    assert_eq!(b.partial_cmp(&b.clone()), Some(std::cmp::Ordering::Equal));
+}
+
+#[test]
+fn node_path() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b")
+            .child(Node::builder("child d"))
+            .child(Node::builder("child e")))
+        .build();
+
+    let path = tree.root()[1][1].path();
+    assert_eq!(path.to_string(), "1/1");
+    assert_eq!(*path, [1, 1]);
+    assert_eq!(tree.get_by_path(&path).unwrap().content, "child e");
+    assert_eq!(tree.get_by_path(&path.parent().unwrap()).unwrap().content, "child b");
+    assert!(tree.get_by_path(&path.child(0)).is_none());
+    assert!(tree.root().path().is_root());
+
+    let prefix = NodePath::from(vec![1]);
+    assert!(path.starts_with(&prefix) && path.starts_with(&NodePath::root()));
+    assert!(!prefix.starts_with(&path));
+    assert_eq!(prefix.concat(&"0".parse().unwrap()), NodePath::from(vec![1, 0]));
+    assert!("1/x".parse::<NodePath>().is_err());
+    assert_eq!("".parse::<NodePath>().unwrap(), NodePath::root());
+
+    // Paths can be used to build Trees.
+    let tree = [(prefix.child(0), 'b'), (NodePath::root(), 'a')].into_iter().collect::<Tree<_>>();
+    assert_eq!(tree.iter_dfs().map(|n| n.content).collect::<String>(), "a\0\0b");
+}
+
+#[test]
+fn zipper() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    let mut zipper = tree.zipper();
+
+    assert!(!zipper.up() && !zipper.left() && !zipper.right());
+    assert!(!zipper.down(2));
+    assert!(zipper.down(0) && !zipper.left() && zipper.right() && !zipper.right());
+    assert_eq!(zipper.focus().content, "child b");
+    assert_eq!(zipper.path().to_string(), "1");
+
+    zipper.insert_sibling(Node::builder("child d").build()).unwrap();
+    assert!(zipper.left() && zipper.down(0));
+    let replaced = zipper.replace(Node::builder("child e").child(Node::builder("child f")).build());
+    assert_eq!(replaced.root().content, "child c");
+    assert!(zipper.down(0));
+    assert_eq!(zipper.remove().unwrap().root().content, "child f");
+    assert_eq!(zipper.focus().content, "child e");
+
+    assert!(zipper.up() && zipper.up());
+    assert!(zipper.remove().is_none());
+    assert!(zipper.insert_sibling(Node::builder("sibling").build()).is_err());
+    zipper.focus_mut().replace_content("root");
+
+    let tree = zipper.finish();
+    assert_eq!(tree.display_compact().to_string(), "root(child a(child e),child b,child d)");
+    assert_eq!(tree.len(), 5);
+}
+
+#[test]
+fn builder_children() {
+    let tree = Node::builder("parent")
+        .children(["child a", "child b"].map(Node::builder))
+        .child_content("child c")
+        .children([])
+        .build();
+    let expected = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b"))
+        .child(Node::builder("child c"))
+        .build();
+
+    assert_eq!(tree, expected);
+    assert_eq!(tree.len(), 4);
+}
+
+#[test]
+fn unfold() {
+    // Each seed is a path (as a string), and each Node has as many children as the depth it is at (up to depth 2).
+    let expand = |path: String| {
+        let depth = path.len();
+        let children = if depth < 2 { (0..=depth).map(|i| format!("{path}{i}")).collect() } else { vec![] };
+        (path, children)
+    };
+    let mut calls = vec![];
+    let dfs = Tree::unfold(String::new(), |seed| {
+        calls.push(seed.clone());
+        expand(seed)
+    });
+    assert_eq!(calls, ["", "0", "00", "01"]);
+    assert_eq!(dfs.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(), ["", "0", "00", "01"]);
+    assert_eq!(dfs.len(), 4);
+
+    // Each Node has 2 children, up to depth 2.
+    let mut calls = vec![];
+    let bfs = Tree::unfold_bfs(String::from("x"), |seed| {
+        calls.push(seed.clone());
+        let children = if seed.len() < 3 { vec![format!("{seed}0"), format!("{seed}1")] } else { vec![] };
+        (seed, children)
+    });
+    assert_eq!(calls, ["x", "x0", "x1", "x00", "x01", "x10", "x11"]);
+    assert_eq!(bfs.display_compact().to_string(), "x(x0(x00,x01),x1(x10,x11))");
+    assert_eq!(bfs.root().children()[1].len(), 3);
+
+    // The sizes of the subtrees are added up once, instead of walking up to the root for every Node.
+    const DEPTH: usize = 100_000;
+    let chain = Tree::unfold(0, |n| (n, (n < DEPTH).then_some(n + 1)));
+    assert_eq!(chain.len(), DEPTH + 1);
+    assert_eq!(chain.root().children()[0].len(), DEPTH);
+    let filled = Tree::fill_like(&chain, |_, path| path.len());
+    assert_eq!(filled.len(), DEPTH + 1);
+    assert_eq!(filled.iter_dfs().last().unwrap().content, DEPTH);
+}
+
+#[test]
+fn child_entry() {
+    let mut tree = Node::builder(String::new()).build();
+    for path in ["a/b", "a/c", "a/b/d", "e"] {
+        let mut node = tree.root_mut();
+        for segment in path.split('/') {
+            node = node.child_entry(|c| c == segment).or_insert_with(|| Node::builder(segment.to_string()).build());
+        }
+    }
+    assert_eq!(tree.display_compact().to_string(), "(a(b(d),c),e)");
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.root().children()[0].len(), 4);
+
+    let mut root = tree.root_mut();
+    assert!(matches!(root.as_mut().child_entry(|c| c == "e"), ChildEntry::Occupied(child) if child.content == "e"));
+    assert!(matches!(root.as_mut().child_entry(|c| c == "d"), ChildEntry::Vacant(parent) if parent.content.is_empty()));
+    root.as_mut().child_entry(|c| c == "e").or_insert_with(|| panic!("The child exists"));
+    let d = root.child_entry(|c| c == "d").or_insert(String::from("d"));
+    assert!(d.parent().unwrap().parent().is_none());
+    assert_eq!(tree.len(), 7);
+}
+
+#[test]
+fn meta() {
+    // Trees built without metadata don't pay for it.
+    assert_eq!(std::mem::size_of_val(Node::builder(0).build().root().meta()), 0);
+
+    let mut tree = Node::builder("a")
+        .child(Node::builder("bb").child(Node::builder("ccc")))
+        .build_with_meta(|content| content.len());
+    assert_eq!(tree.iter_bfs().map(|n| *n.meta()).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let c = tree.root().children()[0].children()[0].ptr();
+    *tree.borrow_descendant(c).unwrap().meta_mut() = 0;
+    let cloned = tree.clone();
+    assert_eq!(*cloned.root().children()[0].children()[0].meta(), 0);
+
+    // map() keeps the metadata, and map_meta() keeps the content.
+    let tree = tree.map(|content| content.to_uppercase());
+    assert_eq!(tree.iter_dfs().map(|n| *n.meta()).collect::<Vec<_>>(), vec![1, 2, 0]);
+    let tree = tree.map_meta(|content, len| content.len() == len);
+    assert_eq!(tree.iter_dfs().map(|n| *n.meta()).collect::<Vec<_>>(), vec![true, true, false]);
+    assert_eq!(tree.iter_dfs().map(|n| n.content.as_str()).collect::<String>(), "ABBCCC");
+}
@@ -0,0 +1,34 @@
+use tree_struct::{Node, NodeOps, Tree, TreeOps};
+
+/// Written once for both backends.
+fn summary<T: Clone, N: NodeOps<T>>(node: &N) -> Vec<(T, usize, bool)> {
+    node.descendants().map(|n| (n.content().clone(), n.depth(), n.is_leaf())).collect()
+}
+
+#[test]
+fn boxed() {
+    let tree = Node::builder('a').child(Node::builder('b').child(Node::builder('d'))).child(Node::builder('c')).build();
+    assert_eq!(summary(&tree.root()), [('a', 0, false), ('b', 1, false), ('d', 2, true), ('c', 1, true)]);
+    assert_eq!(tree, Tree::<char>::from_brackets("a(b(d)c)").unwrap());
+    assert_eq!(TreeOps::len(&tree), 4);
+
+    let b = NodeOps::get(&tree.root(), 0).unwrap();
+    assert_eq!(b.child_count(), 1);
+    assert!(NodeOps::parent(&b).unwrap().is_same_as(tree.root()));
+    assert_eq!(summary(&b), [('b', 1, false), ('d', 2, true)]);
+}
+
+#[test]
+#[cfg(feature = "rc")]
+fn rc() {
+    use tree_struct::rc;
+    let tree = rc::Node::builder('a')
+        .child(rc::Node::builder('b').child(rc::Node::builder('d')))
+        .child(rc::Node::builder('c'))
+        .build();
+    assert_eq!(summary(&tree.root()), [('a', 0, false), ('b', 1, false), ('d', 2, true), ('c', 1, true)]);
+    assert_eq!(TreeOps::len(&tree), 4);
+
+    let boxed = Tree::<char>::from_brackets("a(b(d)c)").unwrap();
+    assert_eq!(summary(&tree.root()), summary(&boxed.root()));
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "rayon")]
+use tree_struct::sync::Node;
+
+#[test]
+fn par_eq_hash() {
+    let build = |leaf| {
+        Node::builder(0)
+            .child(Node::builder(1)
+                .child(Node::builder(2))
+                .child(Node::builder(leaf)))
+            .child(Node::builder(4))
+            .build()
+    };
+    let (a, b, c) = (build(3), build(3), build(5));
+
+    assert!(a.par_eq(&a));
+    assert!(a.par_eq(&b));
+    assert_eq!(a.par_hash(), b.par_hash());
+    assert!(!a.par_eq(&c));
+    assert_ne!(a.par_hash(), c.par_hash());
+
+    // Same contents, different shape.
+    let flat = Node::builder(0)
+        .child(Node::builder(1))
+        .child(Node::builder(2))
+        .child(Node::builder(3))
+        .child(Node::builder(4))
+        .build();
+    assert!(!a.par_eq(&flat));
+    assert_ne!(a.par_hash(), flat.par_hash());
+}
+
+#[test]
+fn clone_deep_parallel() {
+    let tree = Node::builder(0)
+        .child(Node::builder(1)
+            .child(Node::builder(2))
+            .child(Node::builder(3)))
+        .child(Node::builder(4))
+        .build();
+
+    let cloned = tree.clone_deep_parallel();
+    assert!(cloned.par_eq(&tree));
+    assert!(!cloned.root().is_same_as(&tree.root()));
+    assert!(cloned.root().parent().is_none());
+    let child = &cloned.root().children()[0];
+    assert!(child.parent().unwrap().is_same_as(&cloned.root()));
+    assert!(child.children().iter().all(|grandchild| grandchild.parent().unwrap().is_same_as(child)));
+}
+
+#[test]
+fn deep() {
+    // Deep enough to overflow the stack if comparing, hashing or cloning recursed once per level.
+    const DEPTH: usize = 100_000;
+    let chain = || (0..DEPTH).rev().fold(Node::builder(DEPTH), |child, n| Node::builder(n).child(child)).build();
+    // A chain where every Node also has a leaf, so that every level has more than one child.
+    let comb = |last| {
+        (0..DEPTH).rev()
+            .fold(Node::builder(last), |child, n| Node::builder(n).child(child).child(Node::builder(n)))
+            .build()
+    };
+
+    let (a, b) = (chain(), chain());
+    assert!(a.par_eq(&b));
+    assert_eq!(a.par_hash(), b.par_hash());
+    let cloned = a.clone_deep_parallel();
+    assert!(cloned.par_eq(&a));
+    assert_eq!(cloned, a);
+
+    let (a, b, c) = (comb(DEPTH), comb(DEPTH), comb(0));
+    assert!(a.par_eq(&b));
+    assert_eq!(a.par_hash(), b.par_hash());
+    assert!(!a.par_eq(&c));
+    assert_ne!(a.par_hash(), c.par_hash());
+    let cloned = a.clone_deep_parallel();
+    assert!(cloned.par_eq(&a));
+    assert_eq!(cloned, a);
+}
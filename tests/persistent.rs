@@ -0,0 +1,44 @@
+#![cfg(feature = "persistent")]
+use tree_struct::{persistent, Tree};
+
+#[test]
+fn persistent() {
+    let base = persistent::Tree::from(&Tree::<char>::from_brackets("a(b(d)c)").unwrap());
+    assert_eq!(base.len(), 4);
+    let contents = |tree: &persistent::Tree<char>| tree.iter_dfs().map(|n| n.content).collect::<String>();
+    assert_eq!(contents(&base), "abdc");
+
+    let set = base.set_at(&[0, 0], 'D').unwrap();
+    assert_eq!(contents(&set), "abDc");
+    assert_eq!(contents(&base), "abdc");
+    // Only the path to the edited Node was copied.
+    assert!(!set.root().is_same_as(base.root()));
+    assert!(!set.get(&[0]).unwrap().is_same_as(base.get(&[0]).unwrap()));
+    assert!(set.get(&[1]).unwrap().is_same_as(base.get(&[1]).unwrap()));
+
+    let inserted = set.insert_at(&[1, 0], persistent::Tree::new('e').insert_at(&[0], persistent::Tree::new('f')).unwrap()).unwrap();
+    assert_eq!(contents(&inserted), "abDcef");
+    assert_eq!(inserted.len(), 6);
+    assert_eq!(inserted.get(&[1]).unwrap().len(), 3);
+    assert!(inserted.get(&[0]).unwrap().is_same_as(set.get(&[0]).unwrap()));
+
+    let (removed, subtree) = inserted.remove_at(&[0]).unwrap();
+    assert_eq!(contents(&removed), "acef");
+    assert_eq!(removed.len(), 4);
+    assert_eq!(subtree.len(), 2);
+    assert!(subtree.root().is_same_as(inserted.get(&[0]).unwrap()));
+    assert_eq!(removed.set_at(&[], 'x').unwrap().root().content, 'x');
+
+    // Invalid paths.
+    assert!(base.set_at(&[2], 'x').is_none());
+    assert!(base.insert_at(&[], persistent::Tree::new('x')).is_none());
+    assert!(base.insert_at(&[3], persistent::Tree::new('x')).is_none());
+    assert!(base.insert_at(&[0, 2], persistent::Tree::new('x')).is_none());
+    assert!(base.remove_at(&[]).is_none());
+    assert!(base.remove_at(&[0, 1]).is_none());
+
+    // Equal versions compare equal even if they share no Nodes.
+    assert_eq!(removed.insert_at(&[0], subtree).unwrap(), inserted);
+    drop(base);
+    assert_eq!(contents(&set), "abDc");
+}
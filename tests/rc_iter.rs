@@ -1,5 +1,9 @@
 #![cfg(feature = "rc")]
+// With `arc`, the thread-safe version is tested instead (both share the same implementation).
+#[cfg(not(feature = "arc"))]
 use tree_struct::rc::Node;
+#[cfg(feature = "arc")]
+use tree_struct::sync::Node;
 
 #[test]
 fn breadth_first() {
@@ -1,5 +1,10 @@
 #![cfg(feature = "rc")]
-use tree_struct::rc::Node;
+// With `arc`, the thread-safe version is tested instead (both share the same implementation).
+#[cfg(not(feature = "arc"))]
+use tree_struct::rc::{MapError, Node, Tree, WeakNode};
+#[cfg(feature = "arc")]
+use tree_struct::sync::{MapError, Node, Tree, WeakNode};
+use tree_struct::TreeError;
 
 #[test]
 fn siblings() {
@@ -89,6 +94,196 @@ fn detach() {
     );
 }
 
+#[test]
+fn detach_descendant() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .build();
+    let other = Node::builder("other").child(Node::builder("other child")).build();
+
+    assert!(tree.detach_descendant(&tree.root()).is_none());
+    assert!(tree.detach_descendant(&other.root().children()[0]).is_none());
+
+    let target = &tree.root().children()[0].children()[0];
+    let detached = tree.detach_descendant(target).unwrap();
+    assert!(detached.root().is_same_as(target));
+    assert!(tree.detach_descendant(target).is_none());
+    assert_eq!(
+        tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child b"]
+    );
+}
+
+#[test]
+fn try_variants() {
+    let tree = Node::builder("parent").child(Node::builder("child a")).build();
+
+    assert_eq!(tree.try_detach_descendant(&tree.root()).unwrap_err(), TreeError::NotADescendant);
+    let (error, child) = tree.root().try_insert_child(Node::builder("child b").build(), 2).unwrap_err();
+    assert_eq!(error, TreeError::IndexOutOfBounds { index: 2, len: 1 });
+    tree.root().try_insert_child(child, 0).unwrap();
+
+    let target = tree.root().children()[0].ref_clone();
+    assert!(tree.try_detach_descendant(&target).unwrap().root().is_same_as(&target));
+    assert_eq!(tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec!["parent", "child a"]);
+}
+
+#[test]
+fn cycle() {
+    let tree = Node::builder("parent").child(Node::builder("child")).build();
+    let child = tree.root().children()[0].ref_clone();
+
+    let (error, tree) = child.try_append_child(tree).unwrap_err();
+    assert_eq!(error, TreeError::WouldCreateCycle);
+    let (error, tree) = tree.root().try_insert_child(tree, 0).unwrap_err();
+    assert_eq!(error, TreeError::WouldCreateCycle);
+
+    child.try_append_child(Node::builder("grandchild").build()).unwrap();
+    assert_eq!(tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec!["parent", "child", "grandchild"]);
+}
+
+#[test]
+fn try_content() {
+    let tree = Node::builder("parent").child(Node::builder("child")).build();
+    let root = tree.root();
+
+    let guard = root.content_mut();
+    assert!(root.try_content().is_none());
+    assert!(root.try_content_mut().is_none());
+    #[cfg(feature = "arc")]
+    assert!(root.try_content_for(std::time::Duration::from_millis(10)).is_none());
+    drop(guard);
+
+    let guard = root.try_content().unwrap();
+    assert!(root.try_content_mut().is_none());
+    assert_eq!(*guard, "parent");
+    drop(guard);
+    *root.try_content_mut().unwrap() = "root";
+    assert_eq!(*root.content(), "root");
+}
+
+#[test]
+fn weak_node() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b"))
+        .build();
+    let weak = tree.root().children()[1].downgrade();
+    assert!(WeakNode::<&str>::new().upgrade().is_none());
+    assert!(weak.upgrade().unwrap().is_same_as(&tree.root().children()[1]));
+
+    // The detached subtree is kept alive by its Tree, not by the WeakNode.
+    let detached = weak.upgrade().unwrap().detach().unwrap();
+    assert_eq!(*weak.clone().upgrade().unwrap().content(), "child b");
+    drop(detached);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+#[cfg(feature = "arc")]
+fn threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let tree = Node::builder(0).build();
+    let detached = AtomicUsize::new(0);
+
+    std::thread::scope(|s| {
+        for i in 1..=4 {
+            let root = tree.root();
+            let detached = &detached;
+            s.spawn(move || for _ in 0..100 {
+                root.append_child(Node::builder(i).build());
+                // Another thread may detach the same child first.
+                if let Some(child) = root.children().first() {
+                    if child.detach().is_some() {
+                        detached.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(tree.root().children().len(), 400 - detached.into_inner());
+    assert!(tree.root().children().iter().all(|child| child.parent().unwrap().is_same_as(&tree.root())));
+}
+
+#[test]
+fn validate() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    assert_eq!(tree.validate(), Ok(()));
+    assert_eq!(tree.repair(), 0);
+
+    let detached = tree.root().children()[0].detach().unwrap();
+    assert_eq!(detached.validate(), Ok(()));
+    tree.root().append_child(detached);
+    assert_eq!(tree.validate(), Ok(()));
+    assert_eq!(tree.repair(), 0);
+}
+
+#[test]
+fn display_compact() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    assert_eq!(tree.display_compact().to_string(), "parent(child a(child c),child b)");
+    assert_eq!(tree.root().children()[1].display_compact().to_string(), "child b");
+}
+
+#[test]
+fn hash() {
+    use std::hash::{BuildHasher, RandomState};
+    let build = || Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    let state = RandomState::new();
+
+    assert_eq!(build(), build());
+    assert_eq!(state.hash_one(build()), state.hash_one(build()));
+    assert_ne!(build(), Node::builder("parent").child(Node::builder("child a")).build());
+    assert_ne!(state.hash_one(build()), state.hash_one(Node::builder("parent").build()));
+}
+
+#[test]
+fn ord() {
+    let a = Node::builder(1).child(Node::builder(2)).child(Node::builder(3)).build();
+    let b = Node::builder(1).child(Node::builder(2).child(Node::builder(3))).build();
+    let c = Node::builder(1).child(Node::builder(4)).build();
+    let d = Node::builder(1).child(Node::builder(2)).build();
+
+    assert!(d < b && b < a && a < c);
+    assert_eq!(a.cmp(&a.root().clone_deep()), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn remove_child() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b")
+            .child(Node::builder("child c")))
+        .build();
+
+    assert!(tree.root().remove_child(2).is_none());
+    let target = tree.root().children()[1].ref_clone();
+    let removed = tree.root().remove_child(1).unwrap();
+    assert!(removed.root().is_same_as(&target));
+    assert!(target.parent().is_none());
+    assert!(removed.root().children()[0].parent().unwrap().is_same_as(&target));
+    assert_eq!(
+        tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child a"]
+    );
+}
+
 #[test]
 fn append_child() {
     let tree = Node::builder("parent")
@@ -135,9 +330,348 @@ fn append_child() {
     );
 }
 
+#[test]
+fn find() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let found = tree.find(|c| c.ends_with('c')).unwrap();
+    assert!(found.is_same_as(&tree.root().children()[0].children()[0]));
+    // The first match in BFS order is returned.
+    let found = tree.find(|c| c.starts_with("child")).unwrap();
+    assert!(found.is_same_as(&tree.root().children()[0]));
+    assert!(tree.find(|c| c.is_empty()).is_none());
+}
+
+#[test]
+fn read_only() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a").child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+    let view = tree.read_only();
+
+    assert_eq!(
+        view.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child b", "child c"]
+    );
+    let child_c = view.find(|c| *c == "child c").unwrap();
+    assert!(child_c.parent().unwrap().is_same_as(&view.root().children()[0]));
+    assert_eq!(child_c.path_to_root().len(), 3);
+    assert_eq!(*view.root().children()[0].next_sibling().unwrap().content(), "child b");
+
+    // Changes to the Tree are seen by the view.
+    *tree.root().content_mut() = "root";
+    assert_eq!(*view.root().content(), "root");
+    assert!(tree.root().read_only().is_same_as(&view.root()));
+}
+
+#[test]
+fn cow() {
+    let mut tree = Node::builder("parent")
+        .child(Node::builder("child a").child(Node::builder("grandchild")))
+        .build()
+        .into_cow();
+    assert!(!tree.is_shared());
+    let root = tree.root() as *const _;
+    // Not shared, so it is not copied.
+    tree.root_mut().append_child(Node::builder("child b").build().into_cow());
+    assert!(std::ptr::eq(tree.root(), root));
+
+    let snapshot = tree.clone();
+    assert!(tree.is_shared() && snapshot.is_shared());
+    assert!(snapshot.root().is_same_as(tree.root()));
+
+    // Only the path to the modified Node is copied.
+    *tree.get_mut(&[0, 0]).unwrap().content_mut() = "GRANDCHILD";
+    assert!(!tree.is_shared() && !snapshot.is_shared());
+    assert!(!tree.get(&[0]).unwrap().is_same_as(snapshot.get(&[0]).unwrap()));
+    assert!(tree.get(&[1]).unwrap().is_same_as(snapshot.get(&[1]).unwrap()));
+    assert!(tree.get_mut(&[2]).is_none());
+
+    let removed = tree.root_mut().remove_child(1).unwrap();
+    assert!(removed.root().is_same_as(snapshot.get(&[1]).unwrap()));
+    // The inserted subtree is shared too.
+    tree.get_mut(&[0]).unwrap().insert_child(snapshot.clone(), 0);
+    assert!(tree.get(&[0, 0]).unwrap().is_same_as(snapshot.root()));
+
+    let contents = |tree: Tree<&'static str>| tree.iter_dfs().map(|n| *n.content()).collect::<Vec<_>>();
+    assert_eq!(
+        contents(tree.into_tree()),
+        vec!["parent", "child a", "parent", "child a", "grandchild", "child b", "GRANDCHILD"]
+    );
+    assert_eq!(contents(snapshot.into_tree()), vec!["parent", "child a", "grandchild", "child b"]);
+}
+
+#[test]
+fn for_each_child() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b"))
+        .build();
+    let root = tree.root();
+
+    let mut contents = vec![];
+    root.for_each_child(|child| contents.push(*child.content()));
+    assert_eq!(contents, vec!["child a", "child b"]);
+    let children = root.children_ref();
+    assert!(children.iter().zip(root.children().iter()).all(|(a, b)| a.is_same_as(b)));
+    assert!(children[0].next_sibling().unwrap().is_same_as(&children[1]));
+}
+
+#[test]
+fn clear_children() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("a").child(Node::builder("aa")))
+        .child(Node::builder("b"))
+        .build();
+
+    let children = tree.root().clear_children();
+    assert!(tree.root().children().is_empty());
+    assert_eq!(
+        children.iter().map(|c| *c.root().content()).collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+    assert!(children.iter().all(|c| c.root().parent().is_none()));
+    let aa = children[0].root().children()[0].ref_clone();
+    assert!(aa.parent().unwrap().is_same_as(&children[0].root()));
+}
+
+#[test]
+fn swap_children() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d")))
+        .child(Node::builder("child b"))
+        .child(Node::builder("child c"))
+        .build();
+    let child_a = tree.root().children()[0].ref_clone();
+
+    tree.root().swap_children(0, 2);
+    assert_eq!(
+        tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child c", "child b", "child a", "child d"]
+    );
+    assert!(tree.root().children()[2].is_same_as(&child_a));
+    assert!(child_a.parent().unwrap().is_same_as(&tree.root()));
+}
+
+#[test]
+fn swap_content() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let a = tree.root().children()[0].ref_clone();
+    let c = a.children()[0].ref_clone();
+    a.swap_content(&c);
+    c.swap_content(&tree.root().children()[1]);
+    a.swap_content(&a);
+    assert_eq!(
+        tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child c", "child a", "child b"]
+    );
+    assert!(c.parent().unwrap().is_same_as(&a));
+}
+
+#[test]
+fn sort_children() {
+    let tree = Node::builder(0)
+        .child(Node::builder(3))
+        .child(Node::builder(1)
+            .child(Node::builder(5))
+            .child(Node::builder(4)))
+        .child(Node::builder(2))
+        .build();
+
+    tree.root().sort_children_by(|a, b| a.cmp(b));
+    assert_eq!(tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec![0, 1, 2, 3, 5, 4]);
+    assert!(tree.root().children()[0].parent().unwrap().is_same_as(&tree.root()));
+}
+
+#[test]
+fn retain() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child d"))
+            .child(Node::builder("remove e")))
+        .child(Node::builder("remove b")
+            .child(Node::builder("child f")))
+        .child(Node::builder("child c"))
+        .build();
+    let held = tree.root().children()[1].ref_clone();
+
+    let removed = tree.retain(|content| !content.starts_with("remove"));
+    assert_eq!(
+        tree.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child c", "child d"]
+    );
+    assert_eq!(
+        removed.iter().map(|t| *t.root().content()).collect::<Vec<_>>(),
+        vec!["remove b", "remove e"]
+    );
+    assert!(removed[0].root().is_same_as(&held));
+    assert!(held.parent().is_none());
+}
+
+#[test]
+fn path_to_root() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let target = &tree.root().children()[0].children()[0];
+    let path = target.path_to_root();
+    assert_eq!(
+        path.iter().map(|n| *n.content()).collect::<Vec<_>>(),
+        vec!["child c", "child a", "parent"]
+    );
+    assert!(path[0].is_same_as(target));
+    assert!(path[2].is_same_as(&tree.root()));
+    assert_eq!(tree.root().path_to_root().len(), 1);
+}
+
+#[test]
+fn map() {
+    let tree = Node::builder(1)
+        .child(Node::builder(2)
+            .child(Node::builder(4)))
+        .child(Node::builder(3))
+        .build();
+
+    let mapped = tree.clone().map(|n| n * 10).unwrap();
+    assert_eq!(
+        mapped,
+        Node::builder(10)
+            .child(Node::builder(20)
+                .child(Node::builder(40)))
+            .child(Node::builder(30))
+            .build()
+    );
+    // Parents are set up properly.
+    let child = &mapped.root().children()[0].children()[0];
+    assert!(child.parent().unwrap().parent().unwrap().is_same_as(&mapped.root()));
+
+    assert_eq!(tree.clone().try_map(|n| if n < 5 { Ok(n) } else { Err(n) }), Ok(tree.clone()));
+    assert_eq!(tree.clone().try_map(|n| if n < 3 { Ok(n) } else { Err(n) }), Err(MapError::Content(4)));
+
+    // Nodes that are still referenced somewhere else can't be taken apart.
+    let child = tree.root().children()[0].children()[0].ref_clone();
+    let (error, tree) = tree.map(|n| n * 10).unwrap_err();
+    assert_eq!(error, TreeError::SharedNode);
+    drop(child);
+    let weak = tree.root().children()[1].downgrade();
+    let Err(MapError::Tree(TreeError::SharedNode, tree)) = tree.try_map(Ok::<_, ()>) else { panic!() };
+    drop(weak);
+    assert_eq!(tree.map(|n| n * 10).unwrap(), mapped);
+}
+
 #[test]
 fn reference_count() {
-    todo!()
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a"))
+        .child(Node::builder("child b"))
+        .build();
+
+    let root = tree.root();
+    assert_eq!(root.strong_count(), 2);
+    assert_eq!(root.weak_count(), 2);
+
+    let child = root.children()[0].ref_clone();
+    assert_eq!(child.strong_count(), 2);
+    assert_eq!(child.weak_count(), 0);
+    let weak = child.downgrade();
+    assert_eq!(child.weak_count(), 1);
+
+    let detached = child.detach().unwrap();
+    assert_eq!(root.weak_count(), 1);
+    drop(child);
+    assert_eq!(detached.root().strong_count(), 2);
+    drop(detached);
+    assert!(weak.upgrade().is_none());
 }
 
 // Doesn't need Dangling test. No Nodes can dangle because user can't (shouldn't) get a raw pointer to a Node.
+
+#[test]
+fn display() {
+    use tree_struct::{IndentStyle, TreeFormatter};
+    let rc_tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c"))
+            .child(Node::builder("child d\nsecond line")))
+        .child(Node::builder("child b")
+            .child(Node::builder("child e")))
+        .build();
+    let tree = tree_struct::Tree::from_indented("parent\n    child a\n        child c\n        child d\n    child b\n        child e").unwrap();
+    let mut tree = tree.map(|content| content.to_string());
+    let d = tree.iter_dfs().find(|n| n.content == "child d").unwrap().ptr();
+    tree.borrow_descendant(d).unwrap().replace_content(String::from("child d\nsecond line"));
+
+    assert_eq!(rc_tree.to_string(), tree.to_string());
+    for formatter in [TreeFormatter::new().style(IndentStyle::Ascii).indent(2), TreeFormatter::new().max_depth(1)] {
+        assert_eq!(rc_tree.root().format(&formatter).to_string(), formatter.format(tree.root()).to_string());
+    }
+    assert_eq!(rc_tree.root().children()[1].format(&TreeFormatter::new()).to_string(), "child b\n└── child e\n");
+}
+
+#[test]
+fn get() {
+    let tree = Node::builder('a')
+        .child(Node::builder('b'))
+        .child(Node::builder('c')
+            .child(Node::builder('d')))
+        .build();
+    let root = tree.root();
+
+    assert!(root.get(0).unwrap().is_same_as(&root.children()[0]));
+    assert!(root.first_child().unwrap().is_same_as(&root.children()[0]));
+    assert!(root.last_child().unwrap().is_same_as(&root.children()[1]));
+    assert!(root.get(2).is_none());
+    let d = root.get(1).unwrap().get(0).unwrap();
+    assert_eq!(*d.content(), 'd');
+    assert!(d.first_child().is_none() && d.last_child().is_none());
+    // Only the returned child's counter is increased.
+    assert_eq!(root.get(1).unwrap().strong_count(), 2);
+    assert_eq!(d.strong_count(), 2);
+}
+
+#[test]
+#[cfg(feature = "arc")]
+fn rc_and_sync() {
+    use tree_struct::{rc, sync};
+    // Both versions can be used in the same build, and have the same API.
+    let rc_tree = rc::Node::builder('a').child(rc::Node::builder('b')).build();
+    let sync_tree = sync::Node::builder('a').child(sync::Node::builder('b')).build();
+    assert_eq!(rc_tree.to_string(), sync_tree.to_string());
+
+    let root = sync_tree.root();
+    std::thread::spawn(move || root.append_child(sync::Node::builder('c').build())).join().unwrap();
+    assert_eq!(sync_tree.display_compact().to_string(), "a(b,c)");
+    assert_eq!(rc_tree.display_compact().to_string(), "a(b)");
+}
+
+#[test]
+fn deep() {
+    // Deep enough to overflow the stack if building, converting or dropping recursed.
+    const DEPTH: usize = 100_000;
+    let tree = (0..DEPTH).rev().fold(Node::builder(DEPTH), |child, n| Node::builder(n).child(child)).build();
+    let tree = tree.map(|n| n + 1).unwrap();
+    let mut middle = tree.root();
+    for _ in 0..DEPTH / 2 {
+        middle = middle.children()[0].ref_clone();
+    }
+    drop(tree);
+
+    // A Node that is still referenced keeps its subtree.
+    assert_eq!(*middle.content(), DEPTH / 2 + 1);
+    assert!(middle.parent().is_none());
+    assert_eq!(*middle.children()[0].content(), DEPTH / 2 + 2);
+}
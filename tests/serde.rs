@@ -0,0 +1,69 @@
+#![cfg(feature = "serde")]
+use tree_struct::{Node, NodeBuilder, Tree};
+
+#[test]
+fn roundtrip() {
+    let tree = Node::builder("parent")
+        .child(Node::builder("child a")
+            .child(Node::builder("child c")))
+        .child(Node::builder("child b"))
+        .build();
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(
+        json,
+        r#"{"content":"parent","children":[{"content":"child a","children":[{"content":"child c","children":[]}]},{"content":"child b","children":[]}]}"#
+    );
+
+    let deserialized = serde_json::from_str::<Tree<String>>(&json).unwrap();
+    assert_eq!(
+        deserialized.iter_bfs().map(|n| n.content.as_str()).collect::<Vec<_>>(),
+        vec!["parent", "child a", "child b", "child c"]
+    );
+    let child = deserialized.root().children()[0];
+    assert!(child.children()[0].parent().unwrap().is_same_as(child));
+
+    let builder = serde_json::from_str::<NodeBuilder<String>>(&json).unwrap();
+    assert_eq!(serde_json::to_string(&builder).unwrap(), json);
+}
+
+#[cfg(feature = "rc")]
+#[test]
+fn rc_roundtrip() {
+    use tree_struct::rc;
+
+    let tree = rc::Node::builder(1)
+        .child(rc::Node::builder(2)
+            .child(rc::Node::builder(3)))
+        .build();
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, r#"{"content":1,"children":[{"content":2,"children":[{"content":3,"children":[]}]}]}"#);
+
+    let deserialized = serde_json::from_str::<rc::Tree<i32>>(&json).unwrap();
+    assert_eq!(deserialized.iter_bfs().map(|n| *n.content()).collect::<Vec<_>>(), vec![1, 2, 3]);
+    let child = deserialized.root().children()[0].ref_clone();
+    assert!(child.parent().unwrap().is_same_as(&deserialized.root()));
+    #[cfg(feature = "arc")]
+    {
+        let deserialized = serde_json::from_str::<tree_struct::sync::Tree<i32>>(&json).unwrap();
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    }
+}
+
+#[test]
+fn max_depth() {
+    let deep = (0..50).rev().fold(Node::builder(50), |child, i| Node::builder(i).child(child)).build();
+    let json = serde_json::to_string(&deep).unwrap();
+
+    let error = Tree::<i32>::deserialize_with_max_depth(&mut serde_json::Deserializer::from_str(&json), 49).unwrap_err();
+    assert!(error.to_string().contains("maximum depth of 49"));
+    let tree = Tree::<i32>::deserialize_with_max_depth(&mut serde_json::Deserializer::from_str(&json), 50).unwrap();
+    assert_eq!(tree.into_vec_dfs(), (0..=50).collect::<Vec<_>>());
+
+    // Also check the sequence representation, and unknown fields.
+    let tree = serde_json::from_str::<Tree<i32>>("[1, [[2, []]]]").unwrap();
+    assert_eq!(tree.into_vec_bfs(), vec![1, 2]);
+    assert!(serde_json::from_str::<Tree<i32>>(r#"{"content":1,"children":[],"other":0}"#).is_err());
+    assert!(serde_json::from_str::<Tree<i32>>(r#"{"content":1}"#).is_err());
+}
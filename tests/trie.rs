@@ -0,0 +1,49 @@
+#![cfg(feature = "trie")]
+use tree_struct::trie::Trie;
+
+#[test]
+fn trie() {
+    let mut trie = ["tea", "ten", "to", "inn"]
+        .into_iter()
+        .map(|word| (word.chars(), word.len()))
+        .collect::<Trie<char, usize>>();
+    assert_eq!(trie.len(), 4);
+    assert_eq!(trie.insert("i".chars(), 1), None);
+    assert_eq!(trie.insert("to".chars(), 20), Some(2));
+    assert_eq!(trie.len(), 5);
+
+    assert_eq!(trie.get(&['t', 'o']), Some(&20));
+    assert_eq!(trie.get(&['t']), None);
+    assert!(trie.contains(&['i']) && !trie.contains(&['i', 'n']));
+    *trie.get_mut(&['i', 'n', 'n']).unwrap() += 100;
+
+    let words = |trie: &Trie<char, usize>, prefix: &[char]| {
+        trie.iter_prefix(prefix).map(|(path, value)| (path.into_iter().collect::<String>(), *value)).collect::<Vec<_>>()
+    };
+    assert_eq!(words(&trie, &[]), [("i".into(), 1), ("inn".into(), 103), ("tea".into(), 3), ("ten".into(), 3), ("to".into(), 20)]);
+    assert_eq!(words(&trie, &['t', 'e']), [("tea".into(), 3), ("ten".into(), 3)]);
+    assert_eq!(words(&trie, &['i']), [("i".into(), 1), ("inn".into(), 103)]);
+    assert!(words(&trie, &['x']).is_empty());
+    assert_eq!(trie.iter().count(), 5);
+
+    // Removing prunes the Nodes that are left with no value and no children.
+    let nodes = trie.as_tree().len();
+    assert_eq!(trie.remove(&['t', 'e']), None);
+    assert_eq!(trie.remove(&['t', 'e', 'n']), Some(3));
+    assert_eq!(trie.as_tree().len(), nodes - 1);
+    assert_eq!(trie.remove(&['i', 'n', 'n']), Some(103));
+    assert_eq!(trie.as_tree().len(), nodes - 3);
+    // 'i' keeps its Node because it has a value.
+    assert_eq!(trie.get(&['i']), Some(&1));
+    assert_eq!(trie.remove(&['t', 'e', 'a']), Some(3));
+    assert_eq!(trie.remove(&['t', 'o']), Some(20));
+    assert_eq!(trie.remove(&['i']), Some(1));
+    assert!(trie.is_empty());
+    assert_eq!(trie.as_tree().len(), 1);
+
+    // The empty path is the root.
+    trie.insert(std::iter::empty(), 0);
+    assert_eq!(trie.get(&[]), Some(&0));
+    assert_eq!(trie.remove(&[]), Some(0));
+    assert_eq!(trie, Trie::new());
+}
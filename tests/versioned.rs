@@ -0,0 +1,99 @@
+use tree_struct::{ChangeKind, Node, Version, VersionedTree};
+
+#[test]
+fn changes_since() {
+    let mut tree = VersionedTree::new(
+        Node::builder("root")
+            .child(Node::builder("a").child(Node::builder("aa")))
+            .child(Node::builder("b"))
+            .build(),
+        7,
+    );
+    assert_eq!(tree.version(), Version { counter: 0, actor: 7 });
+    assert!(tree.changes_since(Version::default()).is_empty());
+
+    *tree.content_mut(&[0, 0]).unwrap() = "AA";
+    let v1 = tree.version();
+    tree.append_child(&[0], Node::builder("ab").child(Node::builder("aba")).build()).unwrap();
+    assert_eq!(tree.detach(&[1]).unwrap().root().content, "b");
+    assert!(tree.content_mut(&[5]).is_none());
+    assert!(tree.detach(&[]).is_none());
+
+    assert_eq!(
+        tree.changes_since(Version::default()),
+        vec![
+            (vec![0, 0], ChangeKind::Modified),
+            (vec![0, 1], ChangeKind::Inserted),
+            (vec![1], ChangeKind::Removed),
+        ]
+    );
+    assert_eq!(
+        tree.changes_since(v1),
+        vec![(vec![0, 1], ChangeKind::Inserted), (vec![1], ChangeKind::Removed)]
+    );
+    assert!(tree.changes_since(tree.version()).is_empty());
+
+    // Changes made after observing a remote version come after it.
+    let remote = Version { counter: 10, actor: 3 };
+    tree.observe(remote);
+    *tree.content_mut(&[]).unwrap() = "ROOT";
+    assert_eq!(tree.changes_since(remote), vec![(vec![], ChangeKind::Modified)]);
+    assert_eq!(tree.tree().root().content.modified(), Version { counter: 11, actor: 7 });
+
+    assert_eq!(
+        tree.into_tree().iter_bfs().map(|n| n.content).collect::<Vec<_>>(),
+        vec!["ROOT", "a", "AA", "ab", "aba"]
+    );
+}
+
+#[test]
+fn changes_replay() {
+    let mut tree = VersionedTree::new(Node::builder("r").child(Node::builder("x")).child(Node::builder("y")).build(), 1);
+    let start = tree.version();
+
+    // The paths are the ones at the time of each change.
+    *tree.content_mut(&[1]).unwrap() = "Y";
+    tree.detach(&[0]).unwrap();
+    assert_eq!(
+        tree.changes_since(start),
+        vec![(vec![1], ChangeKind::Modified), (vec![0], ChangeKind::Removed)]
+    );
+
+    // A Node inserted and removed after `start` is left out, along with the changes to it and its descendants.
+    let before = tree.version();
+    tree.append_child(&[], Node::builder("z").build()).unwrap();
+    tree.append_child(&[1], Node::builder("za").build()).unwrap();
+    *tree.content_mut(&[1, 0]).unwrap() = "ZA";
+    tree.detach(&[1]).unwrap();
+    assert_eq!(tree.changes_since(before), vec![]);
+    assert_eq!(tree.changes_since(start).len(), 2);
+
+    // The changes after a left out Node are shifted as if it never existed.
+    // The tree is r(Y) here.
+    let before = tree.version();
+    tree.append_child(&[], Node::builder("a").build()).unwrap(); // [1]
+    tree.append_child(&[], Node::builder("b").build()).unwrap(); // [2]
+    *tree.content_mut(&[2]).unwrap() = "B";
+    tree.detach(&[0]).unwrap(); // Y, which existed before
+    *tree.content_mut(&[1]).unwrap() = "BB";
+    tree.detach(&[0]).unwrap(); // a
+    tree.append_child(&[], Node::builder("c").build()).unwrap(); // [1]
+    assert_eq!(
+        tree.tree().root().children_iter().map(|n| n.content.content).collect::<Vec<_>>(),
+        ["BB", "c"]
+    );
+    assert_eq!(
+        tree.changes_since(before),
+        vec![
+            (vec![1], ChangeKind::Inserted),
+            (vec![1], ChangeKind::Modified),
+            (vec![0], ChangeKind::Removed),
+            (vec![0], ChangeKind::Modified),
+            (vec![1], ChangeKind::Inserted),
+        ]
+    );
+    // A Node that existed before `since` is still listed when removed.
+    let middle = tree.version();
+    tree.detach(&[1]).unwrap();
+    assert_eq!(tree.changes_since(middle), vec![(vec![1], ChangeKind::Removed)]);
+}